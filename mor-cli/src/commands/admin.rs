@@ -0,0 +1,161 @@
+// Hands a pool over to a new admin in two steps: propose, then accept
+
+use anchor_lang::InstructionData;
+use clap::Subcommand;
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::instruction;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{
+    CLUSTER_URL, MINING_POOL_SEED, PROGRAM_ID, load_payer_keypair, parse_pool_maker,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum AdminCommand {
+    /// Propose a new admin for a pool you administer (it takes over once it accepts)
+    Propose {
+        /// Wallet or multisig to hand the pool to
+        #[arg(required_unless_present = "cancel", conflicts_with = "cancel")]
+        new_admin: Option<String>,
+        /// Token mint address
+        #[arg(long)]
+        token_mint: String,
+        /// Maker of a pool handed over to you (defaults to your keypair)
+        #[arg(long, value_name = "PUBKEY")]
+        pool_maker: Option<String>,
+        /// Withdraw the pending proposal
+        #[arg(long)]
+        cancel: bool,
+    },
+    /// Accept the admin role of a pool proposed to your keypair
+    Accept {
+        /// Token mint address
+        #[arg(long)]
+        token_mint: String,
+        /// Wallet that created the pool
+        #[arg(long, value_name = "PUBKEY")]
+        pool_maker: String,
+    },
+}
+
+pub fn handle_admin(command: AdminCommand) {
+    let admin = load_payer_keypair().unwrap();
+
+    let (token_mint, pool_maker) = match &command {
+        AdminCommand::Propose {
+            token_mint,
+            pool_maker,
+            ..
+        } => (token_mint, pool_maker.as_deref()),
+        AdminCommand::Accept {
+            token_mint,
+            pool_maker,
+        } => (token_mint, Some(pool_maker.as_str())),
+    };
+
+    // Parse the token mint address
+    let mint_pubkey = match token_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid token mint address:".red(), e);
+            return;
+        }
+    };
+
+    // The pool stays derived from its maker after it is handed over
+    let pool_maker = match parse_pool_maker(pool_maker, &admin.pubkey()) {
+        Ok(pool_maker) => pool_maker,
+        Err(e) => {
+            println!("{}", e.red());
+            return;
+        }
+    };
+
+    // Derive mining pool PDA
+    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+        &[MINING_POOL_SEED, pool_maker.as_ref(), mint_pubkey.as_ref()],
+        &PROGRAM_ID,
+    );
+
+    let instruction_data = match &command {
+        AdminCommand::Propose { new_admin, .. } => {
+            // No new admin withdraws the proposal
+            let new_admin = match new_admin.as_deref().map(str::parse::<Pubkey>).transpose() {
+                Ok(new_admin) => new_admin.unwrap_or_default(),
+                Err(e) => {
+                    println!("{} {}", "Invalid new admin address:".red(), e);
+                    return;
+                }
+            };
+            if new_admin == Pubkey::default() {
+                println!("{}", "Withdrawing the pending admin proposal".yellow());
+            } else {
+                println!(
+                    "{} {} {}",
+                    "Proposing".cyan(),
+                    new_admin,
+                    "as admin, it takes over once it runs pools admin accept".cyan()
+                );
+            }
+            instruction::ProposeAdmin { new_admin }.data()
+        }
+        AdminCommand::Accept { .. } => instruction::AcceptAdmin {}.data(),
+    };
+
+    let accounts = vec![
+        AccountMeta::new_readonly(admin.pubkey(), true), // admin or new_admin
+        AccountMeta::new(mining_pool_pda, false),        // mining_pool_pda
+    ];
+
+    send(&admin, accounts, instruction_data);
+}
+
+/// Signs and sends a single instruction on the base layer
+fn send(admin: &Keypair, accounts: Vec<AccountMeta>, data: Vec<u8>) {
+    let client = RpcClient::new(CLUSTER_URL);
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data,
+    };
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[admin], blockhash);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => println!(
+            "{} {}{}{}",
+            "Transaction sent successfully:".green(),
+            "https://explorer.solana.com/tx/".to_string().cyan(),
+            sig.to_string().cyan(),
+            "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                .to_string()
+                .cyan()
+        ),
+        Err(e) => print_transaction_error(&e),
+    }
+}
@@ -0,0 +1,244 @@
+// Builds the Merkle allowlist of a private pool and sets its root
+
+use anchor_lang::InstructionData;
+use clap::Subcommand;
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::helpers::utils::{allowlist_proof, allowlist_root, verify_allowlist_proof};
+use mor_core::instruction;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{
+    CLUSTER_URL, MINING_POOL_SEED, PROGRAM_ID, format_hash, load_payer_keypair, parse_hash,
+    parse_pool_maker,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum AllowlistCommand {
+    /// Compute the allowlist root of the wallets in a CSV and write their proofs
+    Build {
+        /// CSV whose first column holds the allowed wallet addresses (a header row is skipped)
+        #[arg(long, value_name = "PATH")]
+        csv: PathBuf,
+        /// Where to write the proofs, one wallet per line, for create-account --allowlist
+        #[arg(long, value_name = "PATH", default_value = "allowlist-proofs.txt")]
+        out: PathBuf,
+    },
+    /// Restrict new miners of a pool you created to an allowlist, or open it again
+    Set {
+        /// Token mint address
+        #[arg(long)]
+        token_mint: String,
+        /// Maker of a pool handed over to you (defaults to your keypair)
+        #[arg(long, value_name = "PUBKEY")]
+        pool_maker: Option<String>,
+        /// Allowlist root printed by allowlist build, as hex
+        #[arg(long, required_unless_present = "clear", conflicts_with = "clear")]
+        root: Option<String>,
+        /// Let any wallet create a miner again
+        #[arg(long)]
+        clear: bool,
+    },
+}
+
+pub fn handle_allowlist(command: AllowlistCommand) {
+    match command {
+        AllowlistCommand::Build { csv, out } => {
+            if let Err(e) = build_allowlist(&csv, &out) {
+                println!("{}", e.red());
+            }
+        }
+        AllowlistCommand::Set {
+            token_mint,
+            pool_maker,
+            root,
+            clear: _,
+        } => set_allowlist(token_mint, pool_maker, root),
+    }
+}
+
+/// Reads the wallets of a CSV's first column, sorted and deduplicated so the
+/// same wallets always give the same root
+fn read_allowlist_csv(path: &Path) -> Result<Vec<Pubkey>, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut pubkeys = vec![];
+    for (index, line) in contents.lines().enumerate() {
+        let field = line
+            .split(',')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .trim_matches('"');
+        if field.is_empty() || field.starts_with('#') {
+            continue;
+        }
+        match Pubkey::from_str(field) {
+            Ok(pubkey) => pubkeys.push(pubkey),
+            Err(_) if index == 0 => continue, // header row
+            Err(e) => {
+                return Err(format!(
+                    "Invalid wallet {} on line {}: {}",
+                    field,
+                    index + 1,
+                    e
+                ));
+            }
+        }
+    }
+    pubkeys.sort();
+    pubkeys.dedup();
+    Ok(pubkeys)
+}
+
+fn build_allowlist(csv: &Path, out: &Path) -> Result<(), String> {
+    let pubkeys = read_allowlist_csv(csv)?;
+    let Some(root) = allowlist_root(&pubkeys) else {
+        return Err(format!("No wallet addresses found in {}", csv.display()));
+    };
+
+    // Each line is a wallet followed by the nodes of its proof
+    let mut contents = format!("# allowlist root {}\n", format_hash(&root));
+    for (index, pubkey) in pubkeys.iter().enumerate() {
+        contents.push_str(&pubkey.to_string());
+        for node in allowlist_proof(&pubkeys, index) {
+            contents.push(' ');
+            contents.push_str(&format_hash(&node));
+        }
+        contents.push('\n');
+    }
+    std::fs::write(out, contents)
+        .map_err(|e| format!("Failed to write {}: {}", out.display(), e))?;
+
+    println!("{} {}", "Allowed wallets:".cyan(), pubkeys.len());
+    println!("{} {}", "Allowlist root:".cyan(), format_hash(&root));
+    println!("{} {}", "Proofs written to:".cyan(), out.display());
+    Ok(())
+}
+
+/// Proof of `authority` from a file written by allowlist build, checked
+/// against the root it was built with
+pub fn read_allowlist_proof(path: &Path, authority: &Pubkey) -> Result<Vec<[u8; 32]>, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut root = None;
+    for line in contents.lines() {
+        if let Some(hex) = line.trim().strip_prefix("# allowlist root ") {
+            root = Some(parse_hash(hex)?);
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        if fields.next() != Some(authority.to_string().as_str()) {
+            continue;
+        }
+        let proof = fields.map(parse_hash).collect::<Result<Vec<_>, _>>()?;
+        if let Some(root) = root
+            && !verify_allowlist_proof(&root, authority, &proof)
+        {
+            return Err(format!(
+                "The proof of {} in {} is invalid",
+                authority,
+                path.display()
+            ));
+        }
+        return Ok(proof);
+    }
+    Err(format!("{} is not in {}", authority, path.display()))
+}
+
+fn set_allowlist(token_mint: String, pool_maker: Option<String>, root: Option<String>) {
+    let admin = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    // Parse the token mint address
+    let mint_pubkey = match token_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid token mint address:".red(), e);
+            return;
+        }
+    };
+    let allowlist_root = match root.as_deref().map(parse_hash).transpose() {
+        Ok(root) => root,
+        Err(e) => {
+            println!("{}", e.red());
+            return;
+        }
+    };
+
+    // The pool stays derived from its maker after it is handed over
+    let pool_maker = match parse_pool_maker(pool_maker.as_deref(), &admin.pubkey()) {
+        Ok(pool_maker) => pool_maker,
+        Err(e) => {
+            println!("{}", e.red());
+            return;
+        }
+    };
+
+    // Derive mining pool PDA
+    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+        &[MINING_POOL_SEED, pool_maker.as_ref(), mint_pubkey.as_ref()],
+        &PROGRAM_ID,
+    );
+
+    match &allowlist_root {
+        Some(root) => println!("{} {}", "Allowlist root:".cyan(), format_hash(root)),
+        None => println!(
+            "{} {}",
+            "Allowlist:".cyan(),
+            "off, any wallet can mine".yellow()
+        ),
+    }
+
+    let instruction_data = instruction::SetAllowlist { allowlist_root }.data();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(admin.pubkey(), true), // admin
+        AccountMeta::new(mining_pool_pda, false),        // mining_pool_pda
+    ];
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[&admin], blockhash);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => println!(
+            "{} {}{}{}",
+            "Transaction sent successfully:".green(),
+            "https://explorer.solana.com/tx/".to_string().cyan(),
+            sig.to_string().cyan(),
+            "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                .to_string()
+                .cyan()
+        ),
+        Err(e) => print_transaction_error(&e),
+    }
+}
@@ -0,0 +1,142 @@
+// Measures the local hash rate and estimates how long each difficulty takes to mine
+
+use clap::Parser;
+use colored::*;
+use indicatif::ProgressBar;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use mor_core::helpers::constants::{MAX_DIFFICULTY, MIN_DIFFICULTY};
+use mor_core::helpers::utils::generate_challenge;
+use mor_core::states::PowAlgorithm;
+
+use crate::commands::mine::solve;
+use crate::utils::helpers::{
+    EPOCH_SLOT_LENGTH, SLOT_DURATION, parse_pow_algorithm, pow_algorithm_name,
+};
+
+#[derive(Parser, Debug)]
+pub struct BenchArgs {
+    /// How long to hash for, in seconds
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+    pub seconds: u64,
+
+    /// Hashing threads (defaults to all cores)
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub threads: Option<u64>,
+
+    /// PoW algorithm to benchmark: sha3 or equix
+    #[arg(long, default_value = "sha3", value_parser = parse_pow_algorithm)]
+    pub pow_algorithm: PowAlgorithm,
+}
+
+/// Formats a duration in the largest unit that keeps it readable
+fn format_duration(seconds: f64) -> String {
+    if seconds < 1.0 {
+        format!("{:.0} ms", seconds * 1_000.0)
+    } else if seconds < 60.0 {
+        format!("{:.1} s", seconds)
+    } else if seconds < 3_600.0 {
+        format!("{:.1} min", seconds / 60.0)
+    } else if seconds < 86_400.0 {
+        format!("{:.1} h", seconds / 3_600.0)
+    } else if seconds < 31_557_600.0 {
+        format!("{:.1} days", seconds / 86_400.0)
+    } else {
+        format!("{:.1e} years", seconds / 31_557_600.0)
+    }
+}
+
+pub fn handle_bench(args: &BenchArgs) {
+    let threads = args.threads.map(|t| t as usize).unwrap_or_else(|| {
+        thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
+    let duration = Duration::from_secs(args.seconds);
+
+    // Hash against a target no nonce meets, through the same path mine uses
+    let challenge = generate_challenge(&Pubkey::default(), 0);
+    let running = Arc::new(AtomicBool::new(true));
+    let tried = Arc::new(AtomicU64::new(0));
+
+    let pb = ProgressBar::new_spinner();
+    pb.enable_steady_tick(Duration::from_millis(100));
+
+    let start = Instant::now();
+    let worker = {
+        let running = running.clone();
+        let tried = tried.clone();
+        let pow_algorithm = args.pow_algorithm;
+        thread::spawn(move || {
+            solve(
+                pow_algorithm,
+                &challenge,
+                u32::MAX,
+                threads,
+                &running,
+                &|| true,
+                &tried,
+            )
+        })
+    };
+    while start.elapsed() < duration {
+        pb.set_message(format!(
+            "Hashing {} on {} threads: {} hashes...",
+            pow_algorithm_name(args.pow_algorithm),
+            threads,
+            tried.load(Ordering::Relaxed)
+        ));
+        thread::sleep(Duration::from_millis(100));
+    }
+    running.store(false, Ordering::SeqCst);
+    let _ = worker.join();
+    let elapsed = start.elapsed().as_secs_f64();
+    pb.finish_and_clear();
+
+    let hashes = tried.load(Ordering::Relaxed);
+    let hash_rate = hashes as f64 / elapsed;
+    println!(
+        "{} {:.0} H/s {} ({} hashes in {:.1} s on {} threads)",
+        "Hash rate:".green(),
+        hash_rate,
+        pow_algorithm_name(args.pow_algorithm),
+        hashes,
+        elapsed,
+        threads
+    );
+
+    let epoch_seconds = (SLOT_DURATION * EPOCH_SLOT_LENGTH as u32).as_secs_f64();
+    println!(
+        "{} {}",
+        "Epoch length:".cyan(),
+        format_duration(epoch_seconds)
+    );
+    println!();
+    println!(
+        "{:<18}{:>20}{:>20}",
+        "Difficulty (bits)", "Expected hashes", "Expected time"
+    );
+    // A new miner starts at 0 and adapts between MIN_DIFFICULTY and MAX_DIFFICULTY
+    for difficulty in std::iter::once(0).chain(MIN_DIFFICULTY..=MAX_DIFFICULTY) {
+        // Each required zero bit makes a solution twice as rare
+        let expected_hashes = 2f64.powi(difficulty as i32);
+        let expected_seconds = expected_hashes / hash_rate;
+        let time = format!("{:>20}", format_duration(expected_seconds));
+        println!(
+            "{:<18}{:>20}{}",
+            difficulty,
+            format!("{:.0}", expected_hashes),
+            if expected_seconds > epoch_seconds {
+                time.red()
+            } else {
+                time.green()
+            }
+        );
+    }
+}
@@ -0,0 +1,151 @@
+// Funds a second reward token that a pool pays alongside its own mint
+
+use anchor_lang::InstructionData;
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::instruction;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{
+    CLUSTER_URL, MINING_POOL_REWARD_SEED, MINING_POOL_SEED, PROGRAM_ID, load_payer_keypair,
+    parse_pool_maker, unix_timestamp,
+};
+
+pub fn handle_add_bonus_mint(
+    token_mint: String,
+    pool_maker: Option<String>,
+    bonus_mint: String,
+    amount: u64,
+    rate_bps: u32,
+    days: u64,
+) {
+    let admin = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    // Parse the token mint addresses
+    let mint_pubkey = match token_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid token mint address:".red(), e);
+            return;
+        }
+    };
+    let bonus_mint_pubkey = match bonus_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid bonus mint address:".red(), e);
+            return;
+        }
+    };
+
+    // The pool stays derived from its maker after it is handed over
+    let pool_maker = match parse_pool_maker(pool_maker.as_deref(), &admin.pubkey()) {
+        Ok(pool_maker) => pool_maker,
+        Err(e) => {
+            println!("{}", e.red());
+            return;
+        }
+    };
+
+    // Derive mining pool PDA
+    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+        &[MINING_POOL_SEED, pool_maker.as_ref(), mint_pubkey.as_ref()],
+        &PROGRAM_ID,
+    );
+
+    // The bonus budget is kept in the reward state, which must be on the base layer
+    let (mining_pool_reward_state, _bump) = Pubkey::find_program_address(
+        &[
+            MINING_POOL_REWARD_SEED,
+            pool_maker.as_ref(),
+            mining_pool_pda.as_ref(),
+        ],
+        &PROGRAM_ID,
+    );
+    if let Ok(account) = client.get_account(&mining_pool_reward_state)
+        && account.owner != PROGRAM_ID
+    {
+        println!(
+            "{}",
+            "The reward pool is delegated to the ER. Undelegate it before adding a bonus mint."
+                .yellow()
+        );
+    }
+
+    let admin_ata = get_associated_token_address(&admin.pubkey(), &bonus_mint_pubkey);
+    let bonus_vault = get_associated_token_address(&mining_pool_pda, &bonus_mint_pubkey);
+
+    let ends_at = unix_timestamp() + (days * 86_400) as i64;
+    let instruction_data = instruction::AddBonusMint {
+        amount,
+        rate_bps,
+        ends_at,
+    }
+    .data();
+
+    let accounts = vec![
+        AccountMeta::new(admin.pubkey(), true),              // admin
+        AccountMeta::new(mining_pool_pda, false),            // mining_pool_pda
+        AccountMeta::new(mining_pool_reward_state, false),   // mining_pool_reward_state
+        AccountMeta::new_readonly(bonus_mint_pubkey, false), // bonus_mint
+        AccountMeta::new(admin_ata, false),                  // admin_ata
+        AccountMeta::new(bonus_vault, false),                // bonus_vault
+        AccountMeta::new_readonly(spl_associated_token_account::ID, false), // associated_token_program
+        AccountMeta::new_readonly(spl_token::ID, false),                    // token_program
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),                // system_program
+    ];
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[&admin], blockhash);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => {
+            println!(
+                "{} {}{}{}",
+                "Transaction sent successfully:".green(),
+                "https://explorer.solana.com/tx/".to_string().cyan(),
+                sig.to_string().cyan(),
+                "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                    .to_string()
+                    .cyan()
+            );
+            println!(
+                "{} {} of {} for {} days",
+                "Bonus budget added:".cyan(),
+                amount,
+                bonus_mint_pubkey,
+                days
+            );
+        }
+        Err(e) => print_transaction_error(&e),
+    }
+}
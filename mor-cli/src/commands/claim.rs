@@ -1,4 +1,4 @@
-use anchor_lang::InstructionData;
+use anchor_lang::{AnchorDeserialize, InstructionData};
 use colored::*;
 use indicatif::ProgressBar;
 use mor_core::instruction;
@@ -10,11 +10,15 @@ use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
 use spl_associated_token_account::get_associated_token_address;
 use spl_associated_token_account::instruction::create_associated_token_account;
 
+use crate::commands::get_account::{MinerAccountPoolPda, MiningPoolPda};
+use crate::commands::protocol::{fetch_protocol_config, protocol_config_address};
+use crate::utils::errors::print_transaction_error;
 use crate::utils::helpers::{
-    CLUSTER_URL, MINER_SEED, MINING_POOL_SEED, PROGRAM_ID, load_payer_keypair,
+    CLUSTER_URL, MINER_SEED, MINING_POOL_SEED, MINT_AUTHORITY_SEED, PROGRAM_ID, VESTING_SEED,
+    format_bps, load_payer_keypair,
 };
 
-pub fn handle_claim_rewards(token_mint: String) {
+pub fn handle_claim_rewards(token_mint: String, to: Option<String>, amount: Option<u64>) {
     let authority = load_payer_keypair().unwrap();
     let client = RpcClient::new(CLUSTER_URL);
 
@@ -47,11 +51,40 @@ pub fn handle_claim_rewards(token_mint: String) {
         &PROGRAM_ID,
     );
 
+    let miner = client
+        .get_account_data(&miner_pda)
+        .ok()
+        .and_then(|data| MinerAccountPoolPda::deserialize(&mut &data[1..]).ok());
+
+    // Pay --to, else the miner's beneficiary, else the authority
+    let beneficiary = match to {
+        Some(to) => match to.parse::<Pubkey>() {
+            Ok(pubkey) => pubkey,
+            Err(e) => {
+                println!("{} {}", "Invalid beneficiary address:".red(), e);
+                return;
+            }
+        },
+        None => miner
+            .as_ref()
+            .map(|miner| miner.beneficiary)
+            .filter(|beneficiary| *beneficiary != Pubkey::default())
+            .unwrap_or(authority.pubkey()),
+    };
+    if beneficiary != authority.pubkey() {
+        println!("{} {}", "Paying rewards to:".cyan(), beneficiary);
+    }
+
     // Get vault (mining pool's token account)
     let vault = get_associated_token_address(&mining_pool_pda, &mint_pubkey);
+    // Only signs for pools that mint their rewards
+    let (mint_authority, _bump) = Pubkey::find_program_address(
+        &[MINT_AUTHORITY_SEED, mining_pool_pda.as_ref()],
+        &PROGRAM_ID,
+    );
 
-    // Get recipient ATA (authority's token account)
-    let recipient_ata = get_associated_token_address(&authority.pubkey(), &mint_pubkey);
+    // Get recipient ATA (beneficiary's token account)
+    let recipient_ata = get_associated_token_address(&beneficiary, &mint_pubkey);
 
     let mut instructions = vec![];
 
@@ -64,7 +97,7 @@ pub fn handle_claim_rewards(token_mint: String) {
             // Create ATA instruction
             let create_ata_ix = create_associated_token_account(
                 &authority.pubkey(),
-                &authority.pubkey(),
+                &beneficiary,
                 &mint_pubkey,
                 &spl_token::ID,
             );
@@ -72,21 +105,102 @@ pub fn handle_claim_rewards(token_mint: String) {
         }
     }
 
+    let pool = client
+        .get_account_data(&mining_pool_pda)
+        .ok()
+        .and_then(|data| MiningPoolPda::deserialize(&mut &data[1..]).ok());
+
+    // Vesting pools lock the claim in the miner's escrow, others leave it out
+    let (vesting_escrow, escrow_vault) = match pool.as_ref().and_then(|pool| pool.vesting) {
+        Some(_) => {
+            let (vesting_escrow, _bump) =
+                Pubkey::find_program_address(&[VESTING_SEED, miner_pda.as_ref()], &PROGRAM_ID);
+            println!(
+                "{}",
+                "Rewards of this pool vest. See mor-cli vesting for what you can release. Bonus mints don't vest and are paid right away.".yellow()
+            );
+            (
+                AccountMeta::new(vesting_escrow, false),
+                AccountMeta::new(
+                    get_associated_token_address(&vesting_escrow, &mint_pubkey),
+                    false,
+                ),
+            )
+        }
+        None => (
+            AccountMeta::new_readonly(PROGRAM_ID, false),
+            AccountMeta::new_readonly(PROGRAM_ID, false),
+        ),
+    };
+
+    // The protocol's fee on the claim goes to its treasury's token account
+    let Some(protocol_config) = fetch_protocol_config(&client) else {
+        println!(
+            "{}",
+            "The protocol config does not exist yet. Claims fail until the program's upgrade authority runs mor-cli protocol init."
+                .red()
+        );
+        return;
+    };
+    let treasury_ata = get_associated_token_address(&protocol_config.treasury, &mint_pubkey);
+    if protocol_config.protocol_fee_bps > 0 {
+        println!(
+            "{} {}",
+            "Protocol fee on this claim:".cyan(),
+            format_bps(protocol_config.protocol_fee_bps)
+        );
+    }
+
     // Create the claim rewards instruction data
-    let instruction_data = instruction::ClaimRewards {}.data();
+    let instruction_data = instruction::ClaimRewards { amount }.data();
 
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(authority.pubkey(), true), // authority
         AccountMeta::new(miner_pda, false),         // miner
         AccountMeta::new(mining_pool_pda, false),   // mining_pool_pda
         AccountMeta::new(vault, false),             // vault
         AccountMeta::new(mint_pubkey, false),       // mint
+        AccountMeta::new_readonly(mint_authority, false), // mint_authority
+        AccountMeta::new_readonly(beneficiary, false), // beneficiary
         AccountMeta::new(recipient_ata, false),     // recipient_ata
+        vesting_escrow,                             // vesting_escrow
+        escrow_vault,                               // escrow_vault
+        AccountMeta::new_readonly(protocol_config_address(), false), // protocol_config
+        AccountMeta::new_readonly(protocol_config.treasury, false), // treasury
+        AccountMeta::new(treasury_ata, false),      // treasury_ata
         AccountMeta::new(spl_associated_token_account::ID, false), // associated_token_program
         AccountMeta::new(spl_token::ID, false),     // token_program
         AccountMeta::new(SYSTEM_PROGRAM_ID, false), // system_program
     ];
 
+    // Every bonus mint of the pool is paid alongside, from its own vault
+    let bonus_mints = pool.map(|pool| pool.bonus_mints).unwrap_or_default();
+    for (index, bonus) in bonus_mints.iter().enumerate() {
+        let bonus_rewards = miner
+            .as_ref()
+            .and_then(|miner| miner.bonus_rewards.get(index).copied())
+            .unwrap_or(0);
+        if bonus_rewards > 0 {
+            println!(
+                "{} {} of {}",
+                "Claiming bonus rewards:".cyan(),
+                bonus_rewards,
+                bonus.mint
+            );
+        }
+        accounts.extend([
+            AccountMeta::new_readonly(bonus.mint, false), // bonus mint
+            AccountMeta::new(
+                get_associated_token_address(&mining_pool_pda, &bonus.mint),
+                false,
+            ), // bonus vault
+            AccountMeta::new(
+                get_associated_token_address(&beneficiary, &bonus.mint),
+                false,
+            ), // beneficiary's bonus ATA
+        ]);
+    }
+
     // Prepare the claim instruction
     let claim_ix = Instruction {
         program_id: PROGRAM_ID,
@@ -131,6 +245,6 @@ pub fn handle_claim_rewards(token_mint: String) {
                 .to_string()
                 .cyan()
         ),
-        Err(e) => println!("{} {}", "Transaction failed:".red(), e),
+        Err(e) => print_transaction_error(&e),
     }
 }
@@ -0,0 +1,173 @@
+// Sets and claims the pool maker's commission on mined rewards
+
+use anchor_lang::InstructionData;
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::instruction;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{
+    CLUSTER_URL, MINING_POOL_REWARD_SEED, MINING_POOL_SEED, MINT_AUTHORITY_SEED, PROGRAM_ID,
+    format_bps, load_payer_keypair, parse_pool_maker,
+};
+
+pub fn handle_set_commission(token_mint: String, pool_maker: Option<String>, commission_bps: u16) {
+    let admin = load_payer_keypair().unwrap();
+
+    // Parse the token mint address
+    let mint_pubkey = match token_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid token mint address:".red(), e);
+            return;
+        }
+    };
+
+    // The pool stays derived from its maker after it is handed over
+    let pool_maker = match parse_pool_maker(pool_maker.as_deref(), &admin.pubkey()) {
+        Ok(pool_maker) => pool_maker,
+        Err(e) => {
+            println!("{}", e.red());
+            return;
+        }
+    };
+
+    // Derive mining pool PDA
+    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+        &[MINING_POOL_SEED, pool_maker.as_ref(), mint_pubkey.as_ref()],
+        &PROGRAM_ID,
+    );
+
+    let instruction_data = instruction::SetCommission { commission_bps }.data();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(admin.pubkey(), true), // admin
+        AccountMeta::new(mining_pool_pda, false),        // mining_pool_pda
+    ];
+
+    println!("{} {}", "Commission:".cyan(), format_bps(commission_bps));
+    send(&admin, accounts, instruction_data);
+}
+
+pub fn handle_claim_commission(token_mint: String, pool_maker: Option<String>) {
+    let admin = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    // Parse the token mint address
+    let mint_pubkey = match token_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid token mint address:".red(), e);
+            return;
+        }
+    };
+
+    // The pool stays derived from its maker after it is handed over
+    let pool_maker = match parse_pool_maker(pool_maker.as_deref(), &admin.pubkey()) {
+        Ok(pool_maker) => pool_maker,
+        Err(e) => {
+            println!("{}", e.red());
+            return;
+        }
+    };
+
+    // Derive mining pool PDA
+    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+        &[MINING_POOL_SEED, pool_maker.as_ref(), mint_pubkey.as_ref()],
+        &PROGRAM_ID,
+    );
+
+    // Derive the reward state, which holds the commission earned
+    let (mining_pool_reward_state, _bump) = Pubkey::find_program_address(
+        &[
+            MINING_POOL_REWARD_SEED,
+            pool_maker.as_ref(),
+            mining_pool_pda.as_ref(),
+        ],
+        &PROGRAM_ID,
+    );
+    if let Ok(account) = client.get_account(&mining_pool_reward_state)
+        && account.owner != PROGRAM_ID
+    {
+        println!(
+            "{}",
+            "The reward pool is delegated to the ER. Undelegate it before claiming.".yellow()
+        );
+    }
+
+    let vault = get_associated_token_address(&mining_pool_pda, &mint_pubkey);
+    // Only signs for pools that mint their rewards
+    let (mint_authority, _bump) = Pubkey::find_program_address(
+        &[MINT_AUTHORITY_SEED, mining_pool_pda.as_ref()],
+        &PROGRAM_ID,
+    );
+    let recipient_ata = get_associated_token_address(&admin.pubkey(), &mint_pubkey);
+
+    let instruction_data = instruction::ClaimCommission {}.data();
+
+    let accounts = vec![
+        AccountMeta::new(admin.pubkey(), true),            // admin
+        AccountMeta::new_readonly(mining_pool_pda, false), // mining_pool_pda
+        AccountMeta::new(mining_pool_reward_state, false), // mining_pool_reward_state
+        AccountMeta::new(vault, false),                    // vault
+        AccountMeta::new(mint_pubkey, false),              // mint
+        AccountMeta::new_readonly(mint_authority, false),  // mint_authority
+        AccountMeta::new(recipient_ata, false),            // recipient_ata
+        AccountMeta::new_readonly(spl_associated_token_account::ID, false), // associated_token_program
+        AccountMeta::new_readonly(spl_token::ID, false),                    // token_program
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),                // system_program
+    ];
+
+    send(&admin, accounts, instruction_data);
+}
+
+/// Signs and sends a single instruction on the base layer
+fn send(admin: &Keypair, accounts: Vec<AccountMeta>, data: Vec<u8>) {
+    let client = RpcClient::new(CLUSTER_URL);
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data,
+    };
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[admin], blockhash);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => println!(
+            "{} {}{}{}",
+            "Transaction sent successfully:".green(),
+            "https://explorer.solana.com/tx/".to_string().cyan(),
+            sig.to_string().cyan(),
+            "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                .to_string()
+                .cyan()
+        ),
+        Err(e) => print_transaction_error(&e),
+    }
+}
@@ -8,6 +8,7 @@ use solana_sdk::pubkey::Pubkey;
 use solana_sdk::{signature::Signer, transaction::Transaction};
 use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
 
+use crate::utils::errors::print_transaction_error;
 use crate::utils::helpers::{
     CLUSTER_URL, DELEGATION_PROGRAM_ID, MINER_SEED, MINING_POOL_SEED, PROGRAM_ID,
     load_payer_keypair,
@@ -112,6 +113,6 @@ pub fn handle_delegate_miner(token_mint: String) {
                 .to_string()
                 .cyan()
         ),
-        Err(e) => println!("{} {}", "Transaction failed:".red(), e),
+        Err(e) => print_transaction_error(&e),
     }
 }
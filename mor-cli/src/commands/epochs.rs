@@ -0,0 +1,259 @@
+// Reads a pool's epoch ledgers and the stats its reward shards hold until the next
+// settlement: who solved the recent epochs and what they earned
+
+use anchor_lang::{AnchorDeserialize, InstructionData};
+use colored::*;
+use mor_core::helpers::utils::{epoch_stats_slot, fold_epoch_stats};
+use mor_core::instruction;
+use mor_core::states::{EpochLedger, RewardShard};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+
+use crate::commands::mine::EPOCH_TOLERANCE;
+use crate::commands::settle_epoch::reward_shard_addresses;
+use crate::utils::helpers::{
+    CLUSTER_URL, DELEGATION_PROGRAM_ID, EPOCH_SEED, EPOCH_SLOT_LENGTH, ER_CLUSTER_URL,
+    MAGIC_CONTEXT_PROGRAM_ID, MAGIC_PROGRAM_ID, PROGRAM_ID,
+};
+
+/// Most accounts a single getMultipleAccounts request may ask for
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// Address of a pool's ledger for an epoch
+pub fn epoch_ledger_address(mining_pool_pda: &Pubkey, epoch: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[EPOCH_SEED, mining_pool_pda.as_ref(), &epoch.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+    .0
+}
+
+/// initialize_epoch_ledger, sent to the base layer ahead of the epoch
+pub fn initialize_epoch_ledger_instruction(
+    payer: Pubkey,
+    mining_pool_pda: Pubkey,
+    epoch: u64,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),                     // payer
+            AccountMeta::new_readonly(mining_pool_pda, false), // mining_pool_pda
+            AccountMeta::new(epoch_ledger_address(&mining_pool_pda, epoch), false), // epoch_ledger
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+        ],
+        data: instruction::InitializeEpochLedger { epoch }.data(),
+    }
+}
+
+/// delegate_epoch_ledger, moving a ledger to the ER next to the pool's reward state
+pub fn delegate_epoch_ledger_instruction(
+    payer: Pubkey,
+    mining_pool_pda: Pubkey,
+    mining_pool_reward_state: Pubkey,
+    epoch: u64,
+) -> Instruction {
+    let epoch_ledger = epoch_ledger_address(&mining_pool_pda, epoch);
+    let (buffer_epoch_ledger, _bump) =
+        Pubkey::find_program_address(&[b"buffer", epoch_ledger.as_ref()], &PROGRAM_ID);
+    let (delegation_record_epoch_ledger, _bump) = Pubkey::find_program_address(
+        &[b"delegation", epoch_ledger.as_ref()],
+        &DELEGATION_PROGRAM_ID,
+    );
+    let (delegation_metadata_epoch_ledger, _bump) = Pubkey::find_program_address(
+        &[b"delegation-metadata", epoch_ledger.as_ref()],
+        &DELEGATION_PROGRAM_ID,
+    );
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(payer, true), // payer
+            AccountMeta::new(buffer_epoch_ledger, false),
+            AccountMeta::new(delegation_record_epoch_ledger, false),
+            AccountMeta::new(delegation_metadata_epoch_ledger, false),
+            AccountMeta::new(epoch_ledger, false), // epoch_ledger
+            AccountMeta::new_readonly(mining_pool_pda, false), // mining_pool_pda
+            AccountMeta::new_readonly(mining_pool_reward_state, false), // mining_pool_reward_state
+            AccountMeta::new(PROGRAM_ID, false),
+            AccountMeta::new(DELEGATION_PROGRAM_ID, false), // delegation program
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+        ],
+        data: instruction::DelegateEpochLedger { epoch }.data(),
+    }
+}
+
+/// undelegate_epoch_ledger, sent to the ER once no more solutions are accepted for the epoch
+pub fn undelegate_epoch_ledger_instruction(
+    payer: Pubkey,
+    mining_pool_pda: Pubkey,
+    epoch: u64,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(payer, true), // payer
+            AccountMeta::new(epoch_ledger_address(&mining_pool_pda, epoch), false), // epoch_ledger
+            AccountMeta::new_readonly(mining_pool_pda, false), // mining_pool_pda
+            AccountMeta::new_readonly(MAGIC_PROGRAM_ID, false),
+            AccountMeta::new(MAGIC_CONTEXT_PROGRAM_ID, false),
+        ],
+        data: instruction::UndelegateEpochLedger { epoch }.data(),
+    }
+}
+
+/// close_epoch_ledger, sent to the base layer once settle_epoch folded the finished
+/// epoch's last stats in, returning the ledger's rent to `payer`, who paid it
+pub fn close_epoch_ledger_instruction(
+    payer: Pubkey,
+    mining_pool_pda: Pubkey,
+    epoch: u64,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(payer, false),                    // payer
+            AccountMeta::new_readonly(mining_pool_pda, false), // mining_pool_pda
+            AccountMeta::new(epoch_ledger_address(&mining_pool_pda, epoch), false), // epoch_ledger
+        ],
+        data: instruction::CloseEpochLedger { epoch }.data(),
+    }
+}
+
+pub fn handle_epochs(pool: String, count: u64) {
+    let client = RpcClient::new(CLUSTER_URL);
+    let er_client = RpcClient::new(ER_CLUSTER_URL);
+
+    let mining_pool_pda = match pool.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid pool address:".red(), e);
+            return;
+        }
+    };
+
+    let slot = match client.get_slot() {
+        Ok(slot) => slot,
+        Err(e) => {
+            println!("{} {}", "Failed to get the current slot:".red(), e);
+            return;
+        }
+    };
+    let current_epoch = slot / EPOCH_SLOT_LENGTH;
+    println!("{} {}", "Current epoch:".cyan(), current_epoch);
+
+    // Newest first, from the furthest ahead epoch solutions are accepted for
+    let epochs: Vec<u64> = (0..count)
+        .filter_map(|back| (current_epoch + EPOCH_TOLERANCE).checked_sub(back))
+        .collect();
+    let addresses: Vec<Pubkey> = epochs
+        .iter()
+        .map(|epoch| epoch_ledger_address(&mining_pool_pda, *epoch))
+        .collect();
+
+    // Recent ledgers of a delegated pool are still on the ER, older ones were
+    // returned to the base layer
+    let mut ledgers = vec![];
+    for chunk in addresses.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+        let er_ledgers =
+            fetch_ledgers(&er_client, chunk).unwrap_or_else(|_| vec![None; chunk.len()]);
+        match fetch_ledgers(&client, chunk) {
+            Ok(base_ledgers) => ledgers.extend(
+                er_ledgers
+                    .into_iter()
+                    .zip(base_ledgers)
+                    .map(|(er_ledger, base_ledger)| er_ledger.or(base_ledger)),
+            ),
+            Err(e) => {
+                println!("{} {}", "Failed to fetch epoch ledgers:".red(), e);
+                return;
+            }
+        }
+    }
+
+    // Solutions since the last settlement are still counted on the reward shards
+    let shards = fetch_reward_shards(&er_client, &mining_pool_pda)
+        .filter(|shards| !shards.is_empty())
+        .or_else(|| fetch_reward_shards(&client, &mining_pool_pda))
+        .unwrap_or_default();
+    for (epoch, ledger) in epochs.iter().zip(&mut ledgers) {
+        for stats in shards
+            .iter()
+            .map(|shard| &shard.epoch_stats[epoch_stats_slot(*epoch)])
+            .filter(|stats| stats.epoch == *epoch && stats.submissions > 0)
+        {
+            let ledger = ledger.get_or_insert_with(|| EpochLedger {
+                pool: mining_pool_pda,
+                epoch: *epoch,
+                submissions: 0,
+                total_rewards: 0,
+                best_difficulty: 0,
+                best_miner: Pubkey::default(),
+                payer: Pubkey::default(),
+                settled_epoch: 0,
+                bump: 0,
+            });
+            fold_epoch_stats(ledger, stats);
+        }
+    }
+
+    let ledgers: Vec<EpochLedger> = ledgers
+        .into_iter()
+        .flatten()
+        .filter(|ledger| ledger.submissions > 0)
+        .collect();
+    if ledgers.is_empty() {
+        println!(
+            "{}",
+            format!("No solutions recorded in the last {} epochs.", count).yellow()
+        );
+        return;
+    }
+
+    println!(
+        "{:<12}{:>12}{:>20}{:>10}  Best miner",
+        "Epoch", "Solutions", "Rewards", "Best"
+    );
+    for ledger in ledgers {
+        println!(
+            "{:<12}{:>12}{:>20}{:>10}  {}",
+            ledger.epoch,
+            ledger.submissions,
+            ledger.total_rewards,
+            format!("{} bits", ledger.best_difficulty),
+            ledger.best_miner
+        );
+    }
+}
+
+/// The pool's reward shards that exist on this chain, None if they can't be fetched
+fn fetch_reward_shards(client: &RpcClient, mining_pool_pda: &Pubkey) -> Option<Vec<RewardShard>> {
+    Some(
+        client
+            .get_multiple_accounts(&reward_shard_addresses(mining_pool_pda))
+            .ok()?
+            .into_iter()
+            .flatten()
+            .filter(|account| account.owner == PROGRAM_ID)
+            .filter_map(|account| RewardShard::deserialize(&mut &account.data[1..]).ok())
+            .collect(),
+    )
+}
+
+/// Epoch ledgers at `addresses`, None where one doesn't exist on this chain
+fn fetch_ledgers(
+    client: &RpcClient,
+    addresses: &[Pubkey],
+) -> Result<Vec<Option<EpochLedger>>, String> {
+    Ok(client
+        .get_multiple_accounts(addresses)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|account| {
+            account
+                .filter(|account| account.owner == PROGRAM_ID)
+                .and_then(|account| EpochLedger::deserialize(&mut &account.data[1..]).ok())
+        })
+        .collect())
+}
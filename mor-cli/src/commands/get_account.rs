@@ -2,12 +2,17 @@
 
 use anchor_lang::AnchorDeserialize;
 use colored::*;
+use mor_core::helpers::utils::calculate_loyalty_bps;
+use mor_core::states::{
+    BonusMint, MinerStats, MultiplierTier, PoolMode, PowAlgorithm, VestingConfig,
+};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signer;
 
 use crate::utils::helpers::{
-    CLUSTER_URL, MINER_SEED, MINING_POOL_SEED, PROGRAM_ID, load_payer_keypair,
+    CLUSTER_URL, MINER_SEED, MINING_POOL_SEED, PROGRAM_ID, effective_multiplier, format_multiplier,
+    load_payer_keypair, unix_timestamp,
 };
 
 #[derive(Debug, AnchorDeserialize)]
@@ -15,12 +20,36 @@ pub struct MinerAccountPoolPda {
     pub authority: Pubkey,
     pub last_epoch_mined: u64,
     pub rewards: u64,
-    pub multiplier: u8,
+    pub multiplier: u32,
     pub staked_amount: u64,
     pub difficulty: u8,
     pub pool: Pubkey,
     pub last_staked_timestamp: i64,
     pub bump: u8,
+    pub version: u8,
+    pub signer: Pubkey,
+    pub beneficiary: Pubkey,
+    pub bonus_rewards: Vec<u64>,
+    pub stats: MinerStats,
+}
+
+#[derive(Debug, AnchorDeserialize)]
+pub struct MiningPoolPda {
+    _pool_maker: Pubkey,
+    _mint: Pubkey,
+    _bump: u8,
+    _amount: u64,
+    _pow_algorithm: PowAlgorithm,
+    mint_decimals: u8,
+    multiplier_tiers: Vec<MultiplierTier>,
+    _commission_bps: u16,
+    _mode: PoolMode,
+    _supply_cap: u64,
+    _mint_authority_bump: u8,
+    pub bonus_mints: Vec<BonusMint>,
+    pub vesting: Option<VestingConfig>,
+    _allowlist_root: Option<[u8; 32]>,
+    min_stake: u64,
 }
 
 pub fn handle_get_account(token_mint: String) {
@@ -58,10 +87,10 @@ pub fn handle_get_account(token_mint: String) {
 
     match client.get_account_data(&miner_pda) {
         Ok(account_data) => {
-            // Skip the first 8 bytes (discriminator) for Anchor accounts
-            let data_slice = &account_data[1..];
+            // Skip the 1 byte discriminator, the account is sized for its most bonus mints
+            let mut data_slice = &account_data[1..];
 
-            match MinerAccountPoolPda::try_from_slice(data_slice) {
+            match MinerAccountPoolPda::deserialize(&mut data_slice) {
                 Ok(miner_account) => {
                     println!("{}", "Miner Account Details:".green().bold());
                     println!("{} {}", "Miner PDA:".cyan(), miner_pda);
@@ -77,13 +106,46 @@ pub fn handle_get_account(token_mint: String) {
                         );
                     }
                     println!("{} {}", "Rewards:".cyan(), miner_account.rewards);
-                    println!("{} {}", "Multiplier:".cyan(), miner_account.multiplier);
+                    let pool = client
+                        .get_account_data(&mining_pool_pda)
+                        .ok()
+                        .and_then(|data| MiningPoolPda::deserialize(&mut &data[1..]).ok());
+                    // Bonus rewards line up with the pool's bonus mints
+                    if let Some(pool) = &pool {
+                        for (index, bonus) in pool.bonus_mints.iter().enumerate() {
+                            println!(
+                                "{} {} of {}",
+                                "Bonus Rewards:".cyan(),
+                                miner_account.bonus_rewards.get(index).unwrap_or(&0),
+                                bonus.mint
+                            );
+                        }
+                    }
+                    // The program recomputes the multiplier from the stake on every
+                    // submission, so show what the next one will use
+                    let multiplier = pool
+                        .as_ref()
+                        .map(|pool| {
+                            effective_multiplier(
+                                miner_account.staked_amount,
+                                miner_account.last_staked_timestamp,
+                                pool.mint_decimals,
+                                &pool.multiplier_tiers,
+                            )
+                        })
+                        .unwrap_or(miner_account.multiplier);
+                    println!("{} {}", "Multiplier:".cyan(), format_multiplier(multiplier));
                     println!(
                         "{} {}",
                         "Staked Amount:".cyan(),
                         miner_account.staked_amount
                     );
-                    println!("{} {}", "Difficulty:".cyan(), miner_account.difficulty);
+                    if let Some(pool) = &pool
+                        && pool.min_stake > 0
+                    {
+                        println!("{} {}", "Pool Minimum Stake:".cyan(), pool.min_stake);
+                    }
+                    println!("{} {} bits", "Difficulty:".cyan(), miner_account.difficulty);
                     if miner_account.last_staked_timestamp == 0 {
                         println!(
                             "{} {}",
@@ -96,11 +158,64 @@ pub fn handle_get_account(token_mint: String) {
                             "Last Staked Timestamp:".cyan(),
                             miner_account.last_staked_timestamp
                         );
+                        // Loyalty grows the stake bonus, up to MAX_LOYALTY_BPS
+                        let loyalty_bps = calculate_loyalty_bps(
+                            miner_account.last_staked_timestamp,
+                            unix_timestamp(),
+                        );
+                        println!(
+                            "{} +{}% of the stake bonus",
+                            "Loyalty:".cyan(),
+                            loyalty_bps as f64 / 100.0
+                        );
                     }
                     println!("{} {}", "Bump:".cyan(), miner_account.bump);
+                    println!("{} {}", "Version:".cyan(), miner_account.version);
+                    if miner_account.signer == Pubkey::default() {
+                        println!("{} {}", "Hot Key:".cyan(), "None (authority only)".yellow());
+                    } else {
+                        println!("{} {}", "Hot Key:".cyan(), miner_account.signer);
+                    }
+                    if miner_account.beneficiary == Pubkey::default() {
+                        println!(
+                            "{} {}",
+                            "Beneficiary:".cyan(),
+                            "None (chosen per claim)".yellow()
+                        );
+                    } else {
+                        println!("{} {}", "Beneficiary:".cyan(), miner_account.beneficiary);
+                    }
+
+                    let stats = &miner_account.stats;
+                    println!("{}", "Lifetime Stats:".green().bold());
+                    println!("{} {}", "Solutions:".cyan(), stats.total_solutions);
+                    if stats.total_solutions > 0 {
+                        println!(
+                            "{} {}",
+                            "First Epoch Mined:".cyan(),
+                            stats.first_epoch_mined
+                        );
+                        println!(
+                            "{} {} bits",
+                            "Best Difficulty:".cyan(),
+                            stats.best_difficulty
+                        );
+                        println!(
+                            "{} {} epochs",
+                            "Current Streak:".cyan(),
+                            stats.current_streak
+                        );
+                    }
+                    println!("{} {}", "Rewards Earned:".cyan(), stats.total_rewards);
+                    println!("{} {}", "Rewards Claimed:".cyan(), stats.total_claimed);
                 }
                 Err(e) => {
                     println!("{} {}", "Failed to deserialize miner account:".red(), e);
+                    println!(
+                        "{}",
+                        "The account may use an older layout. Try running the migrate-miner command."
+                            .cyan()
+                    );
                 }
             }
         }
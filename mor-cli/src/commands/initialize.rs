@@ -1,6 +1,7 @@
 // to be executed by admin of the platform
 use anchor_lang::InstructionData;
 use colored::*;
+use solana_client::client_error::ClientError;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
@@ -12,11 +13,18 @@ use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
 use spl_associated_token_account::get_associated_token_address;
 use std::str::FromStr;
 
-use mor_core::instruction::{DelegateRewardPool, InitializePool};
+use mor_core::helpers::constants::REWARD_SHARDS;
+use mor_core::instruction::{
+    DelegateRewardPool, DelegateRewardShard, InitializePool, InitializeRewardShard,
+};
+use mor_core::states::{PoolMode, PowAlgorithm};
 
+use crate::commands::protocol::{fetch_protocol_config, protocol_config_address};
+use crate::commands::settle_epoch::reward_shard_address;
+use crate::utils::errors::print_transaction_error;
 use crate::utils::helpers::{
-    DELEGATION_PROGRAM_ID, MINING_POOL_REWARD_SEED, MINING_POOL_SEED, PROGRAM_ID,
-    load_payer_keypair,
+    DELEGATION_PROGRAM_ID, MINING_POOL_REWARD_SEED, MINING_POOL_SEED, MINT_AUTHORITY_SEED,
+    PROGRAM_ID, format_bps, format_sol, load_payer_keypair, pool_mode_name, pow_algorithm_name,
 };
 
 // Define the constants locally since they're not exported from helpers
@@ -26,6 +34,9 @@ use crate::utils::helpers::{
 pub fn handle_initialize_pool(
     amount: u64,
     token_mint: String,
+    pow_algorithm: PowAlgorithm,
+    commission_bps: u16,
+    mode: PoolMode,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let rpc_client = RpcClient::new("http://localhost:8899");
 
@@ -54,20 +65,48 @@ pub fn handle_initialize_pool(
         &mor_core::ID,
     );
 
+    let (mint_authority, _) = Pubkey::find_program_address(
+        &[MINT_AUTHORITY_SEED, mining_pool_pda.as_ref()],
+        &mor_core::ID,
+    );
+
     // Get associated token accounts
     let pool_maker_ata = get_associated_token_address(&payer.pubkey(), &mint_pubkey);
     let vault = get_associated_token_address(&mining_pool_pda, &mint_pubkey);
 
+    // Pools pay the protocol's creation fee to its treasury
+    let Some(protocol_config) = fetch_protocol_config(&rpc_client) else {
+        return Err(
+            "the protocol config does not exist yet, the program's upgrade authority must run mor-cli protocol init before pools can be created"
+                .into(),
+        );
+    };
+    if protocol_config.pool_creation_fee > 0 {
+        println!(
+            "{} {}",
+            "Pool creation fee:".cyan(),
+            format_sol(protocol_config.pool_creation_fee)
+        );
+    }
+
     // Build instruction
-    let instruction_data = InitializePool { amount };
+    let instruction_data = InitializePool {
+        amount,
+        pow_algorithm,
+        commission_bps,
+        mode,
+    };
 
     let accounts = vec![
         AccountMeta::new(payer.pubkey(), true),   // pool_maker
         AccountMeta::new(mining_pool_pda, false), // mining_pool_pda
         AccountMeta::new(mining_pool_reward_state, false), // mining_pool_reward_state
-        AccountMeta::new_readonly(mint_pubkey, false), // mint
+        AccountMeta::new_readonly(mint_authority, false), // mint_authority
+        AccountMeta::new(mint_pubkey, false),     // mint
         AccountMeta::new(pool_maker_ata, false),  // pool_maker_ata
         AccountMeta::new(vault, false),           // vault
+        AccountMeta::new_readonly(protocol_config_address(), false), // protocol_config
+        AccountMeta::new(protocol_config.treasury, false), // treasury
         AccountMeta::new_readonly(spl_associated_token_account::ID, false), // associated_token_program
         AccountMeta::new_readonly(spl_token::ID, false),                    // token_program
         AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),                // system_program
@@ -105,6 +144,27 @@ pub fn handle_initialize_pool(
             );
             println!("Mining pool PDA: {}", mining_pool_pda);
             println!("Mining pool reward state PDA: {}", mining_pool_reward_state);
+            println!("PoW algorithm: {}", pow_algorithm_name(pow_algorithm));
+            println!("Commission: {}", format_bps(commission_bps));
+            println!("Mode: {}", pool_mode_name(mode));
+            if mode == PoolMode::MintAuthority {
+                println!("Mint authority: {}", mint_authority);
+            }
+
+            // Solutions credit the reward shards, which start with a share of the pool
+            println!("\nCreating the pool's {} reward shards...", REWARD_SHARDS);
+            if let Err(e) = create_reward_shards(
+                &rpc_client,
+                &payer,
+                mining_pool_pda,
+                mining_pool_reward_state,
+            ) {
+                eprintln!("Warning: Failed to set up the reward shards: {}", e);
+                if let Some(client_error) = e.downcast_ref::<ClientError>() {
+                    print_transaction_error(client_error);
+                }
+                eprintln!("Miners can't be credited until the shards exist.");
+            }
 
             // Now delegate the reward pool to Ephemeral Rollup
             println!("\nDelegating reward pool to Ephemeral Rollup...");
@@ -125,15 +185,26 @@ pub fn handle_initialize_pool(
                             .to_string()
                             .cyan()
                     );
+                    if let Err(e) =
+                        delegate_reward_shards_to_er(&rpc_client, &payer, mining_pool_pda)
+                    {
+                        eprintln!("Warning: Failed to delegate the reward shards to ER: {}", e);
+                        if let Some(client_error) = e.downcast_ref::<ClientError>() {
+                            print_transaction_error(client_error);
+                        }
+                    }
                 }
                 Err(e) => {
                     eprintln!("Warning: Failed to delegate reward pool to ER: {}", e);
+                    if let Some(client_error) = e.downcast_ref::<ClientError>() {
+                        print_transaction_error(client_error);
+                    }
                     eprintln!("Pool initialization was successful, but delegation failed.");
                 }
             }
         }
         Err(e) => {
-            eprintln!("Failed to send transaction: {}", e);
+            print_transaction_error(&e);
             return Err(Box::new(e));
         }
     }
@@ -197,3 +268,92 @@ fn delegate_reward_pool_to_er(
     let signature = rpc_client.send_and_confirm_transaction_with_spinner(&transaction)?;
     Ok(signature)
 }
+
+/// Reward shards delegated per transaction, to stay under the transaction size limit
+const SHARDS_PER_DELEGATE_TX: usize = 4;
+
+// Creates the pool's reward shards, each with its share of the pool's budget
+pub fn create_reward_shards(
+    rpc_client: &RpcClient,
+    payer: &solana_sdk::signature::Keypair,
+    mining_pool_pda: Pubkey,
+    mining_pool_reward_state: Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let instructions: Vec<Instruction> = (0..REWARD_SHARDS)
+        .map(|index| Instruction {
+            program_id: mor_core::ID,
+            accounts: vec![
+                AccountMeta::new(payer.pubkey(), true),            // payer
+                AccountMeta::new_readonly(mining_pool_pda, false), // mining_pool_pda
+                AccountMeta::new_readonly(mining_pool_reward_state, false), // mining_pool_reward_state
+                AccountMeta::new(reward_shard_address(&mining_pool_pda, index), false), // reward_shard
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+            ],
+            data: InitializeRewardShard { index }.data(),
+        })
+        .collect();
+
+    let recent_blockhash = rpc_client.get_latest_blockhash()?;
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    rpc_client.send_and_confirm_transaction_with_spinner(&transaction)?;
+    println!("Reward shards created");
+    Ok(())
+}
+
+// Delegates the pool's reward shards to the Ephemeral Rollup, next to the reward state
+fn delegate_reward_shards_to_er(
+    rpc_client: &RpcClient,
+    payer: &solana_sdk::signature::Keypair,
+    mining_pool_pda: Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let indexes: Vec<u8> = (0..REWARD_SHARDS).collect();
+    for chunk in indexes.chunks(SHARDS_PER_DELEGATE_TX) {
+        let instructions: Vec<Instruction> = chunk
+            .iter()
+            .map(|&index| {
+                let reward_shard = reward_shard_address(&mining_pool_pda, index);
+                let (buffer_reward_shard, _bump) =
+                    Pubkey::find_program_address(&[b"buffer", reward_shard.as_ref()], &PROGRAM_ID);
+                let (delegation_record_reward_shard, _bump) = Pubkey::find_program_address(
+                    &[b"delegation", reward_shard.as_ref()],
+                    &DELEGATION_PROGRAM_ID,
+                );
+                let (delegation_metadata_reward_shard, _bump) = Pubkey::find_program_address(
+                    &[b"delegation-metadata", reward_shard.as_ref()],
+                    &DELEGATION_PROGRAM_ID,
+                );
+                Instruction {
+                    program_id: mor_core::ID,
+                    accounts: vec![
+                        AccountMeta::new(payer.pubkey(), true), // payer
+                        AccountMeta::new(buffer_reward_shard, false),
+                        AccountMeta::new(delegation_record_reward_shard, false),
+                        AccountMeta::new(delegation_metadata_reward_shard, false),
+                        AccountMeta::new(reward_shard, false), // reward_shard
+                        AccountMeta::new_readonly(mining_pool_pda, false), // mining_pool_pda
+                        AccountMeta::new(PROGRAM_ID, false),
+                        AccountMeta::new(DELEGATION_PROGRAM_ID, false), // delegation program
+                        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+                    ],
+                    data: DelegateRewardShard { index }.data(),
+                }
+            })
+            .collect();
+
+        let recent_blockhash = rpc_client.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        );
+        rpc_client.send_and_confirm_transaction_with_spinner(&transaction)?;
+    }
+    println!("Successfully delegated the reward shards to ER!");
+    Ok(())
+}
@@ -7,33 +7,66 @@ use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::{signature::Signer, transaction::Transaction};
 use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+use std::path::PathBuf;
 
+use crate::commands::allowlist::read_allowlist_proof;
+use crate::utils::errors::print_transaction_error;
 use crate::utils::helpers::{
     CLUSTER_URL, MINER_SEED, MINING_POOL_SEED, PROGRAM_ID, load_payer_keypair,
 };
 
-pub fn handle_initialize_miner(token_mint: String) {
+pub fn handle_initialize_miner(
+    token_mint: Option<String>,
+    pool: Option<String>,
+    allowlist: Option<PathBuf>,
+) {
     let authority = load_payer_keypair().unwrap();
     let client = RpcClient::new(CLUSTER_URL);
 
-    // Parse the token mint address
-    let mint_pubkey = match token_mint.parse::<Pubkey>() {
-        Ok(pubkey) => pubkey,
-        Err(e) => {
-            println!("{} {}", "Invalid token mint address:".red(), e);
+    let mining_pool_pda = match (token_mint, pool) {
+        (_, Some(pool)) => match pool.parse::<Pubkey>() {
+            Ok(pubkey) => pubkey,
+            Err(e) => {
+                println!("{} {}", "Invalid mining pool address:".red(), e);
+                return;
+            }
+        },
+        (Some(token_mint), None) => {
+            // Parse the token mint address
+            let mint_pubkey = match token_mint.parse::<Pubkey>() {
+                Ok(pubkey) => pubkey,
+                Err(e) => {
+                    println!("{} {}", "Invalid token mint address:".red(), e);
+                    return;
+                }
+            };
+
+            // Derive mining pool PDA - assuming the authority is also the pool maker
+            let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+                &[
+                    MINING_POOL_SEED,
+                    &authority.pubkey().as_ref(),
+                    &mint_pubkey.to_bytes(),
+                ],
+                &PROGRAM_ID,
+            );
+            mining_pool_pda
+        }
+        (None, None) => {
+            println!("{}", "Pass --token-mint or --pool.".red());
             return;
         }
     };
 
-    // Derive mining pool PDA - assuming the authority is also the pool maker
-    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
-        &[
-            MINING_POOL_SEED,
-            &authority.pubkey().as_ref(),
-            &mint_pubkey.to_bytes(),
-        ],
-        &PROGRAM_ID,
-    );
+    // Private pools need the authority's proof of being on their allowlist
+    let proof = match allowlist.map(|path| read_allowlist_proof(&path, &authority.pubkey())) {
+        Some(Ok(proof)) => proof,
+        Some(Err(e)) => {
+            println!("{}", e.red());
+            return;
+        }
+        None => vec![],
+    };
 
     // Derive miner PDA
     let (miner_pda, _bump) = Pubkey::find_program_address(
@@ -46,7 +79,7 @@ pub fn handle_initialize_miner(token_mint: String) {
     );
 
     // Create the instruction data
-    let instruction_data = instruction::InitializeMiner {}.data();
+    let instruction_data = instruction::InitializeMiner { proof }.data();
 
     let accounts = vec![
         AccountMeta::new(authority.pubkey(), true), // authority
@@ -97,6 +130,6 @@ pub fn handle_initialize_miner(token_mint: String) {
                 .to_string()
                 .cyan()
         ),
-        Err(e) => println!("{} {}", "Transaction failed:".red(), e),
+        Err(e) => print_transaction_error(&e),
     }
 }
@@ -0,0 +1,113 @@
+// Upgrades a mining pool created by an older program version to the current layout
+
+use anchor_lang::InstructionData;
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::instruction;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{CLUSTER_URL, MINT_AUTHORITY_SEED, PROGRAM_ID, load_payer_keypair};
+
+/// Size of a pool from before the pool settings, discriminator included
+const MINING_POOL_V0_LEN: usize = 1 + 32 + 32 + 1 + 8;
+
+/// Pool maker and mint of a pool that still has the layout from before the pool
+/// settings, None once it was migrated. Every layout starts with both.
+pub fn old_pool_keys(data: &[u8]) -> Option<(Pubkey, Pubkey)> {
+    if data.len() != MINING_POOL_V0_LEN {
+        return None;
+    }
+    let pool_maker = Pubkey::try_from(&data[1..33]).ok()?;
+    let mint = Pubkey::try_from(&data[33..65]).ok()?;
+    Some((pool_maker, mint))
+}
+
+/// migrate_pool, which the pool's reward state and miners need before theirs
+pub fn migrate_pool_instruction(
+    payer: Pubkey,
+    mining_pool_pda: Pubkey,
+    pool_maker: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    let (mint_authority, _bump) = Pubkey::find_program_address(
+        &[MINT_AUTHORITY_SEED, mining_pool_pda.as_ref()],
+        &PROGRAM_ID,
+    );
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),                       // payer
+            AccountMeta::new(mining_pool_pda, false),            // mining_pool_pda
+            AccountMeta::new_readonly(pool_maker, false),        // pool_maker
+            AccountMeta::new_readonly(mint, false),              // mint
+            AccountMeta::new_readonly(mint_authority, false),    // mint_authority
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+        ],
+        data: instruction::MigratePool {}.data(),
+    }
+}
+
+pub fn handle_migrate_pool(pool: String) {
+    let payer = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    let mining_pool_pda = match pool.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid pool address:".red(), e);
+            return;
+        }
+    };
+
+    let data = match client.get_account_data(&mining_pool_pda) {
+        Ok(data) => data,
+        Err(e) => {
+            println!("{} {}", "Failed to fetch the mining pool:".red(), e);
+            return;
+        }
+    };
+    let Some((pool_maker, mint)) = old_pool_keys(&data) else {
+        println!("{}", "The pool already has the current layout.".yellow());
+        return;
+    };
+
+    let ix = migrate_pool_instruction(payer.pubkey(), mining_pool_pda, pool_maker, mint);
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => println!(
+            "{} {}{}{}",
+            "Transaction sent successfully:".green(),
+            "https://explorer.solana.com/tx/".to_string().cyan(),
+            sig.to_string().cyan(),
+            "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                .to_string()
+                .cyan()
+        ),
+        Err(e) => print_transaction_error(&e),
+    }
+}
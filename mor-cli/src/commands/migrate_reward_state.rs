@@ -0,0 +1,139 @@
+// Upgrades a pool's reward state created by an older program version to the current layout
+
+use anchor_lang::{AnchorDeserialize, InstructionData};
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::instruction;
+use mor_core::states::MiningPoolPda;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+
+use crate::commands::initialize::create_reward_shards;
+use crate::commands::migrate_pool::{migrate_pool_instruction, old_pool_keys};
+use crate::commands::settle_epoch::reward_shard_address;
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{CLUSTER_URL, MINING_POOL_REWARD_SEED, PROGRAM_ID, load_payer_keypair};
+
+pub fn handle_migrate_reward_state(pool: String) {
+    let payer = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    let mining_pool_pda = match pool.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid pool address:".red(), e);
+            return;
+        }
+    };
+
+    let data = match client.get_account_data(&mining_pool_pda) {
+        Ok(data) => data,
+        Err(e) => {
+            println!("{} {}", "Failed to fetch the mining pool:".red(), e);
+            return;
+        }
+    };
+    // migrate_reward_state loads the pool, so an old pool is migrated first
+    let mut instructions = vec![];
+    let pool_maker = match old_pool_keys(&data) {
+        Some((pool_maker, mint)) => {
+            instructions.push(migrate_pool_instruction(
+                payer.pubkey(),
+                mining_pool_pda,
+                pool_maker,
+                mint,
+            ));
+            pool_maker
+        }
+        None => match MiningPoolPda::deserialize(&mut &data[1..]) {
+            Ok(pool_account) => pool_account.pool_maker,
+            Err(e) => {
+                println!("{} {}", "Failed to read the mining pool:".red(), e);
+                return;
+            }
+        },
+    };
+    // The reward state is derived from the pool maker
+    let (mining_pool_reward_state, _bump) = Pubkey::find_program_address(
+        &[
+            MINING_POOL_REWARD_SEED,
+            pool_maker.as_ref(),
+            mining_pool_pda.as_ref(),
+        ],
+        &PROGRAM_ID,
+    );
+
+    // Create the instruction data
+    let instruction_data = instruction::MigrateRewardState {}.data();
+
+    let accounts = vec![
+        AccountMeta::new(payer.pubkey(), true),              // payer
+        AccountMeta::new(mining_pool_reward_state, false),   // mining_pool_reward_state
+        AccountMeta::new_readonly(mining_pool_pda, false),   // mining_pool_pda
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+    ];
+
+    // Prepare the instruction
+    instructions.push(Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data,
+    });
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[&payer],
+        blockhash,
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => println!(
+            "{} {}{}{}",
+            "Transaction sent successfully:".green(),
+            "https://explorer.solana.com/tx/".to_string().cyan(),
+            sig.to_string().cyan(),
+            "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                .to_string()
+                .cyan()
+        ),
+        Err(e) => {
+            print_transaction_error(&e);
+            return;
+        }
+    }
+
+    // Pools from before the reward shards have none yet
+    if client
+        .get_account(&reward_shard_address(&mining_pool_pda, 0))
+        .is_err()
+    {
+        println!("Creating the pool's reward shards...");
+        if let Err(e) =
+            create_reward_shards(&client, &payer, mining_pool_pda, mining_pool_reward_state)
+        {
+            println!("{} {}", "Failed to create the reward shards:".red(), e);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// Sets the stake a miner needs to mine a pool
+
+use anchor_lang::InstructionData;
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::instruction;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{
+    CLUSTER_URL, MINING_POOL_SEED, PROGRAM_ID, load_payer_keypair, parse_pool_maker,
+};
+
+pub fn handle_set_min_stake(token_mint: String, pool_maker: Option<String>, min_stake: u64) {
+    let admin = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    // Parse the token mint address
+    let mint_pubkey = match token_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid token mint address:".red(), e);
+            return;
+        }
+    };
+
+    // The pool stays derived from its maker after it is handed over
+    let pool_maker = match parse_pool_maker(pool_maker.as_deref(), &admin.pubkey()) {
+        Ok(pool_maker) => pool_maker,
+        Err(e) => {
+            println!("{}", e.red());
+            return;
+        }
+    };
+
+    // Derive mining pool PDA
+    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+        &[MINING_POOL_SEED, pool_maker.as_ref(), mint_pubkey.as_ref()],
+        &PROGRAM_ID,
+    );
+
+    if min_stake == 0 {
+        println!("{} {}", "Minimum stake:".cyan(), "none".yellow());
+    } else {
+        println!("{} {} base units", "Minimum stake:".cyan(), min_stake);
+    }
+
+    let instruction_data = instruction::SetMinStake { min_stake }.data();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(admin.pubkey(), true), // admin
+        AccountMeta::new(mining_pool_pda, false),        // mining_pool_pda
+    ];
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[&admin], blockhash);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => println!(
+            "{} {}{}{}",
+            "Transaction sent successfully:".green(),
+            "https://explorer.solana.com/tx/".to_string().cyan(),
+            sig.to_string().cyan(),
+            "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                .to_string()
+                .cyan()
+        ),
+        Err(e) => print_transaction_error(&e),
+    }
+}
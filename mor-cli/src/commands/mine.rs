@@ -1,237 +1,1018 @@
 use anchor_lang::InstructionData;
+use clap::Parser;
 use colored::*;
-use indicatif::ProgressBar;
+use equix::{EquiX, SolverMemory};
+use indicatif::{MultiProgress, ProgressBar};
+use mor_core::helpers::constants::{
+    MAX_BATCH_SOLUTIONS, MAX_EPOCH_OFFSET, MAX_REWARD_SCALE_BITS, MULTIPLIER_BASE_BPS,
+    REWARD_SHARDS,
+};
+use mor_core::helpers::utils::{
+    equix_seed, generate_challenge, leading_zero_bits, pow_hash, reward_shard_index,
+};
 use mor_core::instruction;
-use sha3::{Digest, Sha3_256};
+use mor_core::states::{
+    BonusMint, EpochSolution, MultiplierTier, PoolMode, PowAlgorithm, RewardShard, VestingConfig,
+};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::{signature::Signer, transaction::Transaction};
-use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+use solana_sdk::signature::{Keypair, Signer, read_keypair_file};
+use std::cmp::Reverse;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::sync::{
-    Arc,
-    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+    atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering},
 };
-use std::thread;
+use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
+use crate::commands;
+use crate::commands::settle_epoch::reward_shard_address;
+use crate::utils::errors::print_transaction_error;
 use crate::utils::helpers::{
     CLUSTER_URL, EPOCH_SLOT_LENGTH, ER_CLUSTER_URL, MINER_SEED, MINING_POOL_REWARD_SEED,
-    MINING_POOL_SEED, PROGRAM_ID, load_payer_keypair,
+    MINING_POOL_SEED, PROGRAM_ID, SLOT_DURATION, confirm, effective_multiplier, format_multiplier,
+    load_payer_keypair, pow_algorithm_name,
 };
+use crate::utils::transaction::{SendConfig, send_with_retries};
 use std::str::FromStr;
 
-// Define the Rust struct for the miner account (must match on-chain layout)
+// Define the Rust structs for the accounts read while mining (must match on-chain layout)
 use anchor_lang::AnchorDeserialize;
 
 #[derive(Debug, AnchorDeserialize)]
 pub struct Miner {
     _authority: Pubkey,
     _last_epoch_mined: u64,
-    _rewards: u64,
-    _multiplier: u8,
-    _staked_amount: u64,
+    rewards: u64,
+    _multiplier: u32,
+    staked_amount: u64,
     difficulty: u8,
     _pool: Pubkey,
-    _last_staked_timestamp: i64,
+    last_staked_timestamp: i64,
+    _bump: u8,
+    _version: u8,
+    signer: Pubkey,
+    _beneficiary: Pubkey,
+}
+
+#[derive(Debug, AnchorDeserialize)]
+pub struct MiningPool {
+    pool_maker: Pubkey,
+    _mint: Pubkey,
     _bump: u8,
+    _amount: u64,
+    pow_algorithm: PowAlgorithm,
+    mint_decimals: u8,
+    multiplier_tiers: Vec<MultiplierTier>,
+    commission_bps: u16,
+    _mode: PoolMode,
+    _supply_cap: u64,
+    _mint_authority_bump: u8,
+    _bonus_mints: Vec<BonusMint>,
+    _vesting: Option<VestingConfig>,
+    _allowlist_root: Option<[u8; 32]>,
+    min_stake: u64,
+}
+
+/// The program accepts solutions for epochs within this distance of the current one
+pub const EPOCH_TOLERANCE: u64 = MAX_EPOCH_OFFSET;
+
+/// How often the submitter polls the slot
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Number of nonces a worker tries between progress reports. An Equi-X
+/// attempt runs a whole puzzle solver, so it reports far more often.
+pub fn hash_batch(algorithm: PowAlgorithm) -> u64 {
+    match algorithm {
+        PowAlgorithm::Sha3 => 100_000,
+        PowAlgorithm::Equix => 10,
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct MineArgs {
+    /// Token mint of a pool you created (the pool is derived from your keypair)
+    #[arg(long)]
+    pub token_mint: Option<String>,
+
+    /// Mining pool PDA to mine (repeat to mine several pools)
+    #[arg(long = "pool", value_name = "POOL")]
+    pub pools: Vec<String>,
+
+    /// File listing mining pool PDAs, one per line (# starts a comment)
+    #[arg(long, value_name = "PATH")]
+    pub pools_file: Option<PathBuf>,
+
+    /// Hashing threads shared across all pools
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub threads: Option<u64>,
+
+    /// Retries per solution on transient errors (expired blockhash, RPC failures)
+    #[arg(long, default_value_t = 3)]
+    pub max_retries: u32,
+
+    /// Delay between retries in milliseconds
+    #[arg(long, default_value_t = 500)]
+    pub retry_delay_ms: u64,
+
+    /// Submit solutions to the Solana base layer instead of the ER
+    #[arg(long)]
+    pub base_layer: bool,
+
+    /// Priority fee in micro-lamports per compute unit (base layer only)
+    #[arg(long, requires = "base_layer")]
+    pub compute_unit_price: Option<u64>,
+
+    /// Compute unit limit for each submission (base layer only)
+    #[arg(long, requires = "base_layer")]
+    pub compute_unit_limit: Option<u32>,
+
+    /// How many epochs ahead of the current one to precompute solutions for
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u64).range(0..=4))]
+    pub lookahead: u64,
+
+    /// Maximum number of solutions waiting to be submitted, per pool
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u64).range(1..))]
+    pub queue_size: u64,
+
+    /// Keep searching for a better hash until this many seconds before the
+    /// epoch ends, then submit the best one (an epoch length or more submits
+    /// the first valid hash)
+    #[arg(long, default_value_t = 5)]
+    pub deadline_buffer: u64,
+
+    /// Hot key that signs and pays for submissions instead of your wallet,
+    /// as set with set-miner-signer
+    #[arg(long, value_name = "PATH")]
+    pub miner_keypair: Option<PathBuf>,
+
+    /// Wallet that owns the miners and their rewards (defaults to your keypair).
+    /// Lets a box holding only the --miner-keypair mine for it
+    #[arg(long, value_name = "PUBKEY", requires = "miner_keypair")]
+    pub authority: Option<String>,
+}
+
+/// A nonce found for an epoch, waiting to be submitted
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub epoch: u64,
+    pub nonce: u64,
+    /// Equi-X solution for the nonce (zero for SHA3 pools)
+    pub digest: [u8; 16],
+    /// Leading zero bits of the resulting hash
+    pub difficulty: u32,
+}
+
+/// Digest and hash of a nonce attempt under a pool's PoW algorithm, as
+/// checked by the program. Returns None when Equi-X has no solution for the
+/// nonce. `memory` holds the Equi-X solver memory between attempts.
+pub fn attempt_nonce(
+    algorithm: PowAlgorithm,
+    challenge: &[u8; 32],
+    nonce: u64,
+    memory: &mut Option<SolverMemory>,
+) -> Option<([u8; 16], [u8; 32])> {
+    let digest = match algorithm {
+        PowAlgorithm::Sha3 => [0; 16],
+        PowAlgorithm::Equix => {
+            let memory = memory.get_or_insert_with(SolverMemory::new);
+            let equix = EquiX::new(&equix_seed(challenge, nonce)).ok()?;
+            equix.solve_with_memory(memory).first()?.to_bytes()
+        }
+    };
+    Some((digest, pow_hash(algorithm, challenge, nonce, &digest)))
+}
+
+/// Nonce, digest and leading zero bits of the best hash found for a challenge
+pub type BestHash = Mutex<Option<(u64, [u8; 16], u32)>>;
+
+/// Searches nonces `start`, `start + stride`, ... and records in `best` every
+/// hash with at least `difficulty` leading zero bits that beats it.
+/// `on_progress` is called with the nonces tried since its last call every
+/// `hash_batch` nonces and after each improvement, and stops the search by
+/// returning false.
+pub fn find_nonce(
+    algorithm: PowAlgorithm,
+    challenge: &[u8; 32],
+    difficulty: u32,
+    start: u64,
+    stride: u64,
+    best: &BestHash,
+    mut on_progress: impl FnMut(u64) -> bool,
+) {
+    let batch = hash_batch(algorithm);
+    let mut memory = None;
+    let mut nonce = start;
+    let mut tried: u64 = 0;
+    // Bits a hash needs to beat the best one this worker knows of
+    let mut threshold = difficulty;
+    loop {
+        let mut improved = false;
+        if let Some((digest, hash)) = attempt_nonce(algorithm, challenge, nonce, &mut memory) {
+            let zero_bits = leading_zero_bits(&hash);
+            if zero_bits >= threshold {
+                let mut best = best.lock().unwrap();
+                match *best {
+                    Some((_, _, best_bits)) if best_bits >= zero_bits => {}
+                    _ => {
+                        *best = Some((nonce, digest, zero_bits));
+                        improved = true;
+                    }
+                }
+                threshold = best.map_or(difficulty, |(_, _, best_bits)| best_bits + 1);
+            }
+        }
+        nonce = nonce.wrapping_add(stride);
+        tried += 1;
+        if improved || tried == batch {
+            if !on_progress(tried) {
+                return;
+            }
+            tried = 0;
+        }
+    }
+}
+
+/// Splits the nonce space across `threads` workers and returns the best
+/// solution found. Once there is a solution the search stops as soon as
+/// `past_deadline` returns true, so a deadline that has already passed returns
+/// the first solution found. `tried` counts the nonces hashed by all workers.
+pub fn solve(
+    algorithm: PowAlgorithm,
+    challenge: &[u8; 32],
+    difficulty: u32,
+    threads: usize,
+    running: &AtomicBool,
+    past_deadline: &(dyn Fn() -> bool + Sync),
+    tried: &AtomicU64,
+) -> Option<(u64, [u8; 16], u32)> {
+    let best: BestHash = Mutex::new(None);
+    thread::scope(|scope| {
+        for start in 0..threads {
+            let best = &best;
+            scope.spawn(move || {
+                find_nonce(
+                    algorithm,
+                    challenge,
+                    difficulty,
+                    start as u64,
+                    threads as u64,
+                    best,
+                    |nonces| {
+                        tried.fetch_add(nonces, Ordering::Relaxed);
+                        let found = best.lock().unwrap().is_some();
+                        running.load(Ordering::SeqCst) && !(found && past_deadline())
+                    },
+                );
+            });
+        }
+    });
+    best.into_inner().unwrap()
 }
 
-pub fn handle_mine(token_mint: String) -> Result<(), Box<dyn std::error::Error>> {
-    // Parse token mint address
-    let mint_pubkey = Pubkey::from_str(&token_mint)?;
+/// Fetches an account from the ER, falling back to the base layer
+pub fn fetch_account<T: AnchorDeserialize>(
+    er_client: &RpcClient,
+    base_client: &RpcClient,
+    pubkey: &Pubkey,
+    name: &str,
+) -> Result<T, String> {
+    let (account_data, source) = match er_client.get_account_data(pubkey) {
+        Ok(account_data) => (account_data, "er_client"),
+        Err(_) => match base_client.get_account_data(pubkey) {
+            Ok(account_data) => (account_data, "base"),
+            Err(e) => return Err(format!("Failed to fetch {} account: {}", name, e)),
+        },
+    };
+    // Skip the 1 byte discriminator. Accounts with a Vec (the pool's multiplier
+    // tiers) are sized for its maximum length, so ignore trailing bytes.
+    let mut data_slice = &account_data[1..];
+    T::deserialize(&mut data_slice).map_err(|e| {
+        format!(
+            "Failed to deserialize {} account from {}: {}",
+            name, source, e
+        )
+    })
+}
+
+/// Fetches the miner account from the ER, falling back to the base layer
+pub fn fetch_miner(
+    er_client: &RpcClient,
+    base_client: &RpcClient,
+    miner_pubkey: &Pubkey,
+) -> Result<Miner, String> {
+    fetch_account(er_client, base_client, miner_pubkey, "miner")
+}
+
+/// Reward a solution currently earns in a pool, mirroring submit_solution:
+/// 5% of the remaining pool (the miner's shard budget times REWARD_SHARDS)
+/// times the miner's multiplier (in basis points), doubled for each of the
+/// first MAX_REWARD_SCALE_BITS bits beyond the difficulty, capped at the
+/// shard's budget, less the pool maker's commission
+pub fn expected_reward(
+    shard_budget: u64,
+    multiplier_bps: u32,
+    commission_bps: u16,
+    extra_bits: u32,
+) -> u64 {
+    let base_reward = shard_budget.saturating_mul(REWARD_SHARDS as u64 * 5) / 100;
+    let reward = (base_reward as u128 * multiplier_bps as u128 / MULTIPLIER_BASE_BPS as u128)
+        .min(u64::MAX as u128) as u64;
+    let reward = reward
+        .saturating_mul(1 << extra_bits.min(MAX_REWARD_SCALE_BITS as u32))
+        .min(shard_budget);
+    let commission = (reward as u128 * commission_bps as u128 / MULTIPLIER_BASE_BPS as u128) as u64;
+    reward - commission
+}
 
-    println!("Mining with token mint: {}", mint_pubkey);
-    let payer = load_payer_keypair().map_err(|e| format!("Failed to load keypair: {}", e))?;
+/// Splits `threads` across pools in proportion to their weights. Every pool
+/// with a positive weight gets at least one thread while threads last.
+pub fn allocate_threads(weights: &[u64], threads: usize) -> Vec<usize> {
+    let mut allocation = vec![0; weights.len()];
+    let mut order: Vec<usize> = (0..weights.len()).filter(|&i| weights[i] > 0).collect();
+    order.sort_by(|&a, &b| weights[b].cmp(&weights[a]));
+
+    let mut remaining = threads;
+    for &i in &order {
+        if remaining == 0 {
+            return allocation;
+        }
+        allocation[i] = 1;
+        remaining -= 1;
+    }
+
+    // Share the rest by largest remainder
+    let total: u128 = order.iter().map(|&i| weights[i] as u128).sum();
+    if total == 0 || remaining == 0 {
+        return allocation;
+    }
+    let mut remainders = vec![];
+    let mut assigned = 0;
+    for &i in &order {
+        let exact = weights[i] as u128 * remaining as u128;
+        let share = (exact / total) as usize;
+        allocation[i] += share;
+        assigned += share;
+        remainders.push((exact % total, i));
+    }
+    remainders.sort_by_key(|&(remainder, _)| Reverse(remainder));
+    for (_, i) in remainders.into_iter().take(remaining - assigned) {
+        allocation[i] += 1;
+    }
+    allocation
+}
+
+/// Reads pool PDAs from a file, one per line
+fn read_pools_file(path: &PathBuf) -> Result<Vec<String>, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read pools file {}: {}", path.display(), e))?;
+    Ok(contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Short form of a pool address for status lines
+pub fn short(pubkey: &Pubkey) -> String {
+    let s = pubkey.to_string();
+    format!("{}..{}", &s[..4], &s[s.len() - 4..])
+}
+
+/// A pool mined in this session
+struct PoolSession {
+    mining_pool_pda: Pubkey,
+    mining_pool_reward_state: Pubkey,
+    reward_shard: Pubkey,
+    miner_pubkey: Pubkey,
+    pow_algorithm: PowAlgorithm,
+    threads: usize,
+    difficulty: Arc<AtomicU32>,
+    queued: Arc<AtomicUsize>,
+    receiver: Option<Receiver<Solution>>,
+    solver: Option<JoinHandle<()>>,
+    pending: Option<Solution>,
+    starting_rewards: u64,
+    submitted: u64,
+    dropped: u64,
+    status_bar: ProgressBar,
+}
+
+/// Everything a pool needs to submit a solution
+struct Submitter<'a> {
+    er_client: &'a RpcClient,
+    base_client: &'a RpcClient,
+    submit_client: &'a RpcClient,
+    signer: &'a Keypair,
+    authority: Pubkey,
+    send_config: &'a SendConfig,
+    explorer_cluster: &'a str,
+    progress: &'a MultiProgress,
+}
+
+impl PoolSession {
+    /// Takes the next solution the solver found, if any
+    fn next_solution(&mut self) -> Option<Solution> {
+        if let Some(solution) = self.pending.take() {
+            return Some(solution);
+        }
+        let receiver = self.receiver.as_ref()?;
+        match receiver.try_recv() {
+            Ok(solution) => {
+                self.queued.fetch_sub(1, Ordering::SeqCst);
+                Some(solution)
+            }
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                self.receiver = None;
+                None
+            }
+        }
+    }
+
+    /// Receives the due solutions, if any, and submits them. A miner that fell
+    /// behind on the Ephemeral Rollup catches up with one batch transaction.
+    fn poll(&mut self, epoch: u64, submitter: &Submitter) {
+        let pool = short(&self.mining_pool_pda);
+        let mut due: Vec<Solution> = vec![];
+        while let Some(solution) = self.next_solution() {
+            // Hold solutions for future epochs until their epoch starts
+            if solution.epoch > epoch {
+                self.pending = Some(solution);
+                break;
+            }
+            if epoch - solution.epoch > EPOCH_TOLERANCE {
+                self.dropped += 1;
+                submitter.progress.suspend(|| {
+                    println!(
+                        "{} {} {}",
+                        format!("[{}]", pool).cyan(),
+                        "Solution expired before submission for epoch:".yellow(),
+                        solution.epoch
+                    )
+                });
+                continue;
+            }
+            // The difficulty may have gone up since this solution was found
+            if solution.difficulty < self.difficulty.load(Ordering::SeqCst) {
+                self.dropped += 1;
+                submitter.progress.suspend(|| {
+                    println!(
+                        "{} {} {}",
+                        format!("[{}]", pool).cyan(),
+                        "Solution below current difficulty for epoch:".yellow(),
+                        solution.epoch
+                    )
+                });
+                continue;
+            }
+            due.push(solution);
+            if due.len() == MAX_BATCH_SOLUTIONS as usize {
+                break;
+            }
+        }
+        let (Some(first), Some(last)) = (due.first(), due.last()) else {
+            return;
+        };
+
+        let ix = if due.len() == 1 {
+            // Prepare instruction - match the current submit_solution structure
+            let instruction_data =
+                instruction::SubmitSolution::data(&instruction::SubmitSolution {
+                    nonce: first.nonce,
+                    epoch: first.epoch,
+                    digest: first.digest,
+                });
+            let accounts = vec![
+                AccountMeta::new(self.miner_pubkey, false), // miner
+                AccountMeta::new_readonly(self.mining_pool_pda, false), // mining_pool_pda
+                AccountMeta::new_readonly(self.mining_pool_reward_state, false), // mining_pool_reward_state
+                AccountMeta::new(self.reward_shard, false),                      // reward_shard
+                AccountMeta::new_readonly(submitter.authority, false),           // authority
+                AccountMeta::new(submitter.signer.pubkey(), true),               // signer
+            ];
+            Instruction {
+                program_id: PROGRAM_ID,
+                accounts,
+                data: instruction_data,
+            }
+        } else {
+            // The solver finds epochs in order, which is the order the program requires
+            let solutions = due
+                .iter()
+                .map(|solution| EpochSolution {
+                    epoch: solution.epoch,
+                    nonce: solution.nonce,
+                    digest: solution.digest,
+                })
+                .collect();
+            let instruction_data =
+                instruction::SubmitSolutions::data(&instruction::SubmitSolutions { solutions });
+            let accounts = vec![
+                AccountMeta::new(self.miner_pubkey, false), // miner
+                AccountMeta::new_readonly(self.mining_pool_pda, false), // mining_pool_pda
+                AccountMeta::new_readonly(self.mining_pool_reward_state, false), // mining_pool_reward_state
+                AccountMeta::new(self.reward_shard, false),                      // reward_shard
+                AccountMeta::new_readonly(submitter.authority, false),           // authority
+                AccountMeta::new(submitter.signer.pubkey(), true),               // signer
+            ];
+            Instruction {
+                program_id: PROGRAM_ID,
+                accounts,
+                data: instruction_data,
+            }
+        };
+
+        let epochs = if due.len() == 1 {
+            format!("epoch {}", first.epoch)
+        } else {
+            format!("epochs {}-{}", first.epoch, last.epoch)
+        };
+        let best = due
+            .iter()
+            .map(|solution| solution.difficulty)
+            .max()
+            .unwrap_or(0);
+        self.status_bar
+            .set_message(format!("[{}] Submitting solution for {}...", pool, epochs));
+        let result = send_with_retries(
+            submitter.submit_client,
+            &[ix],
+            submitter.signer,
+            submitter.send_config,
+        );
+        submitter.progress.suspend(|| match &result {
+            Ok(sig) => println!(
+                "{} {} {}{}{}",
+                format!("[{}]", pool).cyan(),
+                if due.len() == 1 {
+                    format!(
+                        "Epoch {} solution ({} bits) sent successfully:",
+                        first.epoch, best
+                    )
+                } else {
+                    format!(
+                        "{} solutions for {} (best {} bits) sent successfully:",
+                        due.len(),
+                        epochs,
+                        best
+                    )
+                }
+                .green(),
+                "https://explorer.solana.com/tx/".cyan(),
+                sig.to_string().cyan(),
+                submitter.explorer_cluster.cyan()
+            ),
+            Err(e) => {
+                print!("{} ", format!("[{}]", pool).cyan());
+                print_transaction_error(e)
+            }
+        });
+        match result {
+            Ok(_) => self.submitted += due.len() as u64,
+            Err(_) => self.dropped += due.len() as u64,
+        }
+
+        // Pick up the adjusted difficulty for the next solutions
+        match fetch_miner(
+            submitter.er_client,
+            submitter.base_client,
+            &self.miner_pubkey,
+        ) {
+            Ok(miner_account) => {
+                self.difficulty
+                    .store(miner_account.difficulty as u32, Ordering::SeqCst);
+            }
+            Err(e) => submitter.progress.suspend(|| println!("{}", e.red())),
+        }
+    }
+}
+
+pub fn handle_mine(args: &MineArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // The authority owns the miners, the signer submits for them
+    let authority = match &args.authority {
+        Some(authority) => Pubkey::from_str(authority)
+            .map_err(|e| format!("Invalid authority {}: {}", authority, e))?,
+        None => load_payer_keypair()
+            .map_err(|e| format!("Failed to load keypair: {}", e))?
+            .pubkey(),
+    };
+    let signer = match &args.miner_keypair {
+        Some(path) => read_keypair_file(path)
+            .map_err(|e| format!("Failed to load {}: {}", path.display(), e))?,
+        None => load_payer_keypair().map_err(|e| format!("Failed to load keypair: {}", e))?,
+    };
+    if signer.pubkey() != authority {
+        println!("{} {}", "Submitting with hot key:".cyan(), signer.pubkey());
+    }
 
     let er_client = RpcClient::new(ER_CLUSTER_URL);
     let base_client = RpcClient::new(CLUSTER_URL);
 
-    // Derive PDAs following the current mor-core structure
-    let (mining_pool_pda, _) = Pubkey::find_program_address(
-        &[
-            MINING_POOL_SEED,
-            payer.pubkey().as_ref(),
-            mint_pubkey.as_ref(),
-        ],
-        &PROGRAM_ID,
-    );
-
-    println!("Mining pool PDA: {}", mining_pool_pda.to_string());
-
-    let (mining_pool_reward_state, _) = Pubkey::find_program_address(
-        &[
-            MINING_POOL_REWARD_SEED,
-            payer.pubkey().as_ref(),
-            mining_pool_pda.as_ref(),
-        ],
-        &PROGRAM_ID,
-    );
-
-    let (miner_pubkey, _) = Pubkey::find_program_address(
-        &[
-            MINER_SEED,
-            payer.pubkey().as_ref(),
-            mining_pool_pda.as_ref(),
-        ],
-        &PROGRAM_ID,
-    );
+    // Solutions are verified against the clock of the chain they are sent to
+    let submit_client = if args.base_layer {
+        &base_client
+    } else {
+        &er_client
+    };
+    let send_config = SendConfig {
+        max_retries: args.max_retries,
+        retry_delay: Duration::from_millis(args.retry_delay_ms),
+        compute_unit_price: args.compute_unit_price,
+        compute_unit_limit: args.compute_unit_limit,
+        base_layer: args.base_layer,
+    };
+    let explorer_cluster = if args.base_layer {
+        "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+    } else {
+        "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A7799"
+    };
 
-    // Ctrl+C handling
+    // Collect the pools to mine
+    let mut pool_pdas: Vec<Pubkey> = vec![];
+    if let Some(token_mint) = &args.token_mint {
+        let mint_pubkey = Pubkey::from_str(token_mint)?;
+        println!("Mining with token mint: {}", mint_pubkey);
+        // Derive the pool PDA - assuming the authority is also the pool maker
+        let (mining_pool_pda, _) = Pubkey::find_program_address(
+            &[MINING_POOL_SEED, authority.as_ref(), mint_pubkey.as_ref()],
+            &PROGRAM_ID,
+        );
+        pool_pdas.push(mining_pool_pda);
+    }
+    let mut pool_args = args.pools.clone();
+    if let Some(path) = &args.pools_file {
+        pool_args.extend(read_pools_file(path)?);
+    }
+    for pool in pool_args {
+        let pubkey =
+            Pubkey::from_str(&pool).map_err(|e| format!("Invalid pool address {}: {}", pool, e))?;
+        if !pool_pdas.contains(&pubkey) {
+            pool_pdas.push(pubkey);
+        }
+    }
+    if pool_pdas.is_empty() {
+        println!(
+            "{}",
+            "Specify a pool with --token-mint, --pool or --pools-file.".red()
+        );
+        return Ok(());
+    }
+
+    // Load each pool's accounts and weigh it by the reward a solution earns now
+    let mut sessions: Vec<PoolSession> = vec![];
+    let mut weights: Vec<u64> = vec![];
+    let progress = MultiProgress::new();
+    for mining_pool_pda in pool_pdas {
+        println!("Mining pool PDA: {}", mining_pool_pda);
+        let pool: MiningPool =
+            match fetch_account(&er_client, &base_client, &mining_pool_pda, "mining pool") {
+                Ok(pool) => pool,
+                Err(e) => {
+                    println!("{}", e.red());
+                    continue;
+                }
+            };
+
+        let (mining_pool_reward_state, _) = Pubkey::find_program_address(
+            &[
+                MINING_POOL_REWARD_SEED,
+                pool.pool_maker.as_ref(),
+                mining_pool_pda.as_ref(),
+            ],
+            &PROGRAM_ID,
+        );
+
+        let (miner_pubkey, _) = Pubkey::find_program_address(
+            &[MINER_SEED, authority.as_ref(), mining_pool_pda.as_ref()],
+            &PROGRAM_ID,
+        );
+
+        // submit_solution no longer creates miners, so offer to run create-account
+        let miner_exists = base_client
+            .get_account_with_commitment(&miner_pubkey, base_client.commitment())
+            .map(|response| response.value.is_some())
+            .unwrap_or(true);
+        if !miner_exists {
+            println!(
+                "{} {}",
+                "You have no miner in this pool yet:".yellow(),
+                miner_pubkey
+            );
+            if args.authority.is_some() || !confirm("Create it now with create-account?") {
+                println!("{}", "Run create-account --pool first.".red());
+                continue;
+            }
+            commands::initialize_miner::handle_initialize_miner(
+                None,
+                Some(mining_pool_pda.to_string()),
+                None,
+            );
+            if !args.base_layer {
+                println!(
+                    "{}",
+                    "Delegate the new miner with delegate-miner to mine on the ER.".yellow()
+                );
+                continue;
+            }
+        }
+
+        let miner_account = match fetch_miner(&er_client, &base_client, &miner_pubkey) {
+            Ok(miner_account) => miner_account,
+            Err(e) => {
+                println!("{}", e.red());
+                continue;
+            }
+        };
+        if signer.pubkey() != authority && miner_account.signer != signer.pubkey() {
+            println!(
+                "{} {}",
+                "The miner's hot key is not this --miner-keypair, set it with set-miner-signer:"
+                    .red(),
+                miner_pubkey
+            );
+            continue;
+        }
+        // Hashing is wasted if the program will reject the solutions
+        if miner_account.staked_amount < pool.min_stake {
+            println!(
+                "{} {} {} {}",
+                "The pool requires a stake of".red(),
+                pool.min_stake,
+                "base units to mine, stake more with stake. Staked:".red(),
+                miner_account.staked_amount
+            );
+            continue;
+        }
+        // Solutions draw from the authority's reward shard, which settle-epoch funds
+        let reward_shard = reward_shard_address(&mining_pool_pda, reward_shard_index(&authority));
+        let shard: RewardShard =
+            match fetch_account(&er_client, &base_client, &reward_shard, "reward shard") {
+                Ok(shard) => shard,
+                Err(e) => {
+                    println!("{}", e.red());
+                    continue;
+                }
+            };
+        if shard.budget == 0 {
+            println!(
+                "{}",
+                "Your reward shard has no budget yet. Solutions are rejected until someone runs settle-epoch for the pool."
+                    .yellow()
+            );
+        }
+
+        // The program recomputes the multiplier from the stake on every submission
+        let multiplier = effective_multiplier(
+            miner_account.staked_amount,
+            miner_account.last_staked_timestamp,
+            pool.mint_decimals,
+            &pool.multiplier_tiers,
+        );
+        let reward = expected_reward(shard.budget, multiplier, pool.commission_bps, 0);
+        println!(
+            "{} {} {} {} bits {} {} {} {}",
+            "PoW algorithm:".cyan(),
+            pow_algorithm_name(pool.pow_algorithm),
+            "Miner difficulty:".cyan(),
+            miner_account.difficulty,
+            "Multiplier:".cyan(),
+            format_multiplier(multiplier),
+            "Expected reward:".cyan(),
+            reward
+        );
+        weights.push(reward);
+        sessions.push(PoolSession {
+            mining_pool_pda,
+            mining_pool_reward_state,
+            reward_shard,
+            miner_pubkey,
+            pow_algorithm: pool.pow_algorithm,
+            threads: 0,
+            difficulty: Arc::new(AtomicU32::new(miner_account.difficulty as u32)),
+            queued: Arc::new(AtomicUsize::new(0)),
+            receiver: None,
+            solver: None,
+            pending: None,
+            starting_rewards: miner_account.rewards,
+            submitted: 0,
+            dropped: 0,
+            status_bar: ProgressBar::hidden(),
+        });
+    }
+
+    let threads = args.threads.map(|t| t as usize).unwrap_or_else(|| {
+        thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
+    for (session, threads) in sessions.iter_mut().zip(allocate_threads(&weights, threads)) {
+        session.threads = threads;
+        if threads == 0 {
+            println!(
+                "{} {}",
+                "Skipping pool with no rewards available (or no threads left):".yellow(),
+                session.mining_pool_pda
+            );
+        }
+    }
+    sessions.retain(|session| session.threads > 0);
+    if sessions.is_empty() {
+        println!("{}", "No pool to mine.".red());
+        return Ok(());
+    }
+
+    let slot = submit_client.get_slot()?;
+    println!("{} {}", "Current epoch:".cyan(), slot / EPOCH_SLOT_LENGTH);
+
+    // State shared between the solver threads and the submitter
     let running = Arc::new(AtomicBool::new(true));
+    let current_slot = Arc::new(AtomicU64::new(slot));
+
+    // Solvers stop improving an epoch's hash this many slots before it ends
+    let buffer_slots = (Duration::from_secs(args.deadline_buffer).as_secs_f64()
+        / SLOT_DURATION.as_secs_f64())
+    .ceil() as u64;
+
+    // Ctrl+C handling
     let r = running.clone();
     ctrlc::set_handler(move || {
         r.store(false, Ordering::SeqCst);
     })
     .expect("Error setting Ctrl-C handler");
 
-    let mut last_epoch_mined: Option<u64> = None;
+    // Each pool's solver searches nonces for upcoming epochs while earlier
+    // solutions are being submitted, keeping the best hash of an epoch until
+    // its deadline and blocking once its queue is full. The
+    // solution a solver is blocked on counts towards the queue, hence the
+    // smaller channel.
+    for session in sessions.iter_mut() {
+        let solver_bar = progress.add(ProgressBar::new_spinner());
+        solver_bar.enable_steady_tick(Duration::from_millis(100));
+        session.status_bar = progress.add(ProgressBar::new_spinner());
+        session
+            .status_bar
+            .enable_steady_tick(Duration::from_millis(100));
 
-    while running.load(Ordering::SeqCst) {
-        // Fetch miner account initially from er_client, if it fails, try base_client
-        let miner_account = match er_client.get_account_data(&miner_pubkey) {
-            Ok(account_data) => {
-                let data_slice = &account_data[1..];
-                match Miner::try_from_slice(data_slice) {
-                    Ok(miner_account) => miner_account,
-                    Err(e) => {
-                        println!(
-                            "{} {}",
-                            "Failed to deserialize miner account from er_client:".red(),
-                            e
-                        );
-                        break;
-                    }
+        let (sender, receiver) = mpsc::sync_channel::<Solution>(args.queue_size as usize - 1);
+        session.receiver = Some(receiver);
+
+        let running = running.clone();
+        let current_slot = current_slot.clone();
+        let difficulty = session.difficulty.clone();
+        let queued = session.queued.clone();
+        let lookahead = args.lookahead;
+        let threads = session.threads;
+        let pow_algorithm = session.pow_algorithm;
+        let pool = short(&session.mining_pool_pda);
+        session.solver = Some(thread::spawn(move || {
+            let mut next_epoch = current_slot.load(Ordering::SeqCst) / EPOCH_SLOT_LENGTH;
+            while running.load(Ordering::SeqCst) {
+                let now = current_slot.load(Ordering::SeqCst) / EPOCH_SLOT_LENGTH;
+                next_epoch = next_epoch.max(now);
+                if next_epoch > now + lookahead {
+                    solver_bar.set_message(format!("[{}] Waiting for epoch {}...", pool, now + 1));
+                    thread::sleep(Duration::from_millis(200));
+                    continue;
                 }
-            }
-            Err(_) => match base_client.get_account_data(&miner_pubkey) {
-                Ok(account_data) => {
-                    let data_slice = &account_data[1..];
-                    match Miner::try_from_slice(data_slice) {
-                        Ok(miner_account) => miner_account,
-                        Err(e) => {
-                            println!(
-                                "{} {}",
-                                "Failed to deserialize miner account from base:".red(),
-                                e
-                            );
-                            break;
-                        }
+
+                let target = difficulty.load(Ordering::SeqCst);
+                let challenge = generate_challenge(&authority, next_epoch);
+                let deadline_slot =
+                    ((next_epoch + 1) * EPOCH_SLOT_LENGTH).saturating_sub(buffer_slots);
+                let past_deadline = || current_slot.load(Ordering::SeqCst) >= deadline_slot;
+                solver_bar.set_message(format!(
+                    "[{}] Solving epoch {} on {} threads...",
+                    pool, next_epoch, threads
+                ));
+                let tried = AtomicU64::new(0);
+                let found = thread::scope(|scope| {
+                    let handle = scope.spawn(|| {
+                        solve(
+                            pow_algorithm,
+                            &challenge,
+                            target,
+                            threads,
+                            &running,
+                            &past_deadline,
+                            &tried,
+                        )
+                    });
+                    while !handle.is_finished() {
+                        solver_bar.set_message(format!(
+                            "[{}] Solving epoch {} on {} threads: tried {} nonces...",
+                            pool,
+                            next_epoch,
+                            threads,
+                            tried.load(Ordering::Relaxed)
+                        ));
+                        thread::sleep(Duration::from_millis(100));
                     }
-                }
-                Err(e) => {
-                    println!("{} {}", "Failed to fetch miner account:".red(), e);
+                    handle.join().unwrap()
+                });
+                // Ctrl+C, the session is over
+                let Some((nonce, digest, achieved)) =
+                    found.filter(|_| running.load(Ordering::SeqCst))
+                else {
+                    break;
+                };
+
+                queued.fetch_add(1, Ordering::SeqCst);
+                let solution = Solution {
+                    epoch: next_epoch,
+                    nonce,
+                    digest,
+                    difficulty: achieved,
+                };
+                if sender.send(solution).is_err() {
                     break;
                 }
-            },
-        };
+                next_epoch += 1;
+            }
+            solver_bar.finish_and_clear();
+        }));
+    }
 
-        let difficulty = miner_account.difficulty as usize;
-        println!("{} {}", "Miner difficulty:".cyan(), difficulty);
+    let submitter = Submitter {
+        er_client: &er_client,
+        base_client: &base_client,
+        submit_client,
+        signer: &signer,
+        authority,
+        send_config: &send_config,
+        explorer_cluster,
+        progress: &progress,
+    };
 
+    while running.load(Ordering::SeqCst) {
         // Get current slot and compute epoch
-        let slot = match er_client.get_slot() {
+        let slot = match submit_client.get_slot() {
             Ok(s) => s,
             Err(e) => {
-                println!("{} {}", "Failed to fetch slot:".red(), e);
+                progress.suspend(|| println!("{} {}", "Failed to fetch slot:".red(), e));
                 break;
             }
         };
         let epoch = slot / EPOCH_SLOT_LENGTH;
-        println!("{} {}", "Current epoch:".cyan(), epoch);
+        current_slot.store(slot, Ordering::SeqCst);
 
-        // Only mine if epoch has advanced
-        if let Some(last) = last_epoch_mined {
-            if epoch == last {
-                thread::sleep(Duration::from_secs(5));
-                continue;
-            }
+        for session in sessions.iter_mut() {
+            session.status_bar.set_message(format!(
+                "[{}] Epoch {} | queued {}/{} | submitted {} | dropped {}",
+                short(&session.mining_pool_pda),
+                epoch,
+                session.queued.load(Ordering::SeqCst),
+                args.queue_size,
+                session.submitted,
+                session.dropped
+            ));
+            session.poll(epoch, &submitter);
         }
-        last_epoch_mined = Some(epoch);
-
-        // Generate challenge
-        let mut epoch_buf = [0u8; 8];
-        epoch_buf.copy_from_slice(&epoch.to_le_bytes());
-        let mut hasher = Sha3_256::new();
-        hasher.update(payer.pubkey().to_bytes());
-        hasher.update(epoch_buf);
-        let challenge = hasher.finalize_reset();
-
-        // Find valid nonce
-        let spinner = ProgressBar::new_spinner();
-        spinner.set_message("Searching for valid nonce...");
-        spinner.enable_steady_tick(Duration::from_millis(100));
-        let mut nonce: u64 = 0;
-        let found_nonce = loop {
-            if !running.load(Ordering::SeqCst) {
-                spinner.finish_and_clear();
-                println!("{}", "Mining interrupted by user.".yellow());
-                return Ok(());
-            }
-            let mut nonce_buf = [0u8; 8];
-            nonce_buf.copy_from_slice(&nonce.to_le_bytes());
-            let mut hasher = Sha3_256::new();
-            hasher.update(&challenge);
-            hasher.update(nonce_buf);
-            let hash = hasher.finalize();
-            if hash[..difficulty].iter().all(|&b| b == 0) {
-                break nonce;
-            }
-            nonce = nonce.wrapping_add(1);
-            if nonce % 100_000 == 0 {
-                spinner.set_message(format!("Tried {} nonces...", nonce));
-            }
-        };
-        spinner.finish_and_clear();
-        println!("{} {}", "Found valid nonce:".green(), found_nonce);
 
-        // Prepare instruction - match the current submit_solution structure
-        let instruction_data = instruction::SubmitSolution::data(&instruction::SubmitSolution {
-            nonce: found_nonce,
-            epoch,
-        });
-        let signers = &[&payer];
-        let accounts = vec![
-            AccountMeta::new(miner_pubkey, false),               // miner
-            AccountMeta::new_readonly(mining_pool_pda, false),   // mining_pool_pda
-            AccountMeta::new(mining_pool_reward_state, false),   // mining_pool_reward_state
-            AccountMeta::new(payer.pubkey(), true),              // authority
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
-        ];
-        let ix = Instruction {
-            program_id: PROGRAM_ID,
-            accounts,
-            data: instruction_data,
-        };
-        let blockhash = match er_client.get_latest_blockhash() {
-            Ok(bh) => bh,
-            Err(e) => {
-                println!("{} {}", "Failed to get recent blockhash:".red(), e);
-                break;
-            }
-        };
-        let tx =
-            Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), signers, blockhash);
-
-        let pb = ProgressBar::new_spinner();
-        pb.set_message("Submitting solution...");
-        pb.enable_steady_tick(Duration::from_millis(100));
-        let result = er_client.send_and_confirm_transaction(&tx);
-        pb.finish_and_clear();
-        match result {
-            Ok(sig) => println!(
-                "{} {}{}{}",
-                "Transaction sent successfully:".green(),
-                "https://explorer.solana.com/tx/".cyan(),
-                sig.to_string().cyan(),
-                "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A7799".cyan()
-            ),
-            Err(e) => println!("{} {}", "Transaction failed:".red(), e),
+        // Stop once every solver has exited and nothing is left to submit
+        if sessions
+            .iter()
+            .all(|session| session.receiver.is_none() && session.pending.is_none())
+        {
+            break;
         }
-        // Print after every success, then wait for next epoch
-        println!("{} {}", "Waiting for next epoch...".yellow(), epoch + 1);
-        thread::sleep(Duration::from_secs(5));
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    running.store(false, Ordering::SeqCst);
+    for session in sessions.iter_mut() {
+        session.receiver = None;
+        if let Some(solver) = session.solver.take() {
+            let _ = solver.join();
+        }
+        session.status_bar.finish_and_clear();
+    }
+
+    println!("{}", "Mining stopped.".yellow());
+    for session in &sessions {
+        let earned = fetch_miner(&er_client, &base_client, &session.miner_pubkey)
+            .map(|miner| {
+                miner
+                    .rewards
+                    .saturating_sub(session.starting_rewards)
+                    .to_string()
+            })
+            .unwrap_or_else(|_| "unknown".to_string());
+        println!(
+            "{} {} {} {} {} {} {} {}",
+            "Pool:".cyan(),
+            session.mining_pool_pda,
+            "Submitted:".cyan(),
+            session.submitted,
+            "Dropped:".cyan(),
+            session.dropped,
+            "Rewards earned:".cyan(),
+            earned
+        );
     }
-    println!("{}", "Mining stopped.");
     Ok(())
 }
@@ -1,13 +1,33 @@
+pub mod admin;
+pub mod allowlist;
+pub mod bonus;
+pub mod commission;
 pub mod initialize;
 pub mod initialize_miner;
+pub mod migrate_miner;
+pub mod migrate_pool;
+pub mod migrate_reward_state;
+pub mod min_stake;
+pub mod pools;
+pub mod protocol;
+pub mod set_beneficiary;
+pub mod set_miner_signer;
+pub mod set_multiplier_tiers;
 
 pub mod delegate_miner;
 pub mod undelegate_miner;
 
+pub mod bench;
+pub mod epochs;
 pub mod mine;
+pub mod settle_epoch;
 
 pub mod claim;
 
+pub mod stake;
+pub mod unstake;
+pub mod vesting;
+
 pub mod get_account;
 
 pub mod setup_keypair;
@@ -0,0 +1,175 @@
+// Lists the mining pools of the program and their terms
+
+use anchor_lang::AnchorDeserialize;
+use clap::Subcommand;
+use colored::*;
+use mor_core::states::{BonusMint, MultiplierTier, PoolMode, PowAlgorithm, VestingConfig};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::commands::admin::AdminCommand;
+use crate::commands::allowlist::AllowlistCommand;
+use crate::commands::mine::{fetch_account, short};
+use crate::utils::helpers::{
+    CLUSTER_URL, ER_CLUSTER_URL, MINING_POOL_REWARD_SEED, PROGRAM_ID, format_bps,
+    format_multiplier, pool_mode_name, pow_algorithm_name,
+};
+
+/// Discriminator of MiningPoolPda accounts
+const MINING_POOL_DISCRIMINATOR: u8 = 1;
+
+#[derive(Subcommand, Debug)]
+pub enum PoolsCommand {
+    /// List every mining pool with its algorithm, remaining rewards and commission
+    List,
+    /// Build or set the allowlist of a private pool
+    Allowlist {
+        #[command(subcommand)]
+        command: AllowlistCommand,
+    },
+    /// Hand a pool over to a new admin, such as a multisig
+    Admin {
+        #[command(subcommand)]
+        command: AdminCommand,
+    },
+}
+
+#[derive(Debug, AnchorDeserialize)]
+struct MiningPool {
+    pool_maker: Pubkey,
+    mint: Pubkey,
+    _bump: u8,
+    _amount: u64,
+    pow_algorithm: PowAlgorithm,
+    _mint_decimals: u8,
+    _multiplier_tiers: Vec<MultiplierTier>,
+    commission_bps: u16,
+    mode: PoolMode,
+    _supply_cap: u64,
+    _mint_authority_bump: u8,
+    bonus_mints: Vec<BonusMint>,
+    _vesting: Option<VestingConfig>,
+    _allowlist_root: Option<[u8; 32]>,
+    min_stake: u64,
+    admin: Pubkey,
+    pending_admin: Pubkey,
+}
+
+#[derive(Debug, AnchorDeserialize)]
+struct MiningPoolReward {
+    _pool_pda: Pubkey,
+    amount: u64,
+    _bump: u8,
+    maker_rewards: u64,
+    bonus_amounts: Vec<u64>,
+}
+
+pub fn handle_list_pools() {
+    let er_client = RpcClient::new(ER_CLUSTER_URL);
+    let base_client = RpcClient::new(CLUSTER_URL);
+
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            vec![MINING_POOL_DISCRIMINATOR],
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let accounts = match base_client.get_program_accounts_with_config(&PROGRAM_ID, config) {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            println!("{} {}", "Failed to fetch mining pools:".red(), e);
+            return;
+        }
+    };
+
+    let mut pools: Vec<(Pubkey, MiningPool)> = accounts
+        .into_iter()
+        .filter_map(|(pubkey, account)| {
+            // Pools are sized for their most multiplier tiers, ignore the rest
+            MiningPool::deserialize(&mut &account.data[1..])
+                .ok()
+                .map(|pool| (pubkey, pool))
+        })
+        .collect();
+    if pools.is_empty() {
+        println!("{}", "No mining pools found.".yellow());
+        return;
+    }
+    pools.sort_by_key(|(_, pool)| (pool.mint, pool.pool_maker));
+
+    println!(
+        "{:<46}{:<12}{:<12}{:<8}{:<7}{:>20}{:>12}{:>20}{:>20}",
+        "Pool",
+        "Mint",
+        "Maker",
+        "PoW",
+        "Mode",
+        "Remaining",
+        "Commission",
+        "Maker rewards",
+        "Min stake"
+    );
+    for (pubkey, pool) in pools {
+        // The reward state lives on the ER while the pool is delegated
+        let (reward_state, _) = Pubkey::find_program_address(
+            &[
+                MINING_POOL_REWARD_SEED,
+                pool.pool_maker.as_ref(),
+                pubkey.as_ref(),
+            ],
+            &PROGRAM_ID,
+        );
+        let reward = fetch_account::<MiningPoolReward>(
+            &er_client,
+            &base_client,
+            &reward_state,
+            "mining pool reward",
+        )
+        .ok();
+        let (remaining, maker_rewards) = match &reward {
+            Some(reward) => (reward.amount.to_string(), reward.maker_rewards.to_string()),
+            None => ("?".to_string(), "?".to_string()),
+        };
+        println!(
+            "{:<46}{:<12}{:<12}{:<8}{:<7}{:>20}{:>12}{:>20}{:>20}",
+            pubkey.to_string(),
+            short(&pool.mint),
+            short(&pool.pool_maker),
+            pow_algorithm_name(pool.pow_algorithm),
+            pool_mode_name(pool.mode),
+            remaining,
+            format_bps(pool.commission_bps),
+            maker_rewards,
+            pool.min_stake
+        );
+        if pool.admin != pool.pool_maker {
+            println!("  {} {}", "admin".cyan(), pool.admin);
+        }
+        if pool.pending_admin != Pubkey::default() {
+            println!("  {} {}", "proposed admin".cyan(), pool.pending_admin);
+        }
+        // Bonus budgets line up with the pool's bonus mints
+        for (index, bonus) in pool.bonus_mints.iter().enumerate() {
+            let budget = reward
+                .as_ref()
+                .and_then(|reward| reward.bonus_amounts.get(index))
+                .map_or("?".to_string(), |amount| amount.to_string());
+            println!(
+                "  {} {}, {} remaining, {} per token mined, ends at {}",
+                "bonus".cyan(),
+                bonus.mint,
+                budget,
+                format_multiplier(bonus.rate_bps),
+                bonus.ends_at
+            );
+        }
+    }
+}
@@ -0,0 +1,261 @@
+// Sets up and tunes the protocol-wide config: its admin, treasury and fees
+
+use anchor_lang::{AnchorDeserialize, InstructionData};
+use clap::Subcommand;
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::instruction;
+use mor_core::states::ProtocolConfig;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{
+    BPF_LOADER_UPGRADEABLE_ID, CLUSTER_URL, PROGRAM_ID, PROTOCOL_CONFIG_SEED, format_bps,
+    format_sol, load_payer_keypair,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum ProtocolCommand {
+    /// Create the protocol config (signed by the program's upgrade authority, run once)
+    Init {
+        /// Wallet collecting the protocol's fees
+        #[arg(long)]
+        treasury: String,
+        /// Lamports charged for each new pool
+        #[arg(long, default_value_t = 0)]
+        pool_creation_fee: u64,
+        /// Share of each claim paid to the treasury, in basis points (at most 1000)
+        #[arg(long, default_value_t = 0)]
+        protocol_fee_bps: u16,
+    },
+    /// Show the protocol config
+    Show,
+    /// Change the protocol config (only the protocol admin can)
+    Set {
+        /// Hand the protocol admin role to this wallet
+        #[arg(long)]
+        admin: Option<String>,
+        /// Wallet collecting the protocol's fees
+        #[arg(long)]
+        treasury: Option<String>,
+        /// Lamports charged for each new pool
+        #[arg(long)]
+        pool_creation_fee: Option<u64>,
+        /// Share of each claim paid to the treasury, in basis points (at most 1000)
+        #[arg(long)]
+        protocol_fee_bps: Option<u16>,
+    },
+}
+
+/// Address of the protocol config
+pub fn protocol_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[PROTOCOL_CONFIG_SEED], &PROGRAM_ID).0
+}
+
+/// Fetches the protocol config, None until the operator ran protocol init
+pub fn fetch_protocol_config(client: &RpcClient) -> Option<ProtocolConfig> {
+    client
+        .get_account_data(&protocol_config_address())
+        .ok()
+        .and_then(|data| ProtocolConfig::deserialize(&mut &data[1..]).ok())
+}
+
+pub fn handle_protocol(command: ProtocolCommand) {
+    match command {
+        ProtocolCommand::Init {
+            treasury,
+            pool_creation_fee,
+            protocol_fee_bps,
+        } => handle_init(treasury, pool_creation_fee, protocol_fee_bps),
+        ProtocolCommand::Show => handle_show(),
+        ProtocolCommand::Set {
+            admin,
+            treasury,
+            pool_creation_fee,
+            protocol_fee_bps,
+        } => handle_set(admin, treasury, pool_creation_fee, protocol_fee_bps),
+    }
+}
+
+fn handle_init(treasury: String, pool_creation_fee: u64, protocol_fee_bps: u16) {
+    let admin = load_payer_keypair().unwrap();
+
+    let treasury = match treasury.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid treasury address:".red(), e);
+            return;
+        }
+    };
+
+    // The upgrade authority recorded in the program data proves who may do this
+    let (program_data, _bump) =
+        Pubkey::find_program_address(&[PROGRAM_ID.as_ref()], &BPF_LOADER_UPGRADEABLE_ID);
+
+    print_fees(&treasury, pool_creation_fee, protocol_fee_bps);
+
+    let instruction_data = instruction::InitializeProtocolConfig {
+        treasury,
+        pool_creation_fee,
+        protocol_fee_bps,
+    }
+    .data();
+
+    let accounts = vec![
+        AccountMeta::new(admin.pubkey(), true),              // admin
+        AccountMeta::new(protocol_config_address(), false),  // protocol_config
+        AccountMeta::new_readonly(PROGRAM_ID, false),        // program
+        AccountMeta::new_readonly(program_data, false),      // program_data
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+    ];
+
+    send(&admin, accounts, instruction_data);
+}
+
+fn handle_show() {
+    let client = RpcClient::new(CLUSTER_URL);
+
+    let Some(config) = fetch_protocol_config(&client) else {
+        println!(
+            "{}",
+            "The protocol config does not exist yet. Run mor-cli protocol init.".yellow()
+        );
+        return;
+    };
+
+    println!(
+        "{} {}",
+        "Protocol config:".cyan(),
+        protocol_config_address()
+    );
+    println!("{} {}", "Admin:".cyan(), config.admin);
+    print_fees(
+        &config.treasury,
+        config.pool_creation_fee,
+        config.protocol_fee_bps,
+    );
+}
+
+fn handle_set(
+    admin: Option<String>,
+    treasury: Option<String>,
+    pool_creation_fee: Option<u64>,
+    protocol_fee_bps: Option<u16>,
+) {
+    let signer = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    let Some(config) = fetch_protocol_config(&client) else {
+        println!(
+            "{}",
+            "The protocol config does not exist yet. Run mor-cli protocol init.".yellow()
+        );
+        return;
+    };
+
+    // Anything left out keeps its current value
+    let admin = match admin.as_deref().map(str::parse::<Pubkey>).transpose() {
+        Ok(admin) => admin.unwrap_or(config.admin),
+        Err(e) => {
+            println!("{} {}", "Invalid admin address:".red(), e);
+            return;
+        }
+    };
+    let treasury = match treasury.as_deref().map(str::parse::<Pubkey>).transpose() {
+        Ok(treasury) => treasury.unwrap_or(config.treasury),
+        Err(e) => {
+            println!("{} {}", "Invalid treasury address:".red(), e);
+            return;
+        }
+    };
+    let pool_creation_fee = pool_creation_fee.unwrap_or(config.pool_creation_fee);
+    let protocol_fee_bps = protocol_fee_bps.unwrap_or(config.protocol_fee_bps);
+
+    if admin != config.admin {
+        println!(
+            "{} {}",
+            "Handing the protocol admin role to:".yellow(),
+            admin
+        );
+    }
+    print_fees(&treasury, pool_creation_fee, protocol_fee_bps);
+
+    let instruction_data = instruction::SetProtocolConfig {
+        admin,
+        treasury,
+        pool_creation_fee,
+        protocol_fee_bps,
+    }
+    .data();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(signer.pubkey(), true), // admin
+        AccountMeta::new(protocol_config_address(), false), // protocol_config
+    ];
+
+    send(&signer, accounts, instruction_data);
+}
+
+fn print_fees(treasury: &Pubkey, pool_creation_fee: u64, protocol_fee_bps: u16) {
+    println!("{} {}", "Treasury:".cyan(), treasury);
+    println!(
+        "{} {}",
+        "Pool creation fee:".cyan(),
+        format_sol(pool_creation_fee)
+    );
+    println!(
+        "{} {}",
+        "Protocol fee on claims:".cyan(),
+        format_bps(protocol_fee_bps)
+    );
+}
+
+/// Signs and sends a single instruction on the base layer
+fn send(signer: &Keypair, accounts: Vec<AccountMeta>, data: Vec<u8>) {
+    let client = RpcClient::new(CLUSTER_URL);
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data,
+    };
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx =
+        Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => println!(
+            "{} {}{}{}",
+            "Transaction sent successfully:".green(),
+            "https://explorer.solana.com/tx/".to_string().cyan(),
+            sig.to_string().cyan(),
+            "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                .to_string()
+                .cyan()
+        ),
+        Err(e) => print_transaction_error(&e),
+    }
+}
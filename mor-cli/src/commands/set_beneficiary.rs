@@ -0,0 +1,141 @@
+// Sets the wallet every claim of your miner pays
+
+use anchor_lang::InstructionData;
+use clap::Parser;
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::instruction;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{
+    CLUSTER_URL, MINER_SEED, MINING_POOL_SEED, PROGRAM_ID, load_payer_keypair,
+};
+
+#[derive(Parser, Debug)]
+pub struct SetBeneficiaryArgs {
+    /// Token mint address
+    #[arg(long)]
+    pub token_mint: String,
+
+    /// Wallet to pay
+    #[arg(required_unless_present = "clear")]
+    pub beneficiary: Option<String>,
+
+    /// Remove the beneficiary, so each claim chooses where to pay
+    #[arg(long, conflicts_with = "beneficiary")]
+    pub clear: bool,
+}
+
+pub fn handle_set_beneficiary(args: &SetBeneficiaryArgs) {
+    let authority = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    // Parse the token mint address
+    let mint_pubkey = match args.token_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid token mint address:".red(), e);
+            return;
+        }
+    };
+
+    let beneficiary = if args.clear {
+        Pubkey::default()
+    } else {
+        match args
+            .beneficiary
+            .as_deref()
+            .unwrap_or_default()
+            .parse::<Pubkey>()
+        {
+            Ok(pubkey) => pubkey,
+            Err(e) => {
+                println!("{} {}", "Invalid beneficiary address:".red(), e);
+                return;
+            }
+        }
+    };
+
+    // Derive mining pool PDA - assuming the authority is also the pool maker
+    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+        &[
+            MINING_POOL_SEED,
+            &authority.pubkey().to_bytes(),
+            &mint_pubkey.to_bytes(),
+        ],
+        &PROGRAM_ID,
+    );
+
+    // Derive miner PDA
+    let (miner_pda, _bump) = Pubkey::find_program_address(
+        &[
+            MINER_SEED,
+            &authority.pubkey().to_bytes(),
+            &mining_pool_pda.to_bytes(),
+        ],
+        &PROGRAM_ID,
+    );
+
+    let instruction_data = instruction::SetMinerBeneficiary { beneficiary }.data();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(authority.pubkey(), true), // authority
+        AccountMeta::new(miner_pda, false),                  // miner
+        AccountMeta::new_readonly(mining_pool_pda, false),   // mining_pool_pda
+    ];
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        blockhash,
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => {
+            if beneficiary == Pubkey::default() {
+                println!("{}", "Beneficiary removed".green());
+            } else {
+                println!("{} {}", "Beneficiary set to:".green(), beneficiary);
+            }
+            println!(
+                "{} {}{}{}",
+                "Transaction sent successfully:".green(),
+                "https://explorer.solana.com/tx/".to_string().cyan(),
+                sig.to_string().cyan(),
+                "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                    .to_string()
+                    .cyan()
+            )
+        }
+        Err(e) => print_transaction_error(&e),
+    }
+}
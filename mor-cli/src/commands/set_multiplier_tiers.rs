@@ -0,0 +1,142 @@
+// Replaces the staking multiplier table of a pool you created
+
+use anchor_lang::InstructionData;
+use clap::Parser;
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::helpers::constants::{DEFAULT_MULTIPLIER_TIERS, MULTIPLIER_BASE_BPS};
+use mor_core::instruction;
+use mor_core::states::MultiplierTier;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{
+    CLUSTER_URL, MINING_POOL_SEED, PROGRAM_ID, format_multiplier, load_payer_keypair,
+    parse_multiplier_tier, parse_pool_maker,
+};
+
+#[derive(Parser, Debug)]
+pub struct SetMultiplierTiersArgs {
+    /// Token mint address
+    #[arg(long)]
+    pub token_mint: String,
+
+    /// Maker of a pool handed over to you (defaults to your keypair)
+    #[arg(long, value_name = "PUBKEY")]
+    pub pool_maker: Option<String>,
+
+    /// Tier as MIN_TOKENS:BONUS_BPS:BPS_PER_TOKEN, e.g. 50:250000:3333
+    /// (repeat in increasing order of MIN_TOKENS; 10000 bps is 1x)
+    #[arg(long = "tier", value_name = "TIER", value_parser = parse_multiplier_tier)]
+    pub tiers: Vec<MultiplierTier>,
+
+    /// Restore the default tiers new pools start with
+    #[arg(long, conflicts_with = "tiers")]
+    pub defaults: bool,
+}
+
+pub fn handle_set_multiplier_tiers(args: &SetMultiplierTiersArgs) {
+    let admin = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    // Parse the token mint address
+    let mint_pubkey = match args.token_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid token mint address:".red(), e);
+            return;
+        }
+    };
+
+    let tiers = if args.defaults {
+        DEFAULT_MULTIPLIER_TIERS.to_vec()
+    } else {
+        args.tiers.clone()
+    };
+
+    // The pool stays derived from its maker after it is handed over
+    let pool_maker = match parse_pool_maker(args.pool_maker.as_deref(), &admin.pubkey()) {
+        Ok(pool_maker) => pool_maker,
+        Err(e) => {
+            println!("{}", e.red());
+            return;
+        }
+    };
+
+    // Derive mining pool PDA
+    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+        &[MINING_POOL_SEED, pool_maker.as_ref(), mint_pubkey.as_ref()],
+        &PROGRAM_ID,
+    );
+
+    println!("{}", "Multiplier tiers:".green().bold());
+    if tiers.is_empty() {
+        println!(
+            "Every miner gets {}",
+            format_multiplier(MULTIPLIER_BASE_BPS)
+        );
+    }
+    for tier in &tiers {
+        println!(
+            "{} {} tokens: {} +{} {} +{}",
+            "From".cyan(),
+            tier.min_tokens,
+            "bonus".cyan(),
+            format_multiplier(tier.bonus_bps),
+            "per extra token".cyan(),
+            format_multiplier(tier.bps_per_token)
+        );
+    }
+
+    let instruction_data = instruction::SetMultiplierTiers {
+        multiplier_tiers: tiers,
+    }
+    .data();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(admin.pubkey(), true), // admin
+        AccountMeta::new(mining_pool_pda, false),        // mining_pool_pda
+    ];
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin.pubkey()), &[&admin], blockhash);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => println!(
+            "{} {}{}{}",
+            "Transaction sent successfully:".green(),
+            "https://explorer.solana.com/tx/".to_string().cyan(),
+            sig.to_string().cyan(),
+            "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                .to_string()
+                .cyan()
+        ),
+        Err(e) => print_transaction_error(&e),
+    }
+}
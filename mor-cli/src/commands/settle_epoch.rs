@@ -0,0 +1,359 @@
+// Cranks settle_epoch, folding the reward shards' credits into a pool's reward state
+// and their epoch stats into the pool's epoch ledgers, which it creates ahead and
+// closes once they are old enough
+
+use anchor_lang::{AnchorDeserialize, InstructionData};
+use clap::Parser;
+use colored::*;
+use mor_core::helpers::constants::{MAX_EPOCH_OFFSET, REWARD_SHARDS};
+use mor_core::helpers::errors::MorErrorCodes;
+use mor_core::instruction;
+use mor_core::states::{EpochLedger, MiningPoolPda, MiningPoolRewardState, RewardShard};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::str::FromStr;
+use std::thread;
+
+use crate::commands::epochs::{
+    close_epoch_ledger_instruction, delegate_epoch_ledger_instruction, epoch_ledger_address,
+    initialize_epoch_ledger_instruction, undelegate_epoch_ledger_instruction,
+};
+use crate::commands::mine::fetch_account;
+use crate::utils::errors::{ProgramError, decode_client_error, print_transaction_error};
+use crate::utils::helpers::{
+    CLUSTER_URL, DELEGATION_PROGRAM_ID, EPOCH_SLOT_LENGTH, ER_CLUSTER_URL, MINING_POOL_REWARD_SEED,
+    PROGRAM_ID, REWARD_SHARD_SEED, SLOT_DURATION, load_payer_keypair,
+};
+use crate::utils::transaction::{SendConfig, send_with_retries};
+
+#[derive(Parser, Debug)]
+pub struct SettleEpochArgs {
+    /// Mining pool PDA to settle
+    #[arg(long)]
+    pub pool: String,
+
+    /// Settle on the Solana base layer instead of the ER, for a pool that isn't delegated
+    #[arg(long)]
+    pub base_layer: bool,
+
+    /// Settle the current epoch once and exit, instead of every epoch
+    #[arg(long)]
+    pub once: bool,
+
+    /// Epochs to keep the ledgers of after they stop taking solutions, before
+    /// closing them to get their rent back
+    #[arg(long, default_value_t = 100)]
+    pub keep_ledgers: u64,
+}
+
+/// Address of one of a pool's reward shards
+pub fn reward_shard_address(mining_pool_pda: &Pubkey, index: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[REWARD_SHARD_SEED, mining_pool_pda.as_ref(), &[index]],
+        &PROGRAM_ID,
+    )
+    .0
+}
+
+/// Addresses of all a pool's reward shards, in index order
+pub fn reward_shard_addresses(mining_pool_pda: &Pubkey) -> Vec<Pubkey> {
+    (0..REWARD_SHARDS)
+        .map(|index| reward_shard_address(mining_pool_pda, index))
+        .collect()
+}
+
+/// settle_epoch for a pool, with every reward shard passed in index order, then the
+/// ledgers of `ledger_epochs` the shards' stats are folded into
+pub fn settle_epoch_instruction(
+    mining_pool_pda: Pubkey,
+    mining_pool_reward_state: Pubkey,
+    ledger_epochs: &[u64],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(mining_pool_pda, false), // mining_pool_pda
+        AccountMeta::new(mining_pool_reward_state, false), // mining_pool_reward_state
+    ];
+    accounts.extend(
+        reward_shard_addresses(&mining_pool_pda)
+            .into_iter()
+            .map(|shard| AccountMeta::new(shard, false)),
+    );
+    accounts.extend(
+        ledger_epochs
+            .iter()
+            .map(|epoch| AccountMeta::new(epoch_ledger_address(&mining_pool_pda, *epoch), false)),
+    );
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction::SettleEpoch {}.data(),
+    }
+}
+
+/// Epochs the pool's reward shards counted solutions of that weren't folded into
+/// their ledgers yet, oldest first
+fn unsettled_epochs(
+    client: &RpcClient,
+    mining_pool_pda: &Pubkey,
+) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
+    let mut epochs: Vec<u64> = client
+        .get_multiple_accounts(&reward_shard_addresses(mining_pool_pda))?
+        .into_iter()
+        .flatten()
+        .filter_map(|account| RewardShard::deserialize(&mut &account.data[1..]).ok())
+        .flat_map(|shard| shard.epoch_stats)
+        .filter(|stats| stats.submissions > 0)
+        .map(|stats| stats.epoch)
+        .collect();
+    epochs.sort_unstable();
+    epochs.dedup();
+    Ok(epochs)
+}
+
+/// Whether settle_epoch can write the ledger where it runs: the ER writes
+/// delegated ledgers, the base layer the ones the program still owns
+fn ledger_writable(
+    base_client: &RpcClient,
+    ledger: &Pubkey,
+    base_layer: bool,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let owner = if base_layer {
+        PROGRAM_ID
+    } else {
+        DELEGATION_PROGRAM_ID
+    };
+    Ok(base_client
+        .get_account_with_commitment(ledger, base_client.commitment())?
+        .value
+        .is_some_and(|account| account.owner == owner))
+}
+
+/// Creates the pool's ledgers of the epochs solutions are accepted for, so
+/// settle_epoch has them to fold the shards' stats into. Off the base layer they
+/// are delegated too, since the ER can't create them.
+fn prepare_epoch_ledgers(
+    base_client: &RpcClient,
+    payer: &Keypair,
+    mining_pool_pda: Pubkey,
+    mining_pool_reward_state: Pubkey,
+    epoch: u64,
+    base_layer: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let base_config = SendConfig {
+        base_layer: true,
+        ..SendConfig::default()
+    };
+    for ledger_epoch in epoch.saturating_sub(MAX_EPOCH_OFFSET)..=epoch + MAX_EPOCH_OFFSET {
+        let ledger = epoch_ledger_address(&mining_pool_pda, ledger_epoch);
+        // Delegated ledgers stay on the base layer, owned by the delegation program
+        if base_client
+            .get_account_with_commitment(&ledger, base_client.commitment())?
+            .value
+            .is_some()
+        {
+            continue;
+        }
+        let mut instructions = vec![initialize_epoch_ledger_instruction(
+            payer.pubkey(),
+            mining_pool_pda,
+            ledger_epoch,
+        )];
+        if !base_layer {
+            instructions.push(delegate_epoch_ledger_instruction(
+                payer.pubkey(),
+                mining_pool_pda,
+                mining_pool_reward_state,
+                ledger_epoch,
+            ));
+        }
+        match send_with_retries(base_client, &instructions, payer, &base_config) {
+            Ok(_) => println!("{} {}", "Prepared ledger of epoch:".green(), ledger_epoch),
+            Err(e) => print_transaction_error(&e),
+        }
+    }
+    Ok(())
+}
+
+/// Returns the ledgers of epochs that no longer take solutions to the base layer,
+/// once settle_epoch folded their stats in
+fn return_epoch_ledgers(
+    base_client: &RpcClient,
+    er_client: &RpcClient,
+    payer: &Keypair,
+    mining_pool_pda: Pubkey,
+    epoch: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Solutions are accepted up to MAX_EPOCH_OFFSET epochs late
+    let Some(last_closed) = epoch.checked_sub(MAX_EPOCH_OFFSET + 1) else {
+        return Ok(());
+    };
+    let unsettled = unsettled_epochs(er_client, &mining_pool_pda)?;
+    for ledger_epoch in last_closed.saturating_sub(MAX_EPOCH_OFFSET)..=last_closed {
+        let ledger = epoch_ledger_address(&mining_pool_pda, ledger_epoch);
+        if unsettled.contains(&ledger_epoch) || !ledger_writable(base_client, &ledger, false)? {
+            continue;
+        }
+        let instruction =
+            undelegate_epoch_ledger_instruction(payer.pubkey(), mining_pool_pda, ledger_epoch);
+        match send_with_retries(er_client, &[instruction], payer, &SendConfig::default()) {
+            Ok(_) => println!("{} {}", "Returned ledger of epoch:".green(), ledger_epoch),
+            Err(e) => print_transaction_error(&e),
+        }
+    }
+    Ok(())
+}
+
+/// Closes the base layer ledgers of epochs that stopped taking solutions more than
+/// `keep_ledgers` epochs ago, once settle_epoch folded their last stats in. Their
+/// rent goes back to whoever paid it.
+fn close_epoch_ledgers(
+    base_client: &RpcClient,
+    payer: &Keypair,
+    mining_pool_pda: Pubkey,
+    epoch: u64,
+    keep_ledgers: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(last_expired) = epoch.checked_sub(MAX_EPOCH_OFFSET + 1 + keep_ledgers) else {
+        return Ok(());
+    };
+    let base_config = SendConfig {
+        base_layer: true,
+        ..SendConfig::default()
+    };
+    for ledger_epoch in last_expired.saturating_sub(MAX_EPOCH_OFFSET)..=last_expired {
+        let ledger = epoch_ledger_address(&mining_pool_pda, ledger_epoch);
+        // Still delegated ledgers are owned by the delegation program
+        let Some(ledger) = base_client
+            .get_account_with_commitment(&ledger, base_client.commitment())?
+            .value
+            .filter(|account| account.owner == PROGRAM_ID)
+            .and_then(|account| EpochLedger::deserialize(&mut &account.data[1..]).ok())
+        else {
+            continue;
+        };
+        if ledger.settled_epoch <= ledger_epoch + MAX_EPOCH_OFFSET {
+            continue;
+        }
+        let instruction =
+            close_epoch_ledger_instruction(ledger.payer, mining_pool_pda, ledger_epoch);
+        match send_with_retries(base_client, &[instruction], payer, &base_config) {
+            Ok(_) => println!("{} {}", "Closed ledger of epoch:".green(), ledger_epoch),
+            Err(e) => print_transaction_error(&e),
+        }
+    }
+    Ok(())
+}
+
+pub fn handle_settle_epoch(args: &SettleEpochArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let payer = load_payer_keypair().map_err(|e| format!("Failed to load keypair: {}", e))?;
+    let mining_pool_pda = Pubkey::from_str(&args.pool)
+        .map_err(|e| format!("Invalid pool address {}: {}", args.pool, e))?;
+
+    // The reward state and its shards live wherever the pool maker delegated them
+    let client = RpcClient::new(if args.base_layer {
+        CLUSTER_URL
+    } else {
+        ER_CLUSTER_URL
+    });
+    let send_config = SendConfig {
+        base_layer: args.base_layer,
+        ..SendConfig::default()
+    };
+
+    let base_client = RpcClient::new(CLUSTER_URL);
+
+    let pool: MiningPoolPda =
+        fetch_account(&client, &base_client, &mining_pool_pda, "mining pool")?;
+    let (mining_pool_reward_state, _) = Pubkey::find_program_address(
+        &[
+            MINING_POOL_REWARD_SEED,
+            pool.pool_maker.as_ref(),
+            mining_pool_pda.as_ref(),
+        ],
+        &PROGRAM_ID,
+    );
+
+    let mut settled_epoch = client
+        .get_account_data(&mining_pool_reward_state)
+        .ok()
+        .and_then(|data| MiningPoolRewardState::deserialize(&mut &data[1..]).ok())
+        .map(|reward_state| reward_state.settled_epoch)
+        .unwrap_or_default();
+
+    loop {
+        let slot = client.get_slot()?;
+        let epoch = slot / EPOCH_SLOT_LENGTH;
+        if let Err(e) = prepare_epoch_ledgers(
+            &base_client,
+            &payer,
+            mining_pool_pda,
+            mining_pool_reward_state,
+            epoch,
+            args.base_layer,
+        ) {
+            println!("{} {}", "Failed to prepare epoch ledgers:".red(), e);
+        }
+        if epoch > settled_epoch {
+            // Stats of an epoch whose ledger isn't ready stay on the shards until it is
+            let mut ledger_epochs = unsettled_epochs(&client, &mining_pool_pda)?;
+            ledger_epochs.retain(|ledger_epoch| {
+                let ledger = epoch_ledger_address(&mining_pool_pda, *ledger_epoch);
+                ledger_writable(&base_client, &ledger, args.base_layer).unwrap_or(false)
+            });
+            let ix =
+                settle_epoch_instruction(mining_pool_pda, mining_pool_reward_state, &ledger_epochs);
+            match send_with_retries(&client, &[ix], &payer, &send_config) {
+                Ok(signature) => {
+                    println!(
+                        "{} {} {}",
+                        format!("Settled epoch {}:", epoch).green(),
+                        "signature".cyan(),
+                        signature
+                    );
+                    settled_epoch = epoch;
+                }
+                // Anyone can crank, so someone else may have settled it first
+                Err(e)
+                    if matches!(
+                        decode_client_error(&e),
+                        Some(ProgramError::Mor(MorErrorCodes::AlreadySettled))
+                    ) =>
+                {
+                    println!("{} {}", "Epoch already settled:".yellow(), epoch);
+                    settled_epoch = epoch;
+                }
+                Err(e) => {
+                    print_transaction_error(&e);
+                    if args.once {
+                        return Err(Box::new(e));
+                    }
+                }
+            }
+        }
+        if !args.base_layer
+            && let Err(e) =
+                return_epoch_ledgers(&base_client, &client, &payer, mining_pool_pda, epoch)
+        {
+            println!("{} {}", "Failed to return epoch ledgers:".red(), e);
+        }
+        if let Err(e) = close_epoch_ledgers(
+            &base_client,
+            &payer,
+            mining_pool_pda,
+            epoch,
+            args.keep_ledgers,
+        ) {
+            println!("{} {}", "Failed to close epoch ledgers:".red(), e);
+        }
+        if args.once {
+            return Ok(());
+        }
+
+        // Sleep until the next epoch starts
+        let slots_left = (epoch + 1) * EPOCH_SLOT_LENGTH - slot;
+        thread::sleep(SLOT_DURATION * slots_left as u32);
+    }
+}
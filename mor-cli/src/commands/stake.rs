@@ -0,0 +1,116 @@
+// Locks tokens in a pool to raise your miner's multiplier
+
+use anchor_lang::InstructionData;
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::instruction;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{
+    CLUSTER_URL, MINER_SEED, MINING_POOL_SEED, PROGRAM_ID, STAKE_VAULT_SEED, load_payer_keypair,
+};
+
+pub fn handle_stake(token_mint: String, amount: u64) {
+    let authority = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    // Parse the token mint address
+    let mint_pubkey = match token_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid token mint address:".red(), e);
+            return;
+        }
+    };
+
+    // Derive mining pool PDA - assuming the authority is also the pool maker
+    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+        &[
+            MINING_POOL_SEED,
+            &authority.pubkey().to_bytes(),
+            &mint_pubkey.to_bytes(),
+        ],
+        &PROGRAM_ID,
+    );
+
+    // Derive miner PDA
+    let (miner_pda, _bump) = Pubkey::find_program_address(
+        &[
+            MINER_SEED,
+            &authority.pubkey().to_bytes(),
+            &mining_pool_pda.to_bytes(),
+        ],
+        &PROGRAM_ID,
+    );
+
+    // Derive the pool's stake vault
+    let (stake_vault, _bump) = Pubkey::find_program_address(
+        &[STAKE_VAULT_SEED, &mining_pool_pda.to_bytes()],
+        &PROGRAM_ID,
+    );
+
+    let authority_ata = get_associated_token_address(&authority.pubkey(), &mint_pubkey);
+
+    let instruction_data = instruction::Stake { amount }.data();
+
+    let accounts = vec![
+        AccountMeta::new(authority.pubkey(), true), // authority
+        AccountMeta::new(miner_pda, false),         // miner
+        AccountMeta::new_readonly(mining_pool_pda, false), // mining_pool_pda
+        AccountMeta::new_readonly(mint_pubkey, false), // mint
+        AccountMeta::new(authority_ata, false),     // authority_ata
+        AccountMeta::new(stake_vault, false),       // stake_vault
+        AccountMeta::new_readonly(spl_token::ID, false), // token_program
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+    ];
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data,
+    };
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        blockhash,
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => println!(
+            "{} {}{}{}",
+            "Transaction sent successfully:".green(),
+            "https://explorer.solana.com/tx/".to_string().cyan(),
+            sig.to_string().cyan(),
+            "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                .to_string()
+                .cyan()
+        ),
+        Err(e) => print_transaction_error(&e),
+    }
+}
@@ -7,6 +7,7 @@ use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::{signature::Signer, transaction::Transaction};
 
+use crate::utils::errors::print_transaction_error;
 use crate::utils::helpers::{
     ER_CLUSTER_URL, MAGIC_CONTEXT_PROGRAM_ID, MAGIC_PROGRAM_ID, MINER_SEED, MINING_POOL_SEED,
     PROGRAM_ID, load_payer_keypair,
@@ -101,6 +102,6 @@ pub fn handle_undelegate_miner(token_mint: String) {
                 .to_string()
                 .cyan()
         ),
-        Err(e) => println!("{} {}", "Transaction failed:".red(), e),
+        Err(e) => print_transaction_error(&e),
     }
 }
@@ -0,0 +1,300 @@
+// Shows and releases a miner's vesting escrow, and sets a pool's vesting schedule
+
+use anchor_lang::{AnchorDeserialize, InstructionData};
+use colored::*;
+use indicatif::ProgressBar;
+use mor_core::helpers::utils::linear_vested;
+use mor_core::instruction;
+use mor_core::states::VestingConfig;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::commands::get_account::MinerAccountPoolPda;
+use crate::utils::errors::print_transaction_error;
+use crate::utils::helpers::{
+    CLUSTER_URL, MINER_SEED, MINING_POOL_SEED, PROGRAM_ID, VESTING_SEED, load_payer_keypair,
+    parse_pool_maker, unix_timestamp,
+};
+
+const DAY: i64 = 86_400;
+
+#[derive(Debug, AnchorDeserialize)]
+pub struct VestingEscrow {
+    _miner: Pubkey,
+    _mint: Pubkey,
+    deposited: u64,
+    released: u64,
+    start_timestamp: i64,
+    cliff_timestamp: i64,
+    duration_seconds: i64,
+    _bump: u8,
+}
+
+impl VestingEscrow {
+    /// Tokens vested by `now`, released ones included
+    fn vested(&self, now: i64) -> u64 {
+        if now < self.cliff_timestamp {
+            return 0;
+        }
+        linear_vested(
+            self.deposited,
+            self.start_timestamp,
+            self.duration_seconds,
+            now,
+        )
+    }
+}
+
+pub fn handle_vesting(token_mint: String, release: bool, to: Option<String>) {
+    let authority = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    // Parse the token mint address
+    let mint_pubkey = match token_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid token mint address:".red(), e);
+            return;
+        }
+    };
+
+    // Derive mining pool PDA - assuming the authority is also the pool maker
+    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+        &[
+            MINING_POOL_SEED,
+            authority.pubkey().as_ref(),
+            mint_pubkey.as_ref(),
+        ],
+        &PROGRAM_ID,
+    );
+
+    // Derive miner PDA and its vesting escrow
+    let (miner_pda, _bump) = Pubkey::find_program_address(
+        &[
+            MINER_SEED,
+            authority.pubkey().as_ref(),
+            mining_pool_pda.as_ref(),
+        ],
+        &PROGRAM_ID,
+    );
+    let (vesting_escrow, _bump) =
+        Pubkey::find_program_address(&[VESTING_SEED, miner_pda.as_ref()], &PROGRAM_ID);
+
+    let escrow = match client
+        .get_account_data(&vesting_escrow)
+        .ok()
+        .and_then(|data| VestingEscrow::deserialize(&mut &data[1..]).ok())
+    {
+        Some(escrow) => escrow,
+        None => {
+            println!(
+                "{}",
+                "No vesting escrow yet. Claims of vesting pools create it.".yellow()
+            );
+            return;
+        }
+    };
+
+    let now = unix_timestamp();
+    let vested = escrow.vested(now);
+    println!("{}", "Vesting Escrow:".green().bold());
+    println!("{} {}", "Escrow PDA:".cyan(), vesting_escrow);
+    println!(
+        "{} {}",
+        "Locked:".cyan(),
+        escrow.deposited.saturating_sub(vested)
+    );
+    println!(
+        "{} {}",
+        "Releasable:".cyan(),
+        vested.saturating_sub(escrow.released)
+    );
+    println!("{} {}", "Released:".cyan(), escrow.released);
+    if now < escrow.cliff_timestamp {
+        println!(
+            "{} in {:.1} days",
+            "Cliff:".cyan(),
+            (escrow.cliff_timestamp - now) as f64 / DAY as f64
+        );
+    }
+    let fully_vested_at = escrow.start_timestamp + escrow.duration_seconds;
+    if now < fully_vested_at.max(escrow.cliff_timestamp) {
+        println!(
+            "{} in {:.1} days",
+            "Fully vested:".cyan(),
+            (fully_vested_at.max(escrow.cliff_timestamp) - now) as f64 / DAY as f64
+        );
+    }
+
+    if release {
+        release_vested(
+            &client,
+            &authority,
+            to,
+            mint_pubkey,
+            mining_pool_pda,
+            miner_pda,
+            vesting_escrow,
+        );
+    }
+}
+
+fn release_vested(
+    client: &RpcClient,
+    authority: &Keypair,
+    to: Option<String>,
+    mint_pubkey: Pubkey,
+    mining_pool_pda: Pubkey,
+    miner_pda: Pubkey,
+    vesting_escrow: Pubkey,
+) {
+    // Pay --to, else the miner's beneficiary, else the authority
+    let beneficiary = match to {
+        Some(to) => match to.parse::<Pubkey>() {
+            Ok(pubkey) => pubkey,
+            Err(e) => {
+                println!("{} {}", "Invalid beneficiary address:".red(), e);
+                return;
+            }
+        },
+        None => client
+            .get_account_data(&miner_pda)
+            .ok()
+            .and_then(|data| MinerAccountPoolPda::deserialize(&mut &data[1..]).ok())
+            .map(|miner| miner.beneficiary)
+            .filter(|beneficiary| *beneficiary != Pubkey::default())
+            .unwrap_or(authority.pubkey()),
+    };
+
+    let escrow_vault = get_associated_token_address(&vesting_escrow, &mint_pubkey);
+    let recipient_ata = get_associated_token_address(&beneficiary, &mint_pubkey);
+
+    let instruction_data = instruction::ReleaseVested {}.data();
+
+    let accounts = vec![
+        AccountMeta::new(authority.pubkey(), true),  // authority
+        AccountMeta::new_readonly(miner_pda, false), // miner
+        AccountMeta::new_readonly(mining_pool_pda, false), // mining_pool_pda
+        AccountMeta::new(vesting_escrow, false),     // vesting_escrow
+        AccountMeta::new(escrow_vault, false),       // escrow_vault
+        AccountMeta::new_readonly(mint_pubkey, false), // mint
+        AccountMeta::new_readonly(beneficiary, false), // beneficiary
+        AccountMeta::new(recipient_ata, false),      // recipient_ata
+        AccountMeta::new_readonly(spl_associated_token_account::ID, false), // associated_token_program
+        AccountMeta::new_readonly(spl_token::ID, false),                    // token_program
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),                // system_program
+    ];
+
+    send(client, authority, accounts, instruction_data);
+}
+
+pub fn handle_set_vesting(
+    token_mint: String,
+    pool_maker: Option<String>,
+    cliff_days: u64,
+    days: u64,
+) {
+    let admin = load_payer_keypair().unwrap();
+    let client = RpcClient::new(CLUSTER_URL);
+
+    // Parse the token mint address
+    let mint_pubkey = match token_mint.parse::<Pubkey>() {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            println!("{} {}", "Invalid token mint address:".red(), e);
+            return;
+        }
+    };
+
+    // The pool stays derived from its maker after it is handed over
+    let pool_maker = match parse_pool_maker(pool_maker.as_deref(), &admin.pubkey()) {
+        Ok(pool_maker) => pool_maker,
+        Err(e) => {
+            println!("{}", e.red());
+            return;
+        }
+    };
+
+    // Derive mining pool PDA
+    let (mining_pool_pda, _bump) = Pubkey::find_program_address(
+        &[MINING_POOL_SEED, pool_maker.as_ref(), mint_pubkey.as_ref()],
+        &PROGRAM_ID,
+    );
+
+    // No duration turns vesting off
+    let vesting = (days > 0).then(|| VestingConfig {
+        cliff_seconds: cliff_days as i64 * DAY,
+        duration_seconds: days as i64 * DAY,
+    });
+    match vesting {
+        Some(_) => {
+            println!(
+                "{} {} day cliff, vesting over {} days",
+                "Vesting:".cyan(),
+                cliff_days,
+                days
+            );
+            println!(
+                "{}",
+                "Only the pool's own mint vests. Bonus mints are still paid out on claim.".yellow()
+            );
+        }
+        None => println!("{} {}", "Vesting:".cyan(), "off".yellow()),
+    }
+
+    let instruction_data = instruction::SetVesting { vesting }.data();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(admin.pubkey(), true), // admin
+        AccountMeta::new(mining_pool_pda, false),        // mining_pool_pda
+    ];
+
+    send(&client, &admin, accounts, instruction_data);
+}
+
+/// Signs and sends a single instruction on the base layer
+fn send(client: &RpcClient, payer: &Keypair, accounts: Vec<AccountMeta>, data: Vec<u8>) {
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data,
+    };
+
+    // Get recent blockhash
+    let blockhash = match client.get_latest_blockhash() {
+        Ok(bh) => bh,
+        Err(e) => {
+            println!("{} {}", "Failed to get recent blockhash:".red(), e);
+            return;
+        }
+    };
+
+    // Build transaction
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Sending and confirming transaction...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let result = client.send_and_confirm_transaction(&tx);
+
+    pb.finish_and_clear();
+
+    match result {
+        Ok(sig) => println!(
+            "{} {}{}{}",
+            "Transaction sent successfully:".green(),
+            "https://explorer.solana.com/tx/".to_string().cyan(),
+            sig.to_string().cyan(),
+            "?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+                .to_string()
+                .cyan()
+        ),
+        Err(e) => print_transaction_error(&e),
+    }
+}
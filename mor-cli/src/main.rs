@@ -1,4 +1,6 @@
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, Parser, Subcommand};
+use mor_core::states::{PoolMode, PowAlgorithm};
+use std::path::PathBuf;
 mod commands;
 mod utils;
 
@@ -16,18 +18,121 @@ pub enum Commands {
     SetupKeypair(commands::setup_keypair::SetupKeypairArgs),
     /// Initialize a mining pool with tokens
     InitializePool {
-        /// Amount of tokens to deposit into the pool (in base units)
+        /// Amount of tokens to deposit into the pool, or the most it may mint (in base units)
         amount: u64,
         /// Token mint address
         #[arg(long)]
         token_mint: String,
+        /// Proof-of-work miners solve: sha3, or equix (memory-hard, ASIC resistant)
+        #[arg(long, default_value = "sha3", value_parser = utils::helpers::parse_pow_algorithm)]
+        pow_algorithm: PowAlgorithm,
+        /// Your share of each mined reward, in basis points (at most 2000)
+        #[arg(long, default_value_t = 0)]
+        commission_bps: u16,
+        /// Where rewards come from: vault (deposited now), or mint (minted on claim, you must
+        /// be the mint authority)
+        #[arg(long, default_value = "vault", value_parser = utils::helpers::parse_pool_mode)]
+        mode: PoolMode,
     },
+    /// Pay a second token alongside a pool you created, or top up and retune one
+    AddBonusMint {
+        /// Amount of bonus tokens to deposit (in base units)
+        amount: u64,
+        /// Token mint address of the pool
+        #[arg(long)]
+        token_mint: String,
+        /// Maker of a pool handed over to you (defaults to your keypair)
+        #[arg(long, value_name = "PUBKEY")]
+        pool_maker: Option<String>,
+        /// Mint address of the bonus token
+        #[arg(long)]
+        bonus_mint: String,
+        /// Bonus base units credited per base unit of the pool's token, in basis points
+        #[arg(long)]
+        rate_bps: u32,
+        /// Days from now until solutions stop earning the bonus
+        #[arg(long, default_value_t = 30)]
+        days: u64,
+    },
+    /// Change your share of the rewards mined from a pool you created
+    SetCommission {
+        /// Share of each mined reward, in basis points (at most 2000)
+        commission_bps: u16,
+        /// Token mint address
+        #[arg(long)]
+        token_mint: String,
+        /// Maker of a pool handed over to you (defaults to your keypair)
+        #[arg(long, value_name = "PUBKEY")]
+        pool_maker: Option<String>,
+    },
+    /// Require miners of a pool you created to stake before they can mine it
+    SetMinStake {
+        /// Staked tokens a miner needs to submit solutions (in base units, 0 for none)
+        min_stake: u64,
+        /// Token mint address
+        #[arg(long)]
+        token_mint: String,
+        /// Maker of a pool handed over to you (defaults to your keypair)
+        #[arg(long, value_name = "PUBKEY")]
+        pool_maker: Option<String>,
+    },
+    /// Claim the commission earned by a pool you created
+    ClaimCommission {
+        /// Token mint address
+        #[arg(long)]
+        token_mint: String,
+        /// Maker of a pool handed over to you (defaults to your keypair)
+        #[arg(long, value_name = "PUBKEY")]
+        pool_maker: Option<String>,
+    },
+    /// Browse mining pools and manage their allowlists and admins
+    #[command(alias = "pool")]
+    Pools {
+        #[command(subcommand)]
+        command: commands::pools::PoolsCommand,
+    },
+    /// Set up and tune the protocol-wide treasury and fees (protocol operator only)
+    Protocol {
+        #[command(subcommand)]
+        command: commands::protocol::ProtocolCommand,
+    },
+    /// Replace the staking multiplier tiers of a pool you created
+    SetMultiplierTiers(commands::set_multiplier_tiers::SetMultiplierTiersArgs),
     /// Create your miner account (can only be run once)
+    #[command(group(ArgGroup::new("mining_pool").required(true).args(["token_mint", "pool"])))]
     CreateAccount {
+        /// Token mint address of a pool you created
+        #[arg(long)]
+        token_mint: Option<String>,
+        /// Mining pool PDA to join
+        #[arg(long)]
+        pool: Option<String>,
+        /// Proofs file from pools allowlist build, for a private pool
+        #[arg(long, value_name = "PATH")]
+        allowlist: Option<PathBuf>,
+    },
+    /// Upgrade your miner account to the current program layout
+    MigrateMiner {
         /// Token mint address
         #[arg(long)]
         token_mint: String,
     },
+    /// Upgrade a mining pool to the current program layout (anyone can run it)
+    MigratePool {
+        /// Mining pool PDA
+        #[arg(long)]
+        pool: String,
+    },
+    /// Upgrade a pool's reward state to the current program layout
+    MigrateRewardState {
+        /// Mining pool PDA
+        #[arg(long)]
+        pool: String,
+    },
+    /// Set the hot key that may submit solutions for your miner
+    SetMinerSigner(commands::set_miner_signer::SetMinerSignerArgs),
+    /// Set the wallet every claim of your miner pays
+    SetBeneficiary(commands::set_beneficiary::SetBeneficiaryArgs),
     /// Delegate your miner to the ER
     DelegateMiner {
         /// Token mint address
@@ -46,14 +151,71 @@ pub enum Commands {
         #[arg(long)]
         token_mint: String,
     },
+    /// Show who solved a pool's recent epochs and what they earned
+    Epochs {
+        /// Mining pool address
+        pool: String,
+        /// How many epochs back to look
+        #[arg(long, default_value_t = 20)]
+        count: u64,
+    },
+    /// Fold the reward shards' credits into a pool's reward state, every epoch (anyone can run it)
+    SettleEpoch(commands::settle_epoch::SettleEpochArgs),
     /// Mine for tokens
-    Mine {
+    Mine(commands::mine::MineArgs),
+    /// Measure your hash rate and the expected time to solve each difficulty
+    Bench(commands::bench::BenchArgs),
+    /// Claim rewards from your miner
+    ClaimRewards {
         /// Token mint address
         #[arg(long)]
         token_mint: String,
+        /// Wallet to pay (defaults to the miner's beneficiary, else your wallet)
+        #[arg(long, value_name = "PUBKEY")]
+        to: Option<String>,
+        /// Amount to claim in base units (defaults to all rewards)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        amount: Option<u64>,
     },
-    /// Claim rewards from your miner
-    ClaimRewards {
+    /// Show your locked and releasable vesting rewards, and release them
+    Vesting {
+        /// Token mint address
+        #[arg(long)]
+        token_mint: String,
+        /// Release everything vested so far
+        #[arg(long)]
+        release: bool,
+        /// Wallet to pay on release (defaults to the miner's beneficiary, else your wallet)
+        #[arg(long, value_name = "PUBKEY", requires = "release")]
+        to: Option<String>,
+    },
+    /// Make claims from a pool you created vest, or stop it with --days 0
+    SetVesting {
+        /// Token mint address
+        #[arg(long)]
+        token_mint: String,
+        /// Maker of a pool handed over to you (defaults to your keypair)
+        #[arg(long, value_name = "PUBKEY")]
+        pool_maker: Option<String>,
+        /// Days after the first locked rewards before any can be released
+        #[arg(long, default_value_t = 0)]
+        cliff_days: u64,
+        /// Days over which each claim vests linearly (0 turns vesting off)
+        #[arg(long)]
+        days: u64,
+    },
+    /// Stake tokens in a pool to raise your multiplier
+    Stake {
+        /// Amount of tokens to stake (in base units)
+        amount: u64,
+        /// Token mint address
+        #[arg(long)]
+        token_mint: String,
+    },
+    /// Withdraw staked tokens (forfeits the same share of the stake's loyalty)
+    Unstake {
+        /// Amount of tokens to unstake (in base units)
+        amount: u64,
         /// Token mint address
         #[arg(long)]
         token_mint: String,
@@ -68,32 +230,145 @@ async fn main() {
         Commands::SetupKeypair(args) => {
             commands::setup_keypair::handle_setup_keypair(&args);
         }
-        Commands::InitializePool { amount, token_mint } => {
-            if let Err(e) = commands::initialize::handle_initialize_pool(amount, token_mint) {
+        Commands::InitializePool {
+            amount,
+            token_mint,
+            pow_algorithm,
+            commission_bps,
+            mode,
+        } => {
+            if let Err(e) = commands::initialize::handle_initialize_pool(
+                amount,
+                token_mint,
+                pow_algorithm,
+                commission_bps,
+                mode,
+            ) {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
         }
-        Commands::CreateAccount { token_mint } => {
-            commands::initialize_miner::handle_initialize_miner(token_mint);
+        Commands::AddBonusMint {
+            amount,
+            token_mint,
+            pool_maker,
+            bonus_mint,
+            rate_bps,
+            days,
+        } => {
+            commands::bonus::handle_add_bonus_mint(
+                token_mint, pool_maker, bonus_mint, amount, rate_bps, days,
+            );
+        }
+        Commands::SetCommission {
+            commission_bps,
+            token_mint,
+            pool_maker,
+        } => {
+            commands::commission::handle_set_commission(token_mint, pool_maker, commission_bps);
+        }
+        Commands::SetMinStake {
+            min_stake,
+            token_mint,
+            pool_maker,
+        } => {
+            commands::min_stake::handle_set_min_stake(token_mint, pool_maker, min_stake);
+        }
+        Commands::ClaimCommission {
+            token_mint,
+            pool_maker,
+        } => {
+            commands::commission::handle_claim_commission(token_mint, pool_maker);
+        }
+        Commands::Pools { command } => match command {
+            commands::pools::PoolsCommand::List => commands::pools::handle_list_pools(),
+            commands::pools::PoolsCommand::Allowlist { command } => {
+                commands::allowlist::handle_allowlist(command)
+            }
+            commands::pools::PoolsCommand::Admin { command } => {
+                commands::admin::handle_admin(command)
+            }
+        },
+        Commands::Protocol { command } => commands::protocol::handle_protocol(command),
+        Commands::CreateAccount {
+            token_mint,
+            pool,
+            allowlist,
+        } => {
+            commands::initialize_miner::handle_initialize_miner(token_mint, pool, allowlist);
+        }
+        Commands::SetMultiplierTiers(args) => {
+            commands::set_multiplier_tiers::handle_set_multiplier_tiers(&args);
+        }
+        Commands::SetMinerSigner(args) => {
+            commands::set_miner_signer::handle_set_miner_signer(&args);
+        }
+        Commands::SetBeneficiary(args) => {
+            commands::set_beneficiary::handle_set_beneficiary(&args);
+        }
+        Commands::MigrateMiner { token_mint } => {
+            commands::migrate_miner::handle_migrate_miner(token_mint);
+        }
+        Commands::MigratePool { pool } => {
+            commands::migrate_pool::handle_migrate_pool(pool);
+        }
+        Commands::MigrateRewardState { pool } => {
+            commands::migrate_reward_state::handle_migrate_reward_state(pool);
         }
         Commands::GetAccount { token_mint } => {
             commands::get_account::handle_get_account(token_mint);
         }
-        Commands::Mine { token_mint } => {
-            if let Err(e) = commands::mine::handle_mine(token_mint) {
+        Commands::Epochs { pool, count } => {
+            commands::epochs::handle_epochs(pool, count);
+        }
+        Commands::SettleEpoch(args) => {
+            if let Err(e) = commands::settle_epoch::handle_settle_epoch(&args) {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
         }
+        Commands::Mine(args) => {
+            if let Err(e) = commands::mine::handle_mine(&args) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Bench(args) => {
+            commands::bench::handle_bench(&args);
+        }
         Commands::DelegateMiner { token_mint } => {
             commands::delegate_miner::handle_delegate_miner(token_mint);
         }
         Commands::UndelegateMiner { token_mint } => {
             commands::undelegate_miner::handle_undelegate_miner(token_mint);
         }
-        Commands::ClaimRewards { token_mint } => {
-            commands::claim::handle_claim_rewards(token_mint);
+        Commands::ClaimRewards {
+            token_mint,
+            to,
+            amount,
+        } => {
+            commands::claim::handle_claim_rewards(token_mint, to, amount);
+        }
+        Commands::Vesting {
+            token_mint,
+            release,
+            to,
+        } => {
+            commands::vesting::handle_vesting(token_mint, release, to);
+        }
+        Commands::SetVesting {
+            token_mint,
+            pool_maker,
+            cliff_days,
+            days,
+        } => {
+            commands::vesting::handle_set_vesting(token_mint, pool_maker, cliff_days, days);
+        }
+        Commands::Stake { amount, token_mint } => {
+            commands::stake::handle_stake(token_mint, amount);
+        }
+        Commands::Unstake { amount, token_mint } => {
+            commands::unstake::handle_unstake(token_mint, amount);
         }
     }
 }
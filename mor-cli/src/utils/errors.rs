@@ -0,0 +1,316 @@
+// Decodes failed transactions into the mor-core error that caused them
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use colored::*;
+use mor_core::helpers::constants::{
+    MAX_BATCH_SOLUTIONS, MAX_BONUS_MINTS, MAX_COMMISSION_BPS, MAX_MULTIPLIER_TIERS,
+    MAX_PROTOCOL_FEE_BPS,
+};
+use mor_core::helpers::errors::MorErrorCodes;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_request::{RpcError, RpcResponseErrorData};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+
+use crate::utils::helpers::format_bps;
+
+/// Every MorErrorCodes variant, in declaration order. The tests below fail
+/// when a variant is missing.
+const MOR_ERROR_CODES: &[MorErrorCodes] = &[
+    MorErrorCodes::AlreadyMined,
+    MorErrorCodes::InvalidSolution,
+    MorErrorCodes::CapReached,
+    MorErrorCodes::NoRewardsToClaim,
+    MorErrorCodes::NoRewardsAvailable,
+    MorErrorCodes::InvalidAuthority,
+    MorErrorCodes::InvalidEpoch,
+    MorErrorCodes::InvalidEpochRange,
+    MorErrorCodes::InsufficientStakedAmount,
+    MorErrorCodes::InvalidAmount,
+    MorErrorCodes::AlreadyMigrated,
+    MorErrorCodes::InvalidMultiplierTiers,
+    MorErrorCodes::InvalidBeneficiary,
+    MorErrorCodes::CommissionTooHigh,
+    MorErrorCodes::TooManyBonusMints,
+    MorErrorCodes::InvalidBonusMint,
+    MorErrorCodes::InvalidVestingSchedule,
+    MorErrorCodes::VestingEscrowRequired,
+    MorErrorCodes::NotAllowlisted,
+    MorErrorCodes::ProtocolFeeTooHigh,
+    MorErrorCodes::InvalidBatchSize,
+    MorErrorCodes::InvalidRewardShard,
+    MorErrorCodes::AlreadySettled,
+    MorErrorCodes::MinerNotInitialized,
+    MorErrorCodes::RewardStateNotInitialized,
+    MorErrorCodes::RewardPoolNotDelegated,
+    MorErrorCodes::InvalidEpochLedger,
+    MorErrorCodes::EpochLedgerNotSettled,
+];
+
+/// Anchor framework errors the CLI commonly runs into
+const ANCHOR_ERROR_CODES: &[AnchorErrorCode] = &[
+    AnchorErrorCode::AccountDidNotDeserialize,
+    AnchorErrorCode::AccountNotInitialized,
+    AnchorErrorCode::AccountOwnedByWrongProgram,
+    AnchorErrorCode::ConstraintSeeds,
+];
+
+/// A program error decoded from a custom error code
+#[derive(Debug, Clone, Copy)]
+pub enum ProgramError {
+    Mor(MorErrorCodes),
+    Anchor(AnchorErrorCode),
+}
+
+impl ProgramError {
+    /// Looks up the error for an Anchor custom error code (e.g. 0x1771)
+    pub fn from_code(code: u32) -> Option<Self> {
+        if let Some(e) = MOR_ERROR_CODES.iter().find(|e| u32::from(**e) == code) {
+            return Some(ProgramError::Mor(*e));
+        }
+        ANCHOR_ERROR_CODES
+            .iter()
+            .find(|e| u32::from(**e) == code)
+            .map(|e| ProgramError::Anchor(*e))
+    }
+
+    pub fn code(&self) -> u32 {
+        match self {
+            ProgramError::Mor(e) => u32::from(*e),
+            ProgramError::Anchor(e) => u32::from(*e),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            ProgramError::Mor(e) => e.name(),
+            ProgramError::Anchor(e) => e.name(),
+        }
+    }
+
+    /// The `#[msg]` text of the error
+    pub fn message(&self) -> String {
+        match self {
+            ProgramError::Mor(e) => e.to_string(),
+            ProgramError::Anchor(e) => e.to_string(),
+        }
+    }
+
+    /// What the user can do about it. `account` is the account Anchor blamed
+    /// for the error, if the logs named one.
+    pub fn suggestion(&self, account: Option<&str>) -> String {
+        let suggestion = match self {
+            ProgramError::Mor(e) => match e {
+                MorErrorCodes::AlreadyMined => {
+                    "You already submitted a solution for this epoch or a later one. Wait for the next epoch."
+                }
+                MorErrorCodes::InvalidSolution => {
+                    "The nonce does not meet your miner's difficulty. Check it with get-account and mine again."
+                }
+                MorErrorCodes::CapReached => {
+                    "The pool's token cap has been reached. No more rewards can be issued."
+                }
+                MorErrorCodes::NoRewardsToClaim => {
+                    "Mine some epochs first, then run undelegate-miner before claiming."
+                }
+                MorErrorCodes::NoRewardsAvailable => {
+                    "Your reward shard has used up its budget until the pool is settled again. Wait for the pool's settle-epoch crank or run `mor-cli settle-epoch --pool <POOL_PDA> --once`. If `mor-cli pools list` shows the pool has no rewards left, ask the pool maker to top it up or mine another pool."
+                }
+                MorErrorCodes::InvalidAuthority => {
+                    "Only the pool's admin (its maker unless handed over) can do this, and only the miner's authority or hot key can submit. Check the keypair loaded by setup-keypair or --miner-keypair."
+                }
+                MorErrorCodes::InvalidEpoch | MorErrorCodes::InvalidEpochRange => {
+                    "The solution is for an epoch too far from the current one. Mine the current epoch."
+                }
+                MorErrorCodes::InsufficientStakedAmount => {
+                    "Not enough tokens staked in this pool, or less than it requires to mine. Check Staked Amount and Pool Minimum Stake with get-account, then stake more or unstake less."
+                }
+                MorErrorCodes::InvalidAmount => {
+                    "Provide a valid, non-zero amount, no more than you hold (see get-account)."
+                }
+                MorErrorCodes::AlreadyMigrated => "Nothing to do, the account is up to date.",
+                MorErrorCodes::InvalidMultiplierTiers => {
+                    return format!(
+                        "List at most {} tiers, in increasing order of MIN_TOKENS.",
+                        MAX_MULTIPLIER_TIERS
+                    );
+                }
+                MorErrorCodes::CommissionTooHigh => {
+                    return format!(
+                        "Commission is capped at {} bps ({}). Pass a lower --commission-bps.",
+                        MAX_COMMISSION_BPS,
+                        format_bps(MAX_COMMISSION_BPS)
+                    );
+                }
+                MorErrorCodes::InvalidBeneficiary => {
+                    "Claim without --to, or change the beneficiary with set-beneficiary first."
+                }
+                MorErrorCodes::TooManyBonusMints => {
+                    return format!(
+                        "A pool pays at most {} bonus mints. Top up one of them with add-bonus-mint instead.",
+                        MAX_BONUS_MINTS
+                    );
+                }
+                MorErrorCodes::InvalidVestingSchedule => {
+                    "Pass --days above 0 and a --cliff-days no longer than --days."
+                }
+                MorErrorCodes::InvalidBatchSize => {
+                    return format!(
+                        "Submit between 1 and {} solutions at once, one per epoch near the current one.",
+                        MAX_BATCH_SOLUTIONS
+                    );
+                }
+                MorErrorCodes::InvalidRewardShard => {
+                    "Pass every reward shard of the pool, in index order."
+                }
+                MorErrorCodes::AlreadySettled => {
+                    "This epoch was already settled. The next settle can run once the epoch ends."
+                }
+                MorErrorCodes::MinerNotInitialized => {
+                    "You have no miner in this pool yet. Run create-account first."
+                }
+                MorErrorCodes::RewardStateNotInitialized => {
+                    "The pool was not set up with initialize-pool. Check the pool you passed."
+                }
+                MorErrorCodes::RewardPoolNotDelegated => {
+                    "The pool's reward state is on the base layer, so its epoch ledgers stay there. Run settle-epoch with --base-layer."
+                }
+                MorErrorCodes::InvalidEpochLedger => {
+                    "Pass settle_epoch only ledgers of the pool being settled, after its reward shards. settle-epoch takes care of this."
+                }
+                MorErrorCodes::EpochLedgerNotSettled => {
+                    "Solutions of this epoch may still be on the reward shards. Run settle-epoch until it settles past the epoch's window, and the ledger is on the base layer."
+                }
+                MorErrorCodes::ProtocolFeeTooHigh => {
+                    return format!(
+                        "The protocol fee on claims can be at most {} basis points ({}).",
+                        MAX_PROTOCOL_FEE_BPS,
+                        format_bps(MAX_PROTOCOL_FEE_BPS)
+                    );
+                }
+                MorErrorCodes::NotAllowlisted => {
+                    "This pool is private. Ask its maker for the allowlist proofs file and pass it with --allowlist."
+                }
+                MorErrorCodes::VestingEscrowRequired => {
+                    "The pool started vesting its rewards. Claim again with the current mor-cli."
+                }
+                MorErrorCodes::InvalidBonusMint => {
+                    "Bonus mints must differ from the pool's mint and be passed in the pool's order."
+                }
+            },
+            ProgramError::Anchor(e) => match e {
+                AnchorErrorCode::AccountDidNotDeserialize => match account {
+                    Some("miner") | Some("miner_pda") => {
+                        "The miner account uses an older layout. Run migrate-miner first."
+                    }
+                    Some(account) => {
+                        return format!(
+                            "The {} account could not be read. It may use an older layout, check that mor-cli matches the deployed program.",
+                            account
+                        );
+                    }
+                    None => {
+                        "An account could not be read. It may use an older layout, run migrate-miner if it is your miner."
+                    }
+                },
+                AnchorErrorCode::AccountNotInitialized => match account {
+                    Some("protocol_config") => {
+                        "The protocol config does not exist yet. The program's upgrade authority must run protocol init before pools can be created or rewards claimed."
+                    }
+                    _ => "The account does not exist yet. Run create-account first.",
+                },
+                AnchorErrorCode::AccountOwnedByWrongProgram => {
+                    "The account is delegated to the ER. Run undelegate-miner first, or run delegate-miner before mining."
+                }
+                AnchorErrorCode::ConstraintSeeds => {
+                    "The account does not belong to this pool. Check the --token-mint you passed."
+                }
+                _ => "",
+            },
+        };
+        suggestion.to_string()
+    }
+}
+
+/// The account Anchor blamed for a failed simulation, from its
+/// "AnchorError caused by account: <name>." log line
+pub fn failed_account(err: &ClientError) -> Option<String> {
+    let ClientErrorKind::RpcError(RpcError::RpcResponseError {
+        data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+        ..
+    }) = err.kind()
+    else {
+        return None;
+    };
+    result.logs.as_ref()?.iter().find_map(|log| {
+        let (_, rest) = log.split_once("AnchorError caused by account: ")?;
+        rest.split('.').next().map(str::to_string)
+    })
+}
+
+/// Extracts the custom program error code from a failed RPC call, if any
+pub fn custom_error_code(err: &ClientError) -> Option<u32> {
+    match err.get_transaction_error()? {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+        _ => None,
+    }
+}
+
+/// Decodes the mor-core or Anchor error behind a failed RPC call, if any
+pub fn decode_client_error(err: &ClientError) -> Option<ProgramError> {
+    custom_error_code(err).and_then(ProgramError::from_code)
+}
+
+/// Prints a failed transaction, decoding the program error when possible
+pub fn print_transaction_error(err: &ClientError) {
+    match decode_client_error(err) {
+        Some(program_error) => {
+            println!(
+                "{} {} ({:#x}): {}",
+                "Transaction failed:".red(),
+                program_error.name().red().bold(),
+                program_error.code(),
+                program_error.message()
+            );
+            let suggestion = program_error.suggestion(failed_account(err).as_deref());
+            if !suggestion.is_empty() {
+                println!("{}", suggestion.yellow());
+            }
+        }
+        None => println!("{} {}", "Transaction failed:".red(), err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Variant names of MorErrorCodes, read from its source
+    fn declared_error_codes() -> Vec<String> {
+        let source = include_str!("../../../mor-core/programs/mor-core/src/helpers/errors.rs");
+        let body = source
+            .split_once("pub enum MorErrorCodes {")
+            .and_then(|(_, rest)| rest.split_once('}'))
+            .map(|(body, _)| body)
+            .expect("MorErrorCodes enum");
+        body.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("#[") && !line.starts_with("//"))
+            .map(|line| line.trim_end_matches(',').to_string())
+            .collect()
+    }
+
+    #[test]
+    fn every_error_code_is_listed() {
+        let listed: Vec<String> = MOR_ERROR_CODES.iter().map(|e| e.name()).collect();
+        assert_eq!(listed, declared_error_codes());
+    }
+
+    #[test]
+    fn error_codes_decode_to_themselves() {
+        for e in MOR_ERROR_CODES {
+            let decoded = ProgramError::from_code(u32::from(*e)).expect("known code");
+            assert_eq!(decoded.name(), e.name());
+        }
+    }
+}
@@ -1,9 +1,15 @@
 use colored::*;
 use mor_core::ID;
+use mor_core::helpers::constants::MULTIPLIER_BASE_BPS;
+use mor_core::helpers::utils::{apply_loyalty, calculate_loyalty_bps, calculate_multiplier};
+use mor_core::states::{MultiplierTier, PoolMode, PowAlgorithm};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
 use solana_sdk::pubkey;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{Keypair, read_keypair_file};
 use std::env;
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub const PROGRAM_ID: Pubkey = ID;
 
@@ -12,14 +18,162 @@ pub const ER_CLUSTER_URL: &str = "http://localhost:7799";
 
 pub const EPOCH_SLOT_LENGTH: u64 = 150;
 
+/// Average Solana slot time, used to convert between slots and seconds
+pub const SLOT_DURATION: Duration = Duration::from_millis(400);
+
 // Seeds for PDAs
 pub const MINING_POOL_SEED: &[u8] = b"mining_pool";
 pub const MINING_POOL_REWARD_SEED: &[u8] = b"mining_pool_reward";
 pub const MINER_SEED: &[u8] = b"miner";
+pub const STAKE_VAULT_SEED: &[u8] = b"stake_vault";
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint";
+pub const VESTING_SEED: &[u8] = b"vesting";
+pub const PROTOCOL_CONFIG_SEED: &[u8] = b"protocol_config";
+pub const EPOCH_SEED: &[u8] = b"epoch";
+pub const REWARD_SHARD_SEED: &[u8] = b"reward_shard";
 
 pub const DELEGATION_PROGRAM_ID: Pubkey = pubkey!("DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh");
 pub const MAGIC_CONTEXT_PROGRAM_ID: Pubkey = pubkey!("MagicContext1111111111111111111111111111111");
 pub const MAGIC_PROGRAM_ID: Pubkey = pubkey!("Magic11111111111111111111111111111111111111");
+pub const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/// Formats lamports as SOL, e.g. 1500000000 as "1.5 SOL"
+pub fn format_sol(lamports: u64) -> String {
+    format!("{} SOL", lamports as f64 / LAMPORTS_PER_SOL as f64)
+}
+
+/// Parses a PoW algorithm name given on the command line
+pub fn parse_pow_algorithm(name: &str) -> Result<PowAlgorithm, String> {
+    match name.to_lowercase().as_str() {
+        "sha3" => Ok(PowAlgorithm::Sha3),
+        "equix" => Ok(PowAlgorithm::Equix),
+        _ => Err(format!(
+            "unknown PoW algorithm '{}' (expected sha3 or equix)",
+            name
+        )),
+    }
+}
+
+/// Display name of a PoW algorithm, as accepted by parse_pow_algorithm
+pub fn pow_algorithm_name(algorithm: PowAlgorithm) -> &'static str {
+    match algorithm {
+        PowAlgorithm::Sha3 => "sha3",
+        PowAlgorithm::Equix => "equix",
+    }
+}
+
+/// Parses a pool mode name given on the command line
+pub fn parse_pool_mode(name: &str) -> Result<PoolMode, String> {
+    match name.to_lowercase().as_str() {
+        "vault" => Ok(PoolMode::Vault),
+        "mint" => Ok(PoolMode::MintAuthority),
+        _ => Err(format!(
+            "unknown pool mode '{}' (expected vault or mint)",
+            name
+        )),
+    }
+}
+
+/// Display name of a pool mode, as accepted by parse_pool_mode
+pub fn pool_mode_name(mode: PoolMode) -> &'static str {
+    match mode {
+        PoolMode::Vault => "vault",
+        PoolMode::MintAuthority => "mint",
+    }
+}
+
+/// Formats a share in basis points as a percentage, e.g. 250 as 2.5%
+pub fn format_bps(bps: u16) -> String {
+    let percent = format!("{:.2}", bps as f64 / 100.0);
+    format!("{}%", percent.trim_end_matches('0').trim_end_matches('.'))
+}
+
+/// Formats a multiplier in basis points as a decimal, e.g. 15000 as 1.5x
+pub fn format_multiplier(bps: u32) -> String {
+    let multiplier = format!("{:.4}", bps as f64 / MULTIPLIER_BASE_BPS as f64);
+    format!(
+        "{}x",
+        multiplier.trim_end_matches('0').trim_end_matches('.')
+    )
+}
+
+/// Maker of the pool an admin command acts on: `--pool-maker` for a pool
+/// handed over to this wallet, else the wallet itself
+pub fn parse_pool_maker(pool_maker: Option<&str>, admin: &Pubkey) -> Result<Pubkey, String> {
+    match pool_maker {
+        Some(pool_maker) => pool_maker
+            .parse::<Pubkey>()
+            .map_err(|e| format!("Invalid pool maker {}: {}", pool_maker, e)),
+        None => Ok(*admin),
+    }
+}
+
+/// Formats a 32-byte hash as lowercase hex
+pub fn format_hash(hash: &[u8; 32]) -> String {
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Parses a 32-byte hash given as 64 hex characters
+pub fn parse_hash(hex: &str) -> Result<[u8; 32], String> {
+    let hex = hex.trim().trim_start_matches("0x");
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(format!("Invalid hash {}: expected 64 hex characters", hex));
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)
+            .map_err(|e| format!("Invalid hash {}: {}", hex, e))?;
+    }
+    Ok(hash)
+}
+
+/// Current unix time in seconds, as the program's Clock sees it
+pub fn unix_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+/// Multiplier the program gives a miner's next submission: the pool's stake
+/// tier, grown by the loyalty of stake locked since `last_staked_timestamp`
+pub fn effective_multiplier(
+    staked_amount: u64,
+    last_staked_timestamp: i64,
+    decimals: u8,
+    tiers: &[MultiplierTier],
+) -> u32 {
+    apply_loyalty(
+        calculate_multiplier(staked_amount, decimals, tiers),
+        calculate_loyalty_bps(last_staked_timestamp, unix_timestamp()),
+    )
+}
+
+/// Parses a multiplier tier given as MIN_TOKENS:BONUS_BPS:BPS_PER_TOKEN
+pub fn parse_multiplier_tier(tier: &str) -> Result<MultiplierTier, String> {
+    let parts: Vec<&str> = tier.split(':').collect();
+    let [min_tokens, bonus_bps, bps_per_token] = parts[..] else {
+        return Err(format!(
+            "invalid tier '{}' (expected MIN_TOKENS:BONUS_BPS:BPS_PER_TOKEN)",
+            tier
+        ));
+    };
+    let number = |value: &str| {
+        value
+            .parse::<u64>()
+            .map_err(|e| format!("invalid number '{}' in tier '{}': {}", value, tier, e))
+    };
+    let bps = |value: &str| {
+        value
+            .parse::<u32>()
+            .map_err(|e| format!("invalid basis points '{}' in tier '{}': {}", value, tier, e))
+    };
+    Ok(MultiplierTier {
+        min_tokens: number(min_tokens)?,
+        bonus_bps: bps(bonus_bps)?,
+        bps_per_token: bps(bps_per_token)?,
+    })
+}
 
 /// Loads the payer keypair from ~/.config/mor-supply/id.json
 /// Returns Ok(Keypair) if found, or Err(String) with a colored error message if not.
@@ -37,3 +191,17 @@ pub fn load_payer_keypair() -> Result<Keypair, String> {
         }
     }
 }
+
+/// Asks a yes or no question on the terminal, defaulting to no
+/// when the answer is empty or stdin can't be read.
+pub fn confirm(prompt: &str) -> bool {
+    print!("{} [y/N] ", prompt);
+    if io::stdout().flush().is_err() {
+        return false;
+    }
+    let mut answer = String::new();
+    match io::stdin().read_line(&mut answer) {
+        Ok(_) => matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"),
+        Err(_) => false,
+    }
+}
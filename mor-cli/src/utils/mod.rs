@@ -1 +1,3 @@
+pub mod errors;
 pub mod helpers;
+pub mod transaction;
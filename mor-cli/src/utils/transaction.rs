@@ -0,0 +1,123 @@
+// Sends transactions with retries, blockhash refresh and optional priority fees
+
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_client::rpc_client::RpcClient;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use std::thread;
+use std::time::Duration;
+
+/// How a transaction should be sent and retried
+#[derive(Debug, Clone)]
+pub struct SendConfig {
+    /// Number of extra attempts after the first one fails with a transient error
+    pub max_retries: u32,
+    /// Delay between attempts
+    pub retry_delay: Duration,
+    /// Priority fee in micro-lamports per compute unit (base layer only)
+    pub compute_unit_price: Option<u64>,
+    /// Compute unit limit requested for the transaction (base layer only)
+    pub compute_unit_limit: Option<u32>,
+    /// Whether the transaction goes to the Solana base layer rather than the ER
+    pub base_layer: bool,
+}
+
+impl Default for SendConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+            compute_unit_price: None,
+            compute_unit_limit: None,
+            base_layer: false,
+        }
+    }
+}
+
+impl SendConfig {
+    /// Prepends the compute budget instructions when sending to the base layer.
+    /// The ER has no fee market, so they are left out there.
+    pub fn with_compute_budget(&self, instructions: &[Instruction]) -> Vec<Instruction> {
+        let mut all = vec![];
+        if self.base_layer {
+            if let Some(units) = self.compute_unit_limit {
+                all.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+            }
+            if let Some(micro_lamports) = self.compute_unit_price {
+                all.push(ComputeBudgetInstruction::set_compute_unit_price(
+                    micro_lamports,
+                ));
+            }
+        }
+        all.extend_from_slice(instructions);
+        all
+    }
+}
+
+/// Returns true when the same transaction could succeed if sent again.
+/// Program errors (InvalidSolution, InvalidEpochRange, ...) are deterministic
+/// and never retried; network failures and expired blockhashes are.
+pub fn is_transient(err: &ClientError) -> bool {
+    match err.get_transaction_error() {
+        Some(TransactionError::BlockhashNotFound) => true,
+        Some(TransactionError::WouldExceedAccountDataBlockLimit)
+        | Some(TransactionError::WouldExceedMaxBlockCostLimit)
+        | Some(TransactionError::WouldExceedMaxAccountCostLimit)
+        | Some(TransactionError::WouldExceedMaxVoteCostLimit) => true,
+        Some(_) => false,
+        None => !matches!(
+            err.kind(),
+            ClientErrorKind::SigningError(_) | ClientErrorKind::SerdeJson(_)
+        ),
+    }
+}
+
+/// Signs and sends `instructions`, refreshing the blockhash and retrying on
+/// transient errors. Before each retry the previous signature is checked so a
+/// transaction that landed after its confirmation timed out is not sent twice.
+#[allow(clippy::result_large_err)]
+pub fn send_with_retries(
+    client: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    config: &SendConfig,
+) -> ClientResult<Signature> {
+    let instructions = config.with_compute_budget(instructions);
+    let mut last_signature: Option<Signature> = None;
+    let mut attempt = 0;
+
+    loop {
+        if let Some(signature) = last_signature {
+            match client.get_signature_status(&signature) {
+                Ok(Some(Ok(()))) => return Ok(signature),
+                Ok(Some(Err(tx_err))) => return Err(ClientErrorKind::from(tx_err).into()),
+                _ => {}
+            }
+        }
+
+        let result = match client.get_latest_blockhash() {
+            Ok(blockhash) => {
+                let tx = Transaction::new_signed_with_payer(
+                    &instructions,
+                    Some(&payer.pubkey()),
+                    &[payer],
+                    blockhash,
+                );
+                last_signature = Some(tx.signatures[0]);
+                client.send_and_confirm_transaction(&tx)
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(signature) => return Ok(signature),
+            Err(e) if attempt < config.max_retries && is_transient(&e) => {
+                attempt += 1;
+                thread::sleep(config.retry_delay);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::states::MultiplierTier;
+
 #[constant]
 pub const MINER_SEED: &[u8] = b"miner";
 
@@ -9,8 +11,114 @@ pub const MINING_POOL_SEED: &[u8] = b"mining_pool";
 #[constant]
 pub const MINING_POOL_REWARD_SEED: &[u8] = b"mining_pool_reward";
 
+#[constant]
+pub const STAKE_VAULT_SEED: &[u8] = b"stake_vault";
+
 #[constant]
 pub const MINT_AUTHORITY_SEED: &[u8] = b"mint";
 
+#[constant]
+pub const VESTING_SEED: &[u8] = b"vesting";
+
+#[constant]
+pub const PROTOCOL_CONFIG_SEED: &[u8] = b"protocol_config";
+
+#[constant]
+pub const EPOCH_SEED: &[u8] = b"epoch";
+
+#[constant]
+pub const REWARD_SHARD_SEED: &[u8] = b"reward_shard";
+
+/// Reward shards per pool, so concurrent solutions of a pool don't all lock one account
+#[constant]
+pub const REWARD_SHARDS: u8 = 8;
+
 #[constant]
 pub const EPOCH_SLOT_LENGTH: u64 = 150;
+
+/// Epochs before or after the current one that solutions and epoch ledgers are accepted for
+#[constant]
+pub const MAX_EPOCH_OFFSET: u64 = 4;
+
+/// Epochs a reward shard counts solutions of until settle_epoch folds them into
+/// their ledgers, one for each epoch solutions are accepted for at a time
+pub const SHARD_EPOCH_SLOTS: usize = 2 * MAX_EPOCH_OFFSET as usize + 1;
+
+/// Leading zero bits the adaptive difficulty never goes below once a miner has mined
+#[constant]
+pub const MIN_DIFFICULTY: u8 = 8;
+
+/// Leading zero bits the adaptive difficulty never goes above
+#[constant]
+pub const MAX_DIFFICULTY: u8 = 32;
+
+/// Extra leading zero bits beyond the miner's difficulty that each double the reward
+#[constant]
+pub const MAX_REWARD_SCALE_BITS: u8 = 4;
+
+/// Most solutions submit_solutions takes, one for each epoch within ±4 of the current
+#[constant]
+pub const MAX_BATCH_SOLUTIONS: u8 = 9;
+
+/// A 1x multiplier, in basis points
+#[constant]
+pub const MULTIPLIER_BASE_BPS: u32 = 10_000;
+
+/// Most tiers a pool's multiplier table can have
+pub const MAX_MULTIPLIER_TIERS: usize = 8;
+
+/// Multiplier table new pools start with, keeping the tiers of the original
+/// calculate_multiplier: a bonus of 0.5x per token from 1 token, 4x at 10
+/// tokens plus 0.5x per token, 24x at 50 plus 0.33x per token and 40x at 100
+/// plus 0.2x per token
+pub const DEFAULT_MULTIPLIER_TIERS: [MultiplierTier; 4] = [
+    MultiplierTier {
+        min_tokens: 1,
+        bonus_bps: 0,
+        bps_per_token: 5_000,
+    },
+    MultiplierTier {
+        min_tokens: 10,
+        bonus_bps: 40_000,
+        bps_per_token: 5_000,
+    },
+    MultiplierTier {
+        min_tokens: 50,
+        bonus_bps: 240_000,
+        bps_per_token: 3_333,
+    },
+    MultiplierTier {
+        min_tokens: 100,
+        bonus_bps: 400_000,
+        bps_per_token: 2_000,
+    },
+];
+
+/// Most bonus reward mints a pool can pay alongside its own mint
+pub const MAX_BONUS_MINTS: usize = 3;
+
+/// Largest share of each credited reward a pool maker can take as commission,
+/// in basis points
+#[constant]
+pub const MAX_COMMISSION_BPS: u16 = 2_000;
+
+/// Largest share of each claim the protocol can take for its treasury, in
+/// basis points
+#[constant]
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
+/// Largest loyalty boost of the stake bonus, in basis points of that bonus
+#[constant]
+pub const MAX_LOYALTY_BPS: u32 = 5_000;
+
+/// Seconds stake must stay locked to earn MAX_LOYALTY_BPS (50 days)
+#[constant]
+pub const LOYALTY_RAMP_SECONDS: i64 = 50 * 86_400;
+
+/// Layout version of MinerAccountPoolPda, bumped whenever migrate_miner has work to do.
+/// Version 0 accounts store difficulty in whole leading zero bytes, versions
+/// before 2 store the multiplier as a whole u8, versions before 3 have no signer,
+/// versions before 4 no beneficiary, versions before 5 no bonus rewards and
+/// versions before 6 no lifetime stats.
+#[constant]
+pub const MINER_ACCOUNT_VERSION: u8 = 6;
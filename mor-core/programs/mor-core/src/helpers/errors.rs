@@ -22,4 +22,40 @@ pub enum MorErrorCodes {
     InsufficientStakedAmount,
     #[msg("Invalid amount.")]
     InvalidAmount,
+    #[msg("Account is already migrated.")]
+    AlreadyMigrated,
+    #[msg("Multiplier tiers must be sorted by stake, with at most 8 tiers.")]
+    InvalidMultiplierTiers,
+    #[msg("Rewards must be claimed to the miner's beneficiary.")]
+    InvalidBeneficiary,
+    #[msg("Commission exceeds the protocol maximum.")]
+    CommissionTooHigh,
+    #[msg("The pool already pays the most bonus reward mints.")]
+    TooManyBonusMints,
+    #[msg("Invalid bonus reward mint or accounts.")]
+    InvalidBonusMint,
+    #[msg("Vesting needs a duration, and a cliff no longer than it.")]
+    InvalidVestingSchedule,
+    #[msg("Rewards of this pool vest, the vesting escrow must be passed.")]
+    VestingEscrowRequired,
+    #[msg("The miner's authority is not on the pool's allowlist.")]
+    NotAllowlisted,
+    #[msg("Protocol fee exceeds the maximum.")]
+    ProtocolFeeTooHigh,
+    #[msg("A batch must hold between one and the maximum number of solutions.")]
+    InvalidBatchSize,
+    #[msg("Reward shard does not belong to this pool or index.")]
+    InvalidRewardShard,
+    #[msg("This epoch's credits were already settled.")]
+    AlreadySettled,
+    #[msg("Miner account does not exist, create it with initialize_miner first.")]
+    MinerNotInitialized,
+    #[msg("Pool reward state does not exist.")]
+    RewardStateNotInitialized,
+    #[msg("Epoch ledgers are only delegated while the pool's reward state is.")]
+    RewardPoolNotDelegated,
+    #[msg("Epoch ledger does not belong to the pool.")]
+    InvalidEpochLedger,
+    #[msg("Epoch ledger is not settled past its epoch's window yet.")]
+    EpochLedgerNotSettled,
 }
@@ -1,6 +1,14 @@
 use anchor_lang::prelude::Pubkey;
 use sha3::{Digest, Sha3_256};
 
+use crate::{
+    helpers::constants::{
+        LOYALTY_RAMP_SECONDS, MAX_DIFFICULTY, MAX_LOYALTY_BPS, MAX_MULTIPLIER_TIERS,
+        MIN_DIFFICULTY, MULTIPLIER_BASE_BPS, REWARD_SHARDS, SHARD_EPOCH_SLOTS,
+    },
+    states::{EpochLedger, EpochStats, MultiplierTier, PowAlgorithm},
+};
+
 pub fn generate_challenge(pubkey: &Pubkey, epoch: u64) -> [u8; 32] {
     let mut hasher = Sha3_256::new();
     hasher.update(pubkey.to_bytes());
@@ -8,23 +16,560 @@ pub fn generate_challenge(pubkey: &Pubkey, epoch: u64) -> [u8; 32] {
     hasher.finalize().into()
 }
 
-// Linear tier multiplier
-pub fn calculate_multiplier(staked_tokens: u64, base_rate: u64) -> u64 {
-    let staked_token_in_whole = staked_tokens.saturating_div(1_000_000_000);
-    if staked_token_in_whole == 0 {
+/// Equi-X challenge of a nonce attempt: challenge || nonce
+pub fn equix_seed(challenge: &[u8; 32], nonce: u64) -> [u8; 40] {
+    let mut seed = [0; 40];
+    seed[..32].copy_from_slice(challenge);
+    seed[32..].copy_from_slice(&nonce.to_le_bytes());
+    seed
+}
+
+/// Checks the proof of work of a nonce attempt. SHA3 has nothing to prove
+/// beyond the hash, Equi-X needs `digest` to be a solution for the nonce.
+pub fn verify_pow(
+    algorithm: PowAlgorithm,
+    challenge: &[u8; 32],
+    nonce: u64,
+    digest: &[u8; 16],
+) -> bool {
+    match algorithm {
+        PowAlgorithm::Sha3 => true,
+        PowAlgorithm::Equix => equix::verify_bytes(&equix_seed(challenge, nonce), digest).is_ok(),
+    }
+}
+
+/// Hash of a nonce attempt, whose leading zero bits are its difficulty.
+/// SHA3 pools hash SHA3(challenge || nonce), Equi-X pools hash the solution
+/// as SHA3(challenge || nonce || digest).
+pub fn pow_hash(
+    algorithm: PowAlgorithm,
+    challenge: &[u8; 32],
+    nonce: u64,
+    digest: &[u8; 16],
+) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(challenge);
+    hasher.update(nonce.to_le_bytes());
+    if algorithm == PowAlgorithm::Equix {
+        hasher.update(digest);
+    }
+    hasher.finalize().into()
+}
+
+/// Number of leading zero bits of a hash, compared against the miner's difficulty
+pub fn leading_zero_bits(hash: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+/// Staking multiplier in basis points: 1x plus the bonus of the highest tier
+/// the whole staked tokens reach, growing linearly within that tier
+pub fn calculate_multiplier(staked_amount: u64, decimals: u8, tiers: &[MultiplierTier]) -> u32 {
+    let staked_tokens = 10_u64
+        .checked_pow(decimals as u32)
+        .map_or(0, |unit| staked_amount / unit);
+
+    let tier_bonus = tiers
+        .iter()
+        .rev()
+        .find(|tier| staked_tokens >= tier.min_tokens)
+        .map_or(0, |tier| {
+            (staked_tokens - tier.min_tokens)
+                .saturating_mul(tier.bps_per_token as u64)
+                .saturating_add(tier.bonus_bps as u64)
+        });
+
+    MULTIPLIER_BASE_BPS.saturating_add(u32::try_from(tier_bonus).unwrap_or(u32::MAX))
+}
+
+/// Loyalty boost in basis points for stake locked since `last_staked_timestamp`,
+/// growing linearly to MAX_LOYALTY_BPS over LOYALTY_RAMP_SECONDS
+pub fn calculate_loyalty_bps(last_staked_timestamp: i64, now: i64) -> u32 {
+    if last_staked_timestamp <= 0 {
         return 0;
     }
+    let locked = now
+        .saturating_sub(last_staked_timestamp)
+        .clamp(0, LOYALTY_RAMP_SECONDS);
+    (locked as u128 * MAX_LOYALTY_BPS as u128 / LOYALTY_RAMP_SECONDS as u128) as u32
+}
+
+/// Grows the stake bonus of a multiplier (everything above 1x) by `loyalty_bps`
+pub fn apply_loyalty(multiplier: u32, loyalty_bps: u32) -> u32 {
+    let stake_bonus = multiplier.saturating_sub(MULTIPLIER_BASE_BPS) as u64;
+    let loyalty_bonus = stake_bonus * loyalty_bps as u64 / MULTIPLIER_BASE_BPS as u64;
+    multiplier.saturating_add(u32::try_from(loyalty_bonus).unwrap_or(u32::MAX))
+}
+
+/// Moves `last_staked_timestamp` towards `now` by the share `changed / total`
+/// of the time locked so far. Staking weighs the added tokens against the new
+/// stake, so they can't borrow the age of a small old stake; unstaking weighs
+/// the removed tokens against the old stake and forfeits that share of the time.
+pub fn weighted_stake_timestamp(
+    last_staked_timestamp: i64,
+    now: i64,
+    changed: u64,
+    total: u64,
+) -> i64 {
+    if last_staked_timestamp <= 0 || total == 0 {
+        return now;
+    }
+    let locked = now.saturating_sub(last_staked_timestamp).max(0) as u128;
+    let moved = (locked * changed.min(total) as u128).div_ceil(total as u128);
+    last_staked_timestamp.saturating_add(moved as i64)
+}
+
+/// Tokens of `deposited` vested by `now` on a linear schedule of
+/// `duration_seconds` starting at `start_timestamp`
+pub fn linear_vested(deposited: u64, start_timestamp: i64, duration_seconds: i64, now: i64) -> u64 {
+    let elapsed = now.saturating_sub(start_timestamp).max(0);
+    if elapsed >= duration_seconds {
+        return deposited;
+    }
+    (deposited as u128 * elapsed as u128 / duration_seconds as u128) as u64
+}
+
+/// Start of a linear schedule of `duration_seconds` over `total` tokens that
+/// has vested `vested` of them by `now`. A deposit moves the start this way,
+/// so what already vested stays vested and the new tokens vest from now on.
+pub fn vesting_start(vested: u64, total: u64, duration_seconds: i64, now: i64) -> i64 {
+    if total == 0 {
+        return now;
+    }
+    let elapsed = vested.min(total) as u128 * duration_seconds.max(0) as u128 / total as u128;
+    now.saturating_sub(elapsed as i64)
+}
+
+/// A vesting schedule needs a duration, and a cliff no longer than it
+pub fn validate_vesting(cliff_seconds: i64, duration_seconds: i64) -> bool {
+    duration_seconds > 0 && (0..=duration_seconds).contains(&cliff_seconds)
+}
+
+/// Leaf of a pool allowlist Merkle tree for `pubkey`. Leaves and nodes are
+/// domain separated so a node can't pass as an allowlisted key.
+pub fn allowlist_leaf(pubkey: &Pubkey) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update([0u8]);
+    hasher.update(pubkey.as_ref());
+    hasher.finalize().into()
+}
+
+/// Parent of two allowlist tree nodes, hashed in sorted order so proofs
+/// don't need to say which side each sibling is on
+pub fn allowlist_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha3_256::new();
+    hasher.update([1u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Whether `proof` leads from the leaf of `pubkey` to `root`
+pub fn verify_allowlist_proof(root: &[u8; 32], pubkey: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let node = proof.iter().fold(allowlist_leaf(pubkey), |node, sibling| {
+        allowlist_node(&node, sibling)
+    });
+    node == *root
+}
+
+/// Builds every level of the allowlist tree of `pubkeys`, leaves first. A node
+/// without a sibling moves up a level unchanged.
+fn allowlist_levels(pubkeys: &[Pubkey]) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![pubkeys.iter().map(allowlist_leaf).collect::<Vec<_>>()];
+    while levels[levels.len() - 1].len() > 1 {
+        let level = levels[levels.len() - 1]
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => allowlist_node(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(level);
+    }
+    levels
+}
+
+/// Root of the allowlist tree of `pubkeys`, None when there are none
+pub fn allowlist_root(pubkeys: &[Pubkey]) -> Option<[u8; 32]> {
+    allowlist_levels(pubkeys).pop()?.first().copied()
+}
+
+/// Proof that `pubkeys[index]` is in the allowlist tree of `pubkeys`
+pub fn allowlist_proof(pubkeys: &[Pubkey], index: usize) -> Vec<[u8; 32]> {
+    let levels = allowlist_levels(pubkeys);
+    let mut proof = vec![];
+    let mut index = index;
+    for level in &levels[..levels.len() - 1] {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        index /= 2;
+    }
+    proof
+}
+
+/// A multiplier table needs at most MAX_MULTIPLIER_TIERS tiers, sorted by
+/// strictly increasing `min_tokens`
+pub fn validate_multiplier_tiers(tiers: &[MultiplierTier]) -> bool {
+    tiers.len() <= MAX_MULTIPLIER_TIERS
+        && tiers
+            .windows(2)
+            .all(|pair| pair[0].min_tokens < pair[1].min_tokens)
+}
+
+/// Reward shard a miner credits its solutions to, spread by its authority
+pub fn reward_shard_index(authority: &Pubkey) -> u8 {
+    authority.to_bytes()[0] % REWARD_SHARDS
+}
+
+/// Slot of a reward shard's epoch_stats that counts the solutions of `epoch`
+pub fn epoch_stats_slot(epoch: u64) -> usize {
+    (epoch % SHARD_EPOCH_SLOTS as u64) as usize
+}
+
+/// Adds a reward shard's counters of an epoch to the epoch's ledger
+pub fn fold_epoch_stats(epoch_ledger: &mut EpochLedger, stats: &EpochStats) {
+    epoch_ledger.submissions = epoch_ledger.submissions.saturating_add(stats.submissions);
+    epoch_ledger.total_rewards = epoch_ledger
+        .total_rewards
+        .saturating_add(stats.total_rewards);
+    if stats.best_difficulty > epoch_ledger.best_difficulty {
+        epoch_ledger.best_difficulty = stats.best_difficulty;
+        epoch_ledger.best_miner = stats.best_miner;
+    }
+}
+
+/// Share of `total` budgeted to shard `index`, the remainder going to the first shards
+pub fn shard_share(total: u64, index: u8) -> u64 {
+    let shards = REWARD_SHARDS as u64;
+    total / shards + u64::from((index as u64) < total % shards)
+}
+
+/// Difficulty after a solution submitted in `current_epoch`, by the epoch the miner
+/// last mined before it. Mining the next epoch on time keeps the difficulty, a second
+/// solution within an epoch raises it and skipped epochs lower it, one bit at a time.
+/// u64::MAX means the miner never mined, which keeps it too.
+pub fn next_difficulty(difficulty: u8, previous_epoch: u64, current_epoch: u64) -> u8 {
+    if previous_epoch == u64::MAX {
+        return difficulty;
+    }
+    match current_epoch.saturating_sub(previous_epoch) {
+        0 if difficulty < MAX_DIFFICULTY => (difficulty + 1).max(MIN_DIFFICULTY),
+        2.. if difficulty > MIN_DIFFICULTY => difficulty - 1,
+        _ => difficulty,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::constants::DEFAULT_MULTIPLIER_TIERS;
+
+    const DECIMALS: u8 = 9;
+    const TOKEN: u64 = 1_000_000_000;
+
+    fn multiplier(tokens: u64) -> u32 {
+        calculate_multiplier(tokens * TOKEN, DECIMALS, &DEFAULT_MULTIPLIER_TIERS)
+    }
+
+    #[test]
+    fn no_stake_is_1x() {
+        assert_eq!(multiplier(0), 10_000);
+        assert_eq!(
+            calculate_multiplier(TOKEN - 1, DECIMALS, &DEFAULT_MULTIPLIER_TIERS),
+            10_000
+        );
+    }
+
+    #[test]
+    fn first_tier_boundaries() {
+        assert_eq!(multiplier(1), 10_000);
+        assert_eq!(multiplier(2), 15_000);
+        assert_eq!(multiplier(9), 50_000);
+    }
+
+    #[test]
+    fn second_tier_boundaries() {
+        assert_eq!(multiplier(10), 50_000);
+        assert_eq!(multiplier(11), 55_000);
+        assert_eq!(multiplier(49), 245_000);
+    }
+
+    #[test]
+    fn third_tier_boundaries() {
+        assert_eq!(multiplier(50), 250_000);
+        assert_eq!(multiplier(51), 253_333);
+        assert_eq!(multiplier(99), 413_317);
+    }
+
+    #[test]
+    fn fourth_tier_boundaries() {
+        assert_eq!(multiplier(100), 410_000);
+        assert_eq!(multiplier(101), 412_000);
+        assert_eq!(multiplier(1_000), 2_210_000);
+    }
+
+    #[test]
+    fn default_tiers_keep_the_original_table() {
+        // The original tiers in whole x, with the `_` arm counting whole tokens
+        let original = |tokens: u64| -> u32 {
+            let bonus = match tokens {
+                0 => 0,
+                1..=9 => tokens / 2,
+                10..=49 => 4 + (tokens - 10) / 2,
+                50..=99 => 24 + (tokens - 50) / 3,
+                _ => 40 + (tokens - 100) / 5,
+            };
+            MULTIPLIER_BASE_BPS + bonus as u32 * MULTIPLIER_BASE_BPS
+        };
+        for tokens in 0..500 {
+            // Basis points keep the fractions the original rounded down
+            assert!(multiplier(tokens).abs_diff(original(tokens)) < MULTIPLIER_BASE_BPS);
+        }
+        for tokens in [1, 10, 50, 100] {
+            assert_eq!(multiplier(tokens), original(tokens));
+        }
+    }
+
+    #[test]
+    fn counts_whole_tokens_not_base_units() {
+        // 100 tokens plus dust stays in the fourth tier's first step
+        assert_eq!(
+            calculate_multiplier(100 * TOKEN + 99, DECIMALS, &DEFAULT_MULTIPLIER_TIERS),
+            410_000
+        );
+        assert_eq!(
+            calculate_multiplier(100, 0, &DEFAULT_MULTIPLIER_TIERS),
+            410_000
+        );
+    }
+
+    #[test]
+    fn huge_stakes_saturate() {
+        assert_eq!(
+            calculate_multiplier(u64::MAX, 0, &DEFAULT_MULTIPLIER_TIERS),
+            u32::MAX
+        );
+    }
+
+    #[test]
+    fn no_tiers_is_1x() {
+        assert_eq!(calculate_multiplier(1_000 * TOKEN, DECIMALS, &[]), 10_000);
+    }
+
+    #[test]
+    fn validates_tier_order_and_count() {
+        assert!(validate_multiplier_tiers(&DEFAULT_MULTIPLIER_TIERS));
+        assert!(validate_multiplier_tiers(&[]));
+
+        let mut unsorted = DEFAULT_MULTIPLIER_TIERS;
+        unsorted.swap(0, 1);
+        assert!(!validate_multiplier_tiers(&unsorted));
+
+        let mut duplicate = DEFAULT_MULTIPLIER_TIERS;
+        duplicate[1].min_tokens = duplicate[0].min_tokens;
+        assert!(!validate_multiplier_tiers(&duplicate));
+
+        let too_many: Vec<MultiplierTier> = (0..=MAX_MULTIPLIER_TIERS as u64)
+            .map(|min_tokens| MultiplierTier {
+                min_tokens,
+                bonus_bps: 0,
+                bps_per_token: 0,
+            })
+            .collect();
+        assert!(!validate_multiplier_tiers(&too_many));
+    }
+
+    const DAY: i64 = 86_400;
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn loyalty_grows_linearly_to_the_cap() {
+        assert_eq!(calculate_loyalty_bps(0, NOW), 0);
+        assert_eq!(calculate_loyalty_bps(NOW, NOW), 0);
+        assert_eq!(calculate_loyalty_bps(NOW - 10 * DAY, NOW), 1_000);
+        assert_eq!(calculate_loyalty_bps(NOW - 25 * DAY, NOW), 2_500);
+        assert_eq!(calculate_loyalty_bps(NOW - 50 * DAY, NOW), MAX_LOYALTY_BPS);
+        assert_eq!(calculate_loyalty_bps(NOW - 500 * DAY, NOW), MAX_LOYALTY_BPS);
+        // A timestamp ahead of the clock earns nothing
+        assert_eq!(calculate_loyalty_bps(NOW + DAY, NOW), 0);
+    }
+
+    #[test]
+    fn loyalty_only_boosts_the_stake_bonus() {
+        assert_eq!(apply_loyalty(10_000, MAX_LOYALTY_BPS), 10_000);
+        assert_eq!(apply_loyalty(15_000, 0), 15_000);
+        assert_eq!(apply_loyalty(15_000, MAX_LOYALTY_BPS), 17_500);
+        assert_eq!(apply_loyalty(u32::MAX, MAX_LOYALTY_BPS), u32::MAX);
+    }
+
+    #[test]
+    fn first_stake_starts_the_clock() {
+        assert_eq!(weighted_stake_timestamp(0, NOW, 100, 100), NOW);
+    }
+
+    #[test]
+    fn topping_up_dilutes_the_stake_age() {
+        let start = NOW - 40 * DAY;
+        // Doubling the stake halves its age
+        assert_eq!(
+            weighted_stake_timestamp(start, NOW, 100, 200),
+            NOW - 20 * DAY
+        );
+        // A large top up of a small old stake is almost new
+        assert_eq!(
+            weighted_stake_timestamp(start, NOW, 99, 100),
+            NOW - 40 * DAY / 100
+        );
+    }
 
-    let tier_bonus = match staked_token_in_whole {
-        1..=9 => staked_token_in_whole.saturating_div(2), // 0.5x per token (rounded down)
-        10..=49 => {
-            4_u64.saturating_add((staked_token_in_whole.saturating_sub(10)).saturating_div(2))
-        } // 4 + 0.5x per token
-        50..=99 => {
-            24_u64.saturating_add((staked_token_in_whole.saturating_sub(50)).saturating_div(3))
-        } // 24 + 0.33x per token
-        _ => 40_u64.saturating_add((staked_tokens.saturating_sub(100)).saturating_div(5)), // 40 + 0.2x per token
-    };
+    #[test]
+    fn unstaking_forfeits_its_share_of_the_time() {
+        let start = NOW - 40 * DAY;
+        assert_eq!(
+            weighted_stake_timestamp(start, NOW, 25, 100),
+            NOW - 30 * DAY
+        );
+        assert_eq!(weighted_stake_timestamp(start, NOW, 100, 100), NOW);
+        assert_eq!(weighted_stake_timestamp(start, NOW, 0, 100), start);
+    }
+
+    #[test]
+    fn linear_vesting() {
+        let start = NOW - 25 * DAY;
+        assert_eq!(linear_vested(1_000, start, 100 * DAY, NOW), 250);
+        assert_eq!(linear_vested(1_000, NOW, 100 * DAY, NOW), 0);
+        assert_eq!(linear_vested(1_000, NOW + DAY, 100 * DAY, NOW), 0);
+        assert_eq!(linear_vested(1_000, start, 25 * DAY, NOW), 1_000);
+    }
+
+    #[test]
+    fn deposits_keep_what_vested() {
+        let duration = 100 * DAY;
+        let start = NOW - 40 * DAY;
+        let vested = linear_vested(1_000, start, duration, NOW);
+        assert_eq!(vested, 400);
 
-    base_rate.saturating_add(tier_bonus)
+        // Doubling the escrow keeps the 400 vested and vests the rest from here
+        let start = vesting_start(vested, 2_000, duration, NOW);
+        assert_eq!(start, NOW - 20 * DAY);
+        assert_eq!(linear_vested(2_000, start, duration, NOW), 400);
+        assert_eq!(linear_vested(2_000, start, duration, NOW + 80 * DAY), 2_000);
+
+        // A fully vested escrow doesn't vest new tokens early
+        let start = vesting_start(1_000, 2_000, duration, NOW);
+        assert_eq!(linear_vested(2_000, start, duration, NOW), 1_000);
+        assert_eq!(vesting_start(0, 0, duration, NOW), NOW);
+    }
+
+    #[test]
+    fn vesting_schedule_validation() {
+        assert!(validate_vesting(0, DAY));
+        assert!(validate_vesting(DAY, DAY));
+        assert!(!validate_vesting(2 * DAY, DAY));
+        assert!(!validate_vesting(0, 0));
+        assert!(!validate_vesting(-1, DAY));
+    }
+
+    fn allowlisted(count: u8) -> Vec<Pubkey> {
+        (0..count)
+            .map(|i| Pubkey::new_from_array([i + 1; 32]))
+            .collect()
+    }
+
+    #[test]
+    fn allowlist_proofs_verify() {
+        for count in 1..=9 {
+            let pubkeys = allowlisted(count);
+            let root = allowlist_root(&pubkeys).unwrap();
+            for (index, pubkey) in pubkeys.iter().enumerate() {
+                let proof = allowlist_proof(&pubkeys, index);
+                assert!(verify_allowlist_proof(&root, pubkey, &proof));
+            }
+        }
+        assert_eq!(allowlist_root(&[]), None);
+    }
+
+    #[test]
+    fn allowlist_rejects_other_keys() {
+        let pubkeys = allowlisted(5);
+        let root = allowlist_root(&pubkeys).unwrap();
+        let outsider = Pubkey::new_from_array([42; 32]);
+        assert!(!verify_allowlist_proof(&root, &outsider, &[]));
+        assert!(!verify_allowlist_proof(
+            &root,
+            &outsider,
+            &allowlist_proof(&pubkeys, 0)
+        ));
+        // Someone else's proof doesn't work either
+        assert!(!verify_allowlist_proof(
+            &root,
+            &pubkeys[0],
+            &allowlist_proof(&pubkeys, 1)
+        ));
+        // Nor does presenting an inner node as the proof's leaf
+        let levels = allowlist_levels(&pubkeys);
+        let inner = Pubkey::new_from_array(levels[1][0]);
+        assert!(!verify_allowlist_proof(&root, &inner, &levels[1][1..2]));
+    }
+
+    #[test]
+    fn shard_shares_add_up() {
+        for total in [0, 1, 7, 8, 9, 1_000_000_007, u64::MAX] {
+            let shares: Vec<u64> = (0..REWARD_SHARDS).map(|i| shard_share(total, i)).collect();
+            assert_eq!(
+                shares.iter().map(|s| *s as u128).sum::<u128>(),
+                total as u128
+            );
+            assert!(shares.iter().max().unwrap() - shares.iter().min().unwrap() <= 1);
+        }
+        for byte in 0..=u8::MAX {
+            assert!(reward_shard_index(&Pubkey::new_from_array([byte; 32])) < REWARD_SHARDS);
+        }
+    }
+
+    #[test]
+    fn accepted_epochs_count_in_separate_slots() {
+        use crate::helpers::constants::MAX_EPOCH_OFFSET;
+
+        for current in [MAX_EPOCH_OFFSET, 1_000, u64::MAX - MAX_EPOCH_OFFSET] {
+            let mut slots: Vec<usize> = (current - MAX_EPOCH_OFFSET..=current + MAX_EPOCH_OFFSET)
+                .map(epoch_stats_slot)
+                .collect();
+            slots.sort();
+            slots.dedup();
+            assert_eq!(slots.len(), SHARD_EPOCH_SLOTS);
+        }
+    }
+
+    #[test]
+    fn on_time_solutions_keep_the_difficulty() {
+        let mut difficulty = 12;
+        for epoch in 100..120 {
+            difficulty = next_difficulty(difficulty, epoch - 1, epoch);
+        }
+        assert_eq!(difficulty, 12);
+    }
+
+    #[test]
+    fn difficulty_follows_the_solving_pace() {
+        // Mining ahead, within the epoch last mined or before it
+        assert_eq!(next_difficulty(12, 100, 100), 13);
+        assert_eq!(next_difficulty(12, 101, 100), 13);
+        assert_eq!(next_difficulty(0, 100, 100), MIN_DIFFICULTY);
+        assert_eq!(next_difficulty(MAX_DIFFICULTY, 100, 100), MAX_DIFFICULTY);
+        // Skipping epochs
+        assert_eq!(next_difficulty(12, 100, 102), 11);
+        assert_eq!(next_difficulty(MIN_DIFFICULTY, 100, 110), MIN_DIFFICULTY);
+        // The first solution
+        assert_eq!(next_difficulty(0, u64::MAX, 100), 0);
+    }
 }
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{constants::MINING_POOL_SEED, errors::MorErrorCodes},
+    states::MiningPoolPda,
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    /// # Become the pool's admin, as proposed by the current one
+    /// Signing proves the new admin, such as a multisig, can act for the pool.
+    fn accept_admin(&mut self) -> Result<()> {
+        let pending_admin = self.mining_pool_pda.pending_admin;
+        require!(
+            pending_admin != Pubkey::default() && self.new_admin.key() == pending_admin,
+            MorErrorCodes::InvalidAuthority
+        );
+
+        self.mining_pool_pda.admin = pending_admin;
+        self.mining_pool_pda.pending_admin = Pubkey::default();
+        Ok(())
+    }
+}
+
+pub fn accept_admin_handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    ctx.accounts.accept_admin()?;
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    helpers::{
+        constants::{MAX_BONUS_MINTS, MINING_POOL_REWARD_SEED, MINING_POOL_SEED},
+        errors::MorErrorCodes,
+    },
+    states::{BonusMint, MiningPoolPda, MiningPoolRewardState},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct AddBonusMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    #[account(
+        mut,
+        seeds = [MINING_POOL_REWARD_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump,
+    )]
+    pub mining_pool_reward_state: Account<'info, MiningPoolRewardState>,
+
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub bonus_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = bonus_mint,
+        associated_token::authority = admin,
+        associated_token::token_program = token_program
+    )]
+    pub admin_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = bonus_mint,
+        associated_token::authority = mining_pool_pda,
+        associated_token::token_program = token_program
+    )]
+    pub bonus_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Programs
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddBonusMint<'info> {
+    /// # Add a bonus mint to the pool, or top up and retune an existing one
+    fn add_bonus_mint(&mut self, amount: u64, rate_bps: u32, ends_at: i64) -> Result<()> {
+        // Only the mining pool's admin can fund bonus rewards
+        require!(
+            self.admin.key() == self.mining_pool_pda.admin,
+            MorErrorCodes::InvalidAuthority
+        );
+        require!(amount > 0 && rate_bps > 0, MorErrorCodes::InvalidAmount);
+        require!(
+            ends_at > Clock::get()?.unix_timestamp,
+            MorErrorCodes::InvalidAmount
+        );
+        require!(
+            self.bonus_mint.key() != self.mining_pool_pda.mint,
+            MorErrorCodes::InvalidBonusMint
+        );
+
+        let bonus = BonusMint {
+            mint: self.bonus_mint.key(),
+            decimals: self.bonus_mint.decimals,
+            rate_bps,
+            ends_at,
+        };
+        let pool = &mut self.mining_pool_pda;
+        let index = match pool.bonus_mints.iter().position(|b| b.mint == bonus.mint) {
+            Some(index) => {
+                pool.bonus_mints[index] = bonus;
+                index
+            }
+            None => {
+                require!(
+                    pool.bonus_mints.len() < MAX_BONUS_MINTS,
+                    MorErrorCodes::TooManyBonusMints
+                );
+                pool.bonus_mints.push(bonus);
+                pool.bonus_mints.len() - 1
+            }
+        };
+
+        // The remaining budget sits next to the pool's own in the reward state
+        let bonus_amounts = &mut self.mining_pool_reward_state.bonus_amounts;
+        if bonus_amounts.len() <= index {
+            bonus_amounts.resize(index + 1, 0);
+        }
+        bonus_amounts[index] = bonus_amounts[index]
+            .checked_add(amount)
+            .ok_or(MorErrorCodes::InvalidAmount)?;
+
+        Ok(())
+    }
+
+    /// # Deposit the bonus tokens
+    fn deposit_tokens(&mut self, amount: u64) -> Result<()> {
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.admin_ata.to_account_info(),
+                    mint: self.bonus_mint.to_account_info(),
+                    to: self.bonus_vault.to_account_info(),
+                    authority: self.admin.to_account_info(),
+                },
+            ),
+            amount,
+            self.bonus_mint.decimals,
+        )?;
+
+        Ok(())
+    }
+}
+
+pub fn add_bonus_mint_handler(
+    ctx: Context<AddBonusMint>,
+    amount: u64,
+    rate_bps: u32,
+    ends_at: i64,
+) -> Result<()> {
+    ctx.accounts.add_bonus_mint(amount, rate_bps, ends_at)?;
+
+    ctx.accounts.deposit_tokens(amount)?;
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        mint_to_checked, transfer_checked, Mint, MintToChecked, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{
+    helpers::{
+        constants::{MINING_POOL_REWARD_SEED, MINING_POOL_SEED, MINT_AUTHORITY_SEED},
+        errors::MorErrorCodes,
+    },
+    states::{MiningPoolPda, MiningPoolRewardState, PoolMode},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ClaimCommission<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    #[account(
+        mut,
+        seeds = [MINING_POOL_REWARD_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump,
+    )]
+    pub mining_pool_reward_state: Account<'info, MiningPoolRewardState>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mining_pool_pda,
+        associated_token::token_program = token_program
+      )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = mining_pool_pda.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Mint authority of MintAuthority pools, only signs
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mining_pool_pda.key().as_ref()],
+        bump = mining_pool_pda.mint_authority_bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = admin,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Programs
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimCommission<'info> {
+    /// # Pay the pool's admin the commission earned so far
+    fn claim_commission(&mut self) -> Result<()> {
+        // The commission goes to whoever runs the pool now
+        require!(
+            self.admin.key() == self.mining_pool_pda.admin,
+            MorErrorCodes::InvalidAuthority
+        );
+        let reward_state = &mut self.mining_pool_reward_state;
+        require!(
+            reward_state.maker_rewards > 0,
+            MorErrorCodes::NoRewardsToClaim
+        );
+
+        let amount = reward_state.maker_rewards;
+        match self.mining_pool_pda.mode {
+            PoolMode::Vault => {
+                let pool_maker_key = self.mining_pool_pda.pool_maker.key();
+                let mint_key = self.mining_pool_pda.mint.key();
+                let signer_seeds: [&[&[u8]]; 1] = [&[
+                    MINING_POOL_SEED,
+                    pool_maker_key.as_ref(),
+                    mint_key.as_ref(),
+                    &[self.mining_pool_pda.bump],
+                ]];
+
+                transfer_checked(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        TransferChecked {
+                            from: self.vault.to_account_info(),
+                            to: self.recipient_ata.to_account_info(),
+                            mint: self.mint.to_account_info(),
+                            authority: self.mining_pool_pda.to_account_info(),
+                        },
+                        &signer_seeds,
+                    ),
+                    amount,
+                    self.mint.decimals,
+                )?;
+            }
+            PoolMode::MintAuthority => {
+                require!(
+                    self.mint
+                        .supply
+                        .checked_add(amount)
+                        .is_some_and(|supply| supply <= self.mining_pool_pda.supply_cap),
+                    MorErrorCodes::CapReached
+                );
+
+                let pool_key = self.mining_pool_pda.key();
+                let signer_seeds: [&[&[u8]]; 1] = [&[
+                    MINT_AUTHORITY_SEED,
+                    pool_key.as_ref(),
+                    &[self.mining_pool_pda.mint_authority_bump],
+                ]];
+
+                mint_to_checked(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        MintToChecked {
+                            mint: self.mint.to_account_info(),
+                            to: self.recipient_ata.to_account_info(),
+                            authority: self.mint_authority.to_account_info(),
+                        },
+                        &signer_seeds,
+                    ),
+                    amount,
+                    self.mint.decimals,
+                )?;
+            }
+        }
+        reward_state.maker_rewards = 0;
+
+        Ok(())
+    }
+}
+
+pub fn claim_commission_handler(ctx: Context<ClaimCommission>) -> Result<()> {
+    ctx.accounts.claim_commission()?;
+
+    Ok(())
+}
@@ -1,15 +1,26 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+    associated_token::{
+        create_idempotent, get_associated_token_address_with_program_id, AssociatedToken, Create,
+    },
+    token_interface::{
+        mint_to_checked, transfer_checked, Mint, MintToChecked, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
 };
 
 use crate::{
     helpers::{
-        constants::{MINER_SEED, MINING_POOL_SEED},
+        constants::{
+            MINER_SEED, MINING_POOL_SEED, MINT_AUTHORITY_SEED, MULTIPLIER_BASE_BPS,
+            PROTOCOL_CONFIG_SEED, VESTING_SEED,
+        },
         errors::MorErrorCodes,
+        utils::{linear_vested, vesting_start},
+    },
+    states::{
+        MinerAccountPoolPda, MiningPoolPda, PoolMode, ProtocolConfig, VestingConfig, VestingEscrow,
     },
-    states::{MinerAccountPoolPda, MiningPoolPda},
 };
 
 #[derive(Accounts)]
@@ -39,18 +50,67 @@ pub struct ClaimRewards<'info> {
       )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(mut, address = mining_pool_pda.mint)]
     pub mint: InterfaceAccount<'info, Mint>,
 
+    /// CHECK: Mint authority of MintAuthority pools, only signs
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mining_pool_pda.key().as_ref()],
+        bump = mining_pool_pda.mint_authority_bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the recipient ATA. It must be the miner's beneficiary
+    /// when one is set, as checked in claim_rewards.
+    pub beneficiary: UncheckedAccount<'info>,
+
     #[account(
         init_if_needed,
         payer = authority,
         associated_token::mint = mint,
-        associated_token::authority = authority,
+        associated_token::authority = beneficiary,
         associated_token::token_program = token_program
     )]
     pub recipient_ata: InterfaceAccount<'info, TokenAccount>,
 
+    /// Where claims of vesting pools are locked, left out for other pools
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = VestingEscrow::INIT_SPACE + VestingEscrow::DISCRIMINATOR.len(),
+        seeds = [VESTING_SEED, miner.key().as_ref()],
+        bump,
+    )]
+    pub vesting_escrow: Option<Account<'info, VestingEscrow>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Protocol treasury, only owns the ATA collecting the protocol fee
+    #[account(address = protocol_config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
     /// Programs
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
@@ -58,45 +118,280 @@ pub struct ClaimRewards<'info> {
 }
 
 impl<'info> ClaimRewards<'info> {
-    fn claim_rewards(&mut self) -> Result<()> {
-        let miner = &mut self.miner;
+    /// # Pay `amount` of the miner's rewards, or all of them, and every bonus reward
+    /// `bonus_accounts` holds the bonus mint, its pool vault and the beneficiary's ATA
+    /// for each of the pool's bonus mints, in order. Without them only the pool's own
+    /// mint is paid. Only the pool's own mint vests, bonus rewards are paid right away.
+    fn claim_rewards(
+        &mut self,
+        amount: Option<u64>,
+        bonus_accounts: &[AccountInfo<'info>],
+        escrow_bump: Option<u8>,
+    ) -> Result<()> {
+        let miner = &self.miner;
 
         // Check if miner has rewards to claim
-        require!(miner.rewards > 0, MorErrorCodes::NoRewardsToClaim);
+        let bonus_due = !bonus_accounts.is_empty() && miner.bonus_rewards.iter().any(|r| *r > 0);
+        require!(
+            miner.rewards > 0 || bonus_due,
+            MorErrorCodes::NoRewardsToClaim
+        );
+        let amount = match amount {
+            Some(amount) => {
+                require!(
+                    amount > 0 && amount <= miner.rewards,
+                    MorErrorCodes::InvalidAmount
+                );
+                amount
+            }
+            None => miner.rewards,
+        };
+
+        // A persisted beneficiary routes every payout
+        require!(
+            miner.beneficiary == Pubkey::default() || self.beneficiary.key() == miner.beneficiary,
+            MorErrorCodes::InvalidBeneficiary
+        );
+
+        if amount > 0 {
+            let paid = self.pay_rewards(amount)?;
+            if let Some(vesting) = self.mining_pool_pda.vesting {
+                self.lock_in_escrow(paid, vesting, escrow_bump)?;
+            }
+        }
+        if !bonus_accounts.is_empty() {
+            self.pay_bonus_rewards(bonus_accounts)?;
+        }
+
+        Ok(())
+    }
+
+    /// # Pay `amount` of the miner's rewards, less the protocol fee
+    /// Vesting pools pay into the miner's vesting escrow instead of the beneficiary.
+    /// Returns what the miner received.
+    fn pay_rewards(&mut self, amount: u64) -> Result<u64> {
+        let to = match self.mining_pool_pda.vesting {
+            Some(_) => self
+                .escrow_vault
+                .as_ref()
+                .ok_or(MorErrorCodes::VestingEscrowRequired)?
+                .to_account_info(),
+            None => self.recipient_ata.to_account_info(),
+        };
+
+        // The protocol's share of every claim goes to its treasury
+        let fee = (amount as u128 * self.protocol_config.protocol_fee_bps as u128
+            / MULTIPLIER_BASE_BPS as u128) as u64;
+        if fee > 0 {
+            self.pay_out(self.treasury_ata.to_account_info(), fee)?;
+        }
+        self.pay_out(to, amount - fee)?;
+        self.miner.rewards -= amount;
+        self.miner.stats.total_claimed = self.miner.stats.total_claimed.saturating_add(amount);
+
+        Ok(amount - fee)
+    }
+
+    /// # Send `amount` of the pool's own mint to `to`, from the vault or freshly minted
+    fn pay_out(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        match self.mining_pool_pda.mode {
+            PoolMode::Vault => {
+                let pool_maker_key = self.mining_pool_pda.pool_maker.key();
+                let mint_key = self.mining_pool_pda.mint.key();
+                // Create the signer seeds for the Vault
+                let signer_seeds: [&[&[u8]]; 1] = [&[
+                    MINING_POOL_SEED,
+                    pool_maker_key.as_ref(),
+                    mint_key.as_ref(),
+                    &[self.mining_pool_pda.bump],
+                ]];
+
+                // Transfer Token A (Vault -> Taker)
+                transfer_checked(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        TransferChecked {
+                            from: self.vault.to_account_info(),
+                            to,
+                            mint: self.mint.to_account_info(),
+                            authority: self.mining_pool_pda.to_account_info(),
+                        },
+                        &signer_seeds,
+                    ),
+                    amount,
+                    self.mint.decimals,
+                )?;
+            }
+            PoolMode::MintAuthority => {
+                // The supply cap holds whatever was credited
+                require!(
+                    self.mint
+                        .supply
+                        .checked_add(amount)
+                        .is_some_and(|supply| supply <= self.mining_pool_pda.supply_cap),
+                    MorErrorCodes::CapReached
+                );
 
+                let pool_key = self.mining_pool_pda.key();
+                let signer_seeds: [&[&[u8]]; 1] = [&[
+                    MINT_AUTHORITY_SEED,
+                    pool_key.as_ref(),
+                    &[self.mining_pool_pda.mint_authority_bump],
+                ]];
+
+                // Mint the reward straight to the beneficiary
+                mint_to_checked(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        MintToChecked {
+                            mint: self.mint.to_account_info(),
+                            to,
+                            authority: self.mint_authority.to_account_info(),
+                        },
+                        &signer_seeds,
+                    ),
+                    amount,
+                    self.mint.decimals,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// # Lock `amount` just paid into the escrow in the miner's vesting schedule
+    fn lock_in_escrow(
+        &mut self,
+        amount: u64,
+        vesting: VestingConfig,
+        escrow_bump: Option<u8>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let miner_key = self.miner.key();
+        let mint_key = self.mint.key();
+        let escrow = self
+            .vesting_escrow
+            .as_mut()
+            .ok_or(MorErrorCodes::VestingEscrowRequired)?;
+
+        // A new or fully released escrow starts a new schedule, cliff included
+        if escrow.deposited == escrow.released {
+            escrow.set_inner(VestingEscrow {
+                miner: miner_key,
+                mint: mint_key,
+                deposited: 0,
+                released: 0,
+                start_timestamp: now,
+                cliff_timestamp: now.saturating_add(vesting.cliff_seconds),
+                duration_seconds: vesting.duration_seconds,
+                bump: escrow_bump.ok_or(MorErrorCodes::VestingEscrowRequired)?,
+            });
+        }
+
+        // What already vested stays vested, the new tokens vest from now on
+        let vested = linear_vested(
+            escrow.deposited,
+            escrow.start_timestamp,
+            escrow.duration_seconds,
+            now,
+        );
+        let deposited = escrow
+            .deposited
+            .checked_add(amount)
+            .ok_or(MorErrorCodes::InvalidAmount)?;
+        escrow.start_timestamp = vesting_start(vested, deposited, vesting.duration_seconds, now);
+        escrow.duration_seconds = vesting.duration_seconds;
+        escrow.deposited = deposited;
+
+        Ok(())
+    }
+
+    /// # Pay every bonus reward from its vault
+    fn pay_bonus_rewards(&mut self, bonus_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let bonus_mints = &self.mining_pool_pda.bonus_mints;
+        require!(
+            bonus_accounts.len() == bonus_mints.len() * 3,
+            MorErrorCodes::InvalidBonusMint
+        );
+
+        let pool_key = self.mining_pool_pda.key();
         let pool_maker_key = self.mining_pool_pda.pool_maker.key();
         let mint_key = self.mining_pool_pda.mint.key();
-        // Create the signer seeds for the Vault
         let signer_seeds: [&[&[u8]]; 1] = [&[
             MINING_POOL_SEED,
             pool_maker_key.as_ref(),
             mint_key.as_ref(),
             &[self.mining_pool_pda.bump],
         ]];
+        let token_program = self.token_program.key();
+
+        for (index, (bonus, accounts)) in bonus_mints
+            .iter()
+            .zip(bonus_accounts.chunks_exact(3))
+            .enumerate()
+        {
+            let amount = self.miner.bonus_rewards.get(index).copied().unwrap_or(0);
+            if amount == 0 {
+                continue;
+            }
+            let (mint, vault, recipient_ata) = (&accounts[0], &accounts[1], &accounts[2]);
+            require!(
+                mint.key() == bonus.mint
+                    && vault.key()
+                        == get_associated_token_address_with_program_id(
+                            &pool_key,
+                            &bonus.mint,
+                            &token_program,
+                        )
+                    && recipient_ata.key()
+                        == get_associated_token_address_with_program_id(
+                            &self.beneficiary.key(),
+                            &bonus.mint,
+                            &token_program,
+                        ),
+                MorErrorCodes::InvalidBonusMint
+            );
 
-        // Transfer Token A (Vault -> Taker)
-        transfer_checked(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                TransferChecked {
-                    from: self.vault.to_account_info(),
-                    to: self.recipient_ata.to_account_info(),
-                    mint: self.mint.to_account_info(),
-                    authority: self.mining_pool_pda.to_account_info(),
+            // The beneficiary may not hold this token yet
+            create_idempotent(CpiContext::new(
+                self.associated_token_program.to_account_info(),
+                Create {
+                    payer: self.authority.to_account_info(),
+                    associated_token: recipient_ata.clone(),
+                    authority: self.beneficiary.to_account_info(),
+                    mint: mint.clone(),
+                    system_program: self.system_program.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
                 },
-                &signer_seeds,
-            ),
-            miner.rewards,
-            self.mint.decimals,
-        )?;
-        miner.rewards = 0;
+            ))?;
+
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TransferChecked {
+                        from: vault.clone(),
+                        to: recipient_ata.clone(),
+                        mint: mint.clone(),
+                        authority: self.mining_pool_pda.to_account_info(),
+                    },
+                    &signer_seeds,
+                ),
+                amount,
+                bonus.decimals,
+            )?;
+            self.miner.bonus_rewards[index] = 0;
+        }
 
         Ok(())
     }
 }
 
-pub fn claim_rewards_handler(ctx: Context<ClaimRewards>) -> Result<()> {
-    ctx.accounts.claim_rewards()?;
+pub fn claim_rewards_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimRewards<'info>>,
+    amount: Option<u64>,
+) -> Result<()> {
+    ctx.accounts
+        .claim_rewards(amount, ctx.remaining_accounts, ctx.bumps.vesting_escrow)?;
 
     Ok(())
 }
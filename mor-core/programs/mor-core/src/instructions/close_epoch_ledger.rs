@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::constants::{EPOCH_SEED, MAX_EPOCH_OFFSET, MINING_POOL_SEED},
+    helpers::errors::MorErrorCodes,
+    states::{EpochLedger, MiningPoolPda},
+};
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct CloseEpochLedger<'info> {
+    /// CHECK: Only receives the ledger's rent, and must be who paid it
+    #[account(mut, address = epoch_ledger.payer)]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+    )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [EPOCH_SEED, mining_pool_pda.key().as_ref(), &epoch.to_le_bytes()],
+        bump = epoch_ledger.bump,
+    )]
+    pub epoch_ledger: Account<'info, EpochLedger>,
+}
+
+impl<'info> CloseEpochLedger<'info> {
+    /// # Close a finished epoch's ledger, returning its rent to whoever paid it
+    /// Permissionless on the base layer, so undelegated first, once a settlement
+    /// after the epoch stopped taking solutions folded the shards' last stats of it in.
+    fn close_epoch_ledger(&mut self, epoch: u64) -> Result<()> {
+        require_gt!(
+            self.epoch_ledger.settled_epoch,
+            epoch.saturating_add(MAX_EPOCH_OFFSET),
+            MorErrorCodes::EpochLedgerNotSettled
+        );
+
+        msg!("closed ledger of epoch {}", epoch);
+        Ok(())
+    }
+}
+
+pub fn close_epoch_ledger_handler(ctx: Context<CloseEpochLedger>, epoch: u64) -> Result<()> {
+    ctx.accounts.close_epoch_ledger(epoch)?;
+
+    Ok(())
+}
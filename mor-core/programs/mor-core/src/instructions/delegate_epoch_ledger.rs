@@ -0,0 +1,62 @@
+use crate::{
+    helpers::constants::{EPOCH_SEED, MINING_POOL_REWARD_SEED, MINING_POOL_SEED},
+    helpers::errors::MorErrorCodes,
+    states::MiningPoolPda,
+};
+use anchor_lang::prelude::*;
+use ephemeral_rollups_sdk::anchor::delegate;
+use ephemeral_rollups_sdk::consts::DELEGATION_PROGRAM_ID;
+use ephemeral_rollups_sdk::cpi::DelegateConfig;
+
+#[delegate]
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct DelegateEpochLedger<'info> {
+    pub payer: Signer<'info>,
+    /// CHECK The pda to delegate
+    #[account(
+        mut,
+        del,
+        seeds = [EPOCH_SEED, mining_pool_pda.key().as_ref(), &epoch.to_le_bytes()],
+        bump,
+    )]
+    pub epoch_ledger: AccountInfo<'info>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    /// CHECK: Only its owner is read, to tell whether the reward pool is delegated
+    #[account(
+        seeds = [MINING_POOL_REWARD_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump,
+    )]
+    pub mining_pool_reward_state: UncheckedAccount<'info>,
+}
+
+impl<'info> DelegateEpochLedger<'info> {
+    /// # Delegate a pool's epoch ledger to the ER, next to its reward pool
+    /// Permissionless, but only while the reward state is delegated, so miners
+    /// of a pool mining on the base layer keep their ledgers there.
+    fn delegate_ledger(&mut self, epoch: u64) -> Result<()> {
+        require_keys_eq!(
+            *self.mining_pool_reward_state.owner,
+            DELEGATION_PROGRAM_ID,
+            MorErrorCodes::RewardPoolNotDelegated
+        );
+
+        let mining_pool_pda_key = self.mining_pool_pda.key();
+        let epoch_bytes = epoch.to_le_bytes();
+        let epoch_ledger_seeds = [EPOCH_SEED, mining_pool_pda_key.as_ref(), &epoch_bytes];
+        self.delegate_epoch_ledger(&self.payer, &epoch_ledger_seeds, DelegateConfig::default())?;
+        Ok(())
+    }
+}
+
+pub fn delegate_epoch_ledger_handler(ctx: Context<DelegateEpochLedger>, epoch: u64) -> Result<()> {
+    ctx.accounts.delegate_ledger(epoch)?;
+
+    Ok(())
+}
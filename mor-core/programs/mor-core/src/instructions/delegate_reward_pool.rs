@@ -11,7 +11,7 @@ use ephemeral_rollups_sdk::cpi::DelegateConfig;
 #[derive(Accounts)]
 #[instruction()]
 pub struct DelegateRewardPool<'info> {
-    pub payer: Signer<'info>, // pool maker
+    pub payer: Signer<'info>, // pool admin
     /// CHECK The pda to delegate
     #[account(
         mut,
@@ -30,18 +30,18 @@ pub struct DelegateRewardPool<'info> {
 
 impl<'info> DelegateRewardPool<'info> {
     fn delegate_reward_pool(&mut self) -> Result<()> {
-        // Only the mining pool's pool_maker can delegate the reward pool
+        // Only the mining pool's admin can delegate the reward pool
         require!(
-            self.payer.key() == self.mining_pool_pda.pool_maker,
+            self.payer.key() == self.mining_pool_pda.admin,
             MorErrorCodes::InvalidAuthority
         );
 
         let mining_pool_pda_key = self.mining_pool_pda.key();
         // delegate the session account to the ER
-        let payer_key = self.payer.key();
+        let pool_maker_key = self.mining_pool_pda.pool_maker.key();
         let mining_pool_reward_state_seeds = [
             MINING_POOL_REWARD_SEED,
-            payer_key.as_ref(),
+            pool_maker_key.as_ref(),
             mining_pool_pda_key.as_ref(),
         ];
         self.delegate_mining_pool_reward_state(
@@ -0,0 +1,51 @@
+use crate::{
+    helpers::constants::{MINING_POOL_SEED, REWARD_SHARD_SEED},
+    helpers::errors::MorErrorCodes,
+    states::MiningPoolPda,
+};
+use anchor_lang::prelude::*;
+use ephemeral_rollups_sdk::anchor::delegate;
+use ephemeral_rollups_sdk::cpi::DelegateConfig;
+
+#[delegate]
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct DelegateRewardShard<'info> {
+    pub payer: Signer<'info>, // pool admin
+    /// CHECK The pda to delegate
+    #[account(
+        mut,
+        del,
+        seeds = [REWARD_SHARD_SEED, mining_pool_pda.key().as_ref(), &[index]],
+        bump,
+    )]
+    pub reward_shard: AccountInfo<'info>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+}
+
+impl<'info> DelegateRewardShard<'info> {
+    /// # Delegate one of the pool's reward shards to the ER, next to its reward state
+    fn delegate_shard(&mut self, index: u8) -> Result<()> {
+        // Only the mining pool's admin can delegate the reward shards
+        require!(
+            self.payer.key() == self.mining_pool_pda.admin,
+            MorErrorCodes::InvalidAuthority
+        );
+
+        let mining_pool_pda_key = self.mining_pool_pda.key();
+        let reward_shard_seeds = [REWARD_SHARD_SEED, mining_pool_pda_key.as_ref(), &[index]];
+        self.delegate_reward_shard(&self.payer, &reward_shard_seeds, DelegateConfig::default())?;
+        Ok(())
+    }
+}
+
+pub fn delegate_reward_shard_handler(ctx: Context<DelegateRewardShard>, index: u8) -> Result<()> {
+    ctx.accounts.delegate_shard(index)?;
+
+    Ok(())
+}
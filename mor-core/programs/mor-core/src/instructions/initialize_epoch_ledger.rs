@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::constants::{EPOCH_SEED, EPOCH_SLOT_LENGTH, MINING_POOL_SEED},
+    instructions::submit_solution::require_epoch_in_range,
+    states::{EpochLedger, MiningPoolPda},
+};
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct InitializeEpochLedger<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+    )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = EpochLedger::INIT_SPACE + EpochLedger::DISCRIMINATOR.len(),
+        seeds = [EPOCH_SEED, mining_pool_pda.key().as_ref(), &epoch.to_le_bytes()],
+        bump,
+    )]
+    pub epoch_ledger: Account<'info, EpochLedger>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeEpochLedger<'info> {
+    /// # Create a pool's ledger for an epoch solutions are accepted for
+    /// Anyone can pay for it, and gets the rent back from close_epoch_ledger. The
+    /// settle-epoch crank creates ledgers ahead of their epoch so they can be
+    /// delegated, since the ER can't create accounts.
+    fn initialize_epoch_ledger(&mut self, epoch: u64, bump: u8) -> Result<()> {
+        let current_epoch = Clock::get()?.slot / EPOCH_SLOT_LENGTH;
+        require_epoch_in_range(epoch, current_epoch)?;
+
+        self.epoch_ledger.set_inner(EpochLedger {
+            pool: self.mining_pool_pda.key(),
+            epoch,
+            submissions: 0,
+            total_rewards: 0,
+            best_difficulty: 0,
+            best_miner: Pubkey::default(),
+            payer: self.payer.key(),
+            settled_epoch: 0,
+            bump,
+        });
+        Ok(())
+    }
+}
+
+pub fn initialize_epoch_ledger_handler(
+    ctx: Context<InitializeEpochLedger>,
+    epoch: u64,
+) -> Result<()> {
+    ctx.accounts
+        .initialize_epoch_ledger(epoch, ctx.bumps.epoch_ledger)?;
+
+    Ok(())
+}
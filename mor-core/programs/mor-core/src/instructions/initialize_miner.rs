@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    helpers::constants::{MINER_SEED, MINING_POOL_SEED},
-    states::{MinerAccountPoolPda, MiningPoolPda},
+    helpers::{
+        constants::{MINER_ACCOUNT_VERSION, MINER_SEED, MINING_POOL_SEED, MULTIPLIER_BASE_BPS},
+        errors::MorErrorCodes,
+        utils::verify_allowlist_proof,
+    },
+    states::{MinerAccountPoolPda, MinerStats, MiningPoolPda},
 };
 
 #[derive(Accounts)]
@@ -31,25 +35,38 @@ pub struct InitializeMiner<'info> {
 }
 
 impl<'info> InitializeMiner<'info> {
-    fn initialize_miner(&mut self, bumps: &InitializeMinerBumps) -> Result<()> {
+    fn initialize_miner(&mut self, bumps: &InitializeMinerBumps, proof: &[[u8; 32]]) -> Result<()> {
+        // Private pools only take miners whose authority is on the allowlist
+        if let Some(root) = &self.mining_pool_pda.allowlist_root {
+            require!(
+                verify_allowlist_proof(root, &self.authority.key(), proof),
+                MorErrorCodes::NotAllowlisted
+            );
+        }
+
         let miner = &mut self.miner;
 
         // Initialize miner account with default values
         miner.authority = self.authority.key();
         miner.last_epoch_mined = u64::MAX; // means "never mined"
         miner.rewards = 0;
-        miner.multiplier = 1; // Start with base multiplier
+        miner.multiplier = MULTIPLIER_BASE_BPS; // Start with base multiplier
         miner.staked_amount = 0;
         miner.last_staked_timestamp = 0;
         miner.difficulty = 0;
         miner.pool = self.mining_pool_pda.key();
         miner.bump = bumps.miner;
+        miner.version = MINER_ACCOUNT_VERSION;
+        miner.signer = Pubkey::default(); // only the authority submits until set_miner_signer
+        miner.beneficiary = Pubkey::default(); // claims go where the authority chooses
+        miner.bonus_rewards = Vec::new(); // credited once the pool adds a bonus mint
+        miner.stats = MinerStats::default();
         Ok(())
     }
 }
 
-pub fn initialize_miner_handler(ctx: Context<InitializeMiner>) -> Result<()> {
-    ctx.accounts.initialize_miner(&ctx.bumps)?;
+pub fn initialize_miner_handler(ctx: Context<InitializeMiner>, proof: Vec<[u8; 32]>) -> Result<()> {
+    ctx.accounts.initialize_miner(&ctx.bumps, &proof)?;
 
     Ok(())
 }
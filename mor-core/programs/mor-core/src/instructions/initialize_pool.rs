@@ -1,13 +1,22 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+    token_interface::{
+        set_authority, spl_token_2022::instruction::AuthorityType, transfer_checked, Mint,
+        SetAuthority, TokenAccount, TokenInterface, TransferChecked,
+    },
 };
 
 use crate::{
-    helpers::constants::{MINING_POOL_REWARD_SEED, MINING_POOL_SEED},
+    helpers::constants::{
+        DEFAULT_MULTIPLIER_TIERS, MAX_COMMISSION_BPS, MINING_POOL_REWARD_SEED, MINING_POOL_SEED,
+        MINT_AUTHORITY_SEED, PROTOCOL_CONFIG_SEED,
+    },
     helpers::errors::MorErrorCodes,
-    states::{MiningPoolPda, MiningPoolRewardState},
+    states::{MiningPoolPda, MiningPoolRewardState, PoolMode, PowAlgorithm, ProtocolConfig},
 };
 
 #[derive(Accounts)]
@@ -34,8 +43,16 @@ pub struct InitializePool<'info> {
     )]
     pub mining_pool_reward_state: Account<'info, MiningPoolRewardState>,
 
+    /// CHECK: PDA that becomes the mint authority of MintAuthority pools
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mining_pool_pda.key().as_ref()],
+        bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
     /// Token Accounts
     #[account(
+        mut,
         mint::token_program = token_program
     )]
     pub mint: InterfaceAccount<'info, Mint>,
@@ -55,6 +72,16 @@ pub struct InitializePool<'info> {
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Protocol treasury, receives the pool creation fee
+    #[account(mut, address = protocol_config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
     /// Programs
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
@@ -63,19 +90,52 @@ pub struct InitializePool<'info> {
 
 impl<'info> InitializePool<'info> {
     /// # Create the Mining Pool
-    fn create_mining_pool(&mut self, amount: u64, bump: u8) -> Result<()> {
+    fn create_mining_pool(
+        &mut self,
+        amount: u64,
+        pow_algorithm: PowAlgorithm,
+        commission_bps: u16,
+        mode: PoolMode,
+        bumps: &InitializePoolBumps,
+    ) -> Result<()> {
+        // Minted rewards may take the supply up to its current amount plus the pool amount
+        let supply_cap = match mode {
+            PoolMode::Vault => 0,
+            PoolMode::MintAuthority => self
+                .mint
+                .supply
+                .checked_add(amount)
+                .ok_or(MorErrorCodes::InvalidAmount)?,
+        };
+
         self.mining_pool_pda.set_inner(MiningPoolPda {
             pool_maker: self.pool_maker.key(),
             mint: self.mint.key(),
             amount,
-            bump,
+            bump: bumps.mining_pool_pda,
+            pow_algorithm,
+            mint_decimals: self.mint.decimals,
+            multiplier_tiers: DEFAULT_MULTIPLIER_TIERS.to_vec(),
+            commission_bps,
+            mode,
+            supply_cap,
+            mint_authority_bump: bumps.mint_authority,
+            bonus_mints: Vec::new(),
+            vesting: None,
+            allowlist_root: None,
+            min_stake: 0,
+            admin: self.pool_maker.key(),
+            pending_admin: Pubkey::default(),
         });
 
         self.mining_pool_reward_state
             .set_inner(MiningPoolRewardState {
                 pool_pda: self.mining_pool_pda.key(),
                 amount,
-                bump,
+                bump: bumps.mining_pool_reward_state,
+                maker_rewards: 0,
+                bonus_amounts: Vec::new(),
+                settled_epoch: 0,
             });
 
         Ok(())
@@ -99,18 +159,72 @@ impl<'info> InitializePool<'info> {
 
         Ok(())
     }
+
+    /// # Pay the protocol's pool creation fee to its treasury
+    fn pay_creation_fee(&mut self) -> Result<()> {
+        let fee = self.protocol_config.pool_creation_fee;
+        if fee == 0 {
+            return Ok(());
+        }
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.pool_maker.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+
+        Ok(())
+    }
+
+    /// # Hand the mint authority over to the program
+    fn take_mint_authority(&mut self) -> Result<()> {
+        set_authority(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: self.pool_maker.to_account_info(),
+                    account_or_mint: self.mint.to_account_info(),
+                },
+            ),
+            AuthorityType::MintTokens,
+            Some(self.mint_authority.key()),
+        )?;
+
+        Ok(())
+    }
 }
 
-pub fn initialize_pool_handler(ctx: Context<InitializePool>, amount: u64) -> Result<()> {
+pub fn initialize_pool_handler(
+    ctx: Context<InitializePool>,
+    amount: u64,
+    pow_algorithm: PowAlgorithm,
+    commission_bps: u16,
+    mode: PoolMode,
+) -> Result<()> {
     // Validate the amount
     require_gte!(amount, 0, MorErrorCodes::InvalidAmount);
+    require_gte!(
+        MAX_COMMISSION_BPS,
+        commission_bps,
+        MorErrorCodes::CommissionTooHigh
+    );
 
     // Save the Mining Pool Data
     ctx.accounts
-        .create_mining_pool(amount, ctx.bumps.mining_pool_pda)?;
+        .create_mining_pool(amount, pow_algorithm, commission_bps, mode, &ctx.bumps)?;
+
+    ctx.accounts.pay_creation_fee()?;
 
-    // Deposit Tokens
-    ctx.accounts.deposit_tokens(amount)?;
+    match mode {
+        // Deposit Tokens
+        PoolMode::Vault => ctx.accounts.deposit_tokens(amount)?,
+        // Rewards are minted on claim instead
+        PoolMode::MintAuthority => ctx.accounts.take_mint_authority()?,
+    }
 
     Ok(())
 }
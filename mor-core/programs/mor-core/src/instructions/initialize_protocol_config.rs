@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{
+        constants::{MAX_PROTOCOL_FEE_BPS, PROTOCOL_CONFIG_SEED},
+        errors::MorErrorCodes,
+    },
+    program::MorCore,
+    states::ProtocolConfig,
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ProtocolConfig::INIT_SPACE + ProtocolConfig::DISCRIMINATOR.len(),
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Only the program's upgrade authority can create the config, so no one
+    /// can claim the protocol before its operator does
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, MorCore>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ MorErrorCodes::InvalidAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeProtocolConfig<'info> {
+    /// # Create the protocol config, with the signer as protocol admin
+    fn initialize_protocol_config(
+        &mut self,
+        treasury: Pubkey,
+        pool_creation_fee: u64,
+        protocol_fee_bps: u16,
+        bump: u8,
+    ) -> Result<()> {
+        require_gte!(
+            MAX_PROTOCOL_FEE_BPS,
+            protocol_fee_bps,
+            MorErrorCodes::ProtocolFeeTooHigh
+        );
+
+        self.protocol_config.set_inner(ProtocolConfig {
+            admin: self.admin.key(),
+            treasury,
+            pool_creation_fee,
+            protocol_fee_bps,
+            bump,
+        });
+        Ok(())
+    }
+}
+
+pub fn initialize_protocol_config_handler(
+    ctx: Context<InitializeProtocolConfig>,
+    treasury: Pubkey,
+    pool_creation_fee: u64,
+    protocol_fee_bps: u16,
+) -> Result<()> {
+    ctx.accounts.initialize_protocol_config(
+        treasury,
+        pool_creation_fee,
+        protocol_fee_bps,
+        ctx.bumps.protocol_config,
+    )?;
+
+    Ok(())
+}
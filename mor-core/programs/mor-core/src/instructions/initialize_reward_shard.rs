@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{
+        constants::{MINING_POOL_REWARD_SEED, MINING_POOL_SEED, REWARD_SHARDS, REWARD_SHARD_SEED},
+        errors::MorErrorCodes,
+        utils::shard_share,
+    },
+    states::{MiningPoolPda, MiningPoolRewardState, RewardShard},
+};
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct InitializeRewardShard<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+    )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    /// Read for the shard's first budget, so it must be on the base layer
+    #[account(
+        seeds = [MINING_POOL_REWARD_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump = mining_pool_reward_state.bump,
+    )]
+    pub mining_pool_reward_state: Account<'info, MiningPoolRewardState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RewardShard::INIT_SPACE + RewardShard::DISCRIMINATOR.len(),
+        seeds = [REWARD_SHARD_SEED, mining_pool_pda.key().as_ref(), &[index]],
+        bump,
+    )]
+    pub reward_shard: Account<'info, RewardShard>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeRewardShard<'info> {
+    /// # Create one of the pool's reward shards
+    /// Anyone can pay for it. It starts with its share of the pool's remaining
+    /// rewards, as settle_epoch would split them, so it can credit solutions
+    /// before the first settle.
+    fn initialize_reward_shard(&mut self, index: u8, bump: u8) -> Result<()> {
+        require_gt!(REWARD_SHARDS, index, MorErrorCodes::InvalidRewardShard);
+
+        let reward_state = &self.mining_pool_reward_state;
+        self.reward_shard.set_inner(RewardShard {
+            pool: self.mining_pool_pda.key(),
+            index,
+            budget: shard_share(reward_state.amount, index),
+            credited: 0,
+            commission: 0,
+            bonus_budgets: reward_state
+                .bonus_amounts
+                .iter()
+                .map(|remaining| shard_share(*remaining, index))
+                .collect(),
+            bonus_credited: vec![0; reward_state.bonus_amounts.len()],
+            epoch_stats: Default::default(),
+            bump,
+        });
+        Ok(())
+    }
+}
+
+pub fn initialize_reward_shard_handler(
+    ctx: Context<InitializeRewardShard>,
+    index: u8,
+) -> Result<()> {
+    ctx.accounts
+        .initialize_reward_shard(index, ctx.bumps.reward_shard)?;
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+use crate::{
+    helpers::{
+        constants::{MINER_ACCOUNT_VERSION, MINER_SEED, MINING_POOL_SEED, MULTIPLIER_BASE_BPS},
+        errors::MorErrorCodes,
+    },
+    states::{MinerAccountPoolPda, MinerStats, MiningPoolPda},
+};
+
+/// Size of a version 1 miner account, discriminator included
+const MINER_V1_LEN: usize = 1 + 32 + 8 + 8 + 1 + 8 + 1 + 32 + 8 + 1 + 1;
+
+/// Miner layout of versions 0 and 1, with a whole u8 multiplier.
+/// Version 0 accounts have no version byte, resizing reads it as 0.
+#[derive(AnchorDeserialize)]
+struct MinerAccountV1 {
+    authority: Pubkey,
+    last_epoch_mined: u64,
+    rewards: u64,
+    multiplier: u8,
+    staked_amount: u64,
+    difficulty: u8,
+    pool: Pubkey,
+    last_staked_timestamp: i64,
+    bump: u8,
+    version: u8,
+}
+
+impl From<MinerAccountV1> for MinerAccountPoolPda {
+    fn from(miner: MinerAccountV1) -> Self {
+        MinerAccountPoolPda {
+            authority: miner.authority,
+            last_epoch_mined: miner.last_epoch_mined,
+            rewards: miner.rewards,
+            multiplier: miner.multiplier as u32 * MULTIPLIER_BASE_BPS,
+            staked_amount: miner.staked_amount,
+            difficulty: miner.difficulty,
+            pool: miner.pool,
+            last_staked_timestamp: miner.last_staked_timestamp,
+            bump: miner.bump,
+            version: miner.version,
+            signer: Pubkey::default(),
+            beneficiary: Pubkey::default(),
+            bonus_rewards: Vec::new(),
+            stats: MinerStats::default(),
+        }
+    }
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct MigrateMiner<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: May still have an older layout that Account<MinerAccountPoolPda> can't load.
+    /// The seeds, owner and discriminator are checked before it is resized and rewritten.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [MINER_SEED, authority.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump
+    )]
+    pub miner: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateMiner<'info> {
+    /// # Grow the miner account to the current layout
+    fn resize_miner(&mut self) -> Result<()> {
+        let miner_info = self.miner.to_account_info();
+        require!(
+            miner_info
+                .try_borrow_data()?
+                .starts_with(MinerAccountPoolPda::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        let space = MinerAccountPoolPda::INIT_SPACE + MinerAccountPoolPda::DISCRIMINATOR.len();
+        if miner_info.data_len() >= space {
+            return Ok(());
+        }
+
+        // Top up rent for the larger account
+        let rent = Rent::get()?.minimum_balance(space);
+        let lamports = rent.saturating_sub(miner_info.lamports());
+        if lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.authority.to_account_info(),
+                        to: miner_info.clone(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+
+        // New fields are zero-filled, which reads as version 0
+        miner_info.resize(space)?;
+        Ok(())
+    }
+
+    /// # Convert the miner's fields to the current version
+    /// `data_len` is the account size before resizing, which tells the layouts apart.
+    /// Later layouts only append fields, which resizing zero-fills.
+    fn migrate_miner(&mut self, data_len: usize) -> Result<()> {
+        let miner_info = self.miner.to_account_info();
+        let mut miner = if data_len <= MINER_V1_LEN {
+            let data = miner_info.try_borrow_data()?;
+            MinerAccountV1::deserialize(&mut &data[MinerAccountPoolPda::DISCRIMINATOR.len()..])?
+                .into()
+        } else {
+            MinerAccountPoolPda::try_deserialize(&mut &miner_info.try_borrow_data()?[..])?
+        };
+        require!(
+            miner.version < MINER_ACCOUNT_VERSION,
+            MorErrorCodes::AlreadyMigrated
+        );
+
+        if miner.version == 0 {
+            // Difficulty was counted in leading zero bytes, it is now leading zero bits
+            miner.difficulty = miner.difficulty.saturating_mul(8);
+        }
+        if miner.version < 3 {
+            // Only the authority submits until it sets a hot key
+            miner.signer = Pubkey::default();
+        }
+        if miner.version < 4 {
+            // Claims go where the authority chooses until it sets a beneficiary
+            miner.beneficiary = Pubkey::default();
+        }
+        if miner.version < 5 {
+            // Bonus rewards are only credited once the pool adds a bonus mint
+            miner.bonus_rewards = Vec::new();
+        }
+        if miner.version < 6 {
+            // Lifetime stats count from the migration on
+            miner.stats = MinerStats::default();
+        }
+        miner.version = MINER_ACCOUNT_VERSION;
+
+        miner.try_serialize(&mut &mut miner_info.try_borrow_mut_data()?[..])?;
+        Ok(())
+    }
+}
+
+pub fn migrate_miner_handler(ctx: Context<MigrateMiner>) -> Result<()> {
+    let data_len = ctx.accounts.miner.data_len();
+
+    ctx.accounts.resize_miner()?;
+
+    ctx.accounts.migrate_miner(data_len)?;
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    helpers::{
+        constants::{DEFAULT_MULTIPLIER_TIERS, MINING_POOL_SEED, MINT_AUTHORITY_SEED},
+        errors::MorErrorCodes,
+    },
+    states::{MiningPoolPda, PoolMode, PowAlgorithm},
+};
+
+/// Pool layout from before the pool settings, with only the pool's budget
+#[derive(AnchorDeserialize)]
+struct MiningPoolV0 {
+    pool_maker: Pubkey,
+    mint: Pubkey,
+    bump: u8,
+    amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct MigratePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: May still have an older layout that Account<MiningPoolPda> can't load.
+    /// The seeds, owner and discriminator are checked before it is resized and rewritten.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [MINING_POOL_SEED, pool_maker.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub mining_pool_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Only its key is used, to derive the pool's address
+    pub pool_maker: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Only its bump is stored, for MintAuthority pools
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mining_pool_pda.key().as_ref()],
+        bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigratePool<'info> {
+    /// # Grow the pool to the current layout
+    /// Anyone can pay for it, the rewrite only depends on the pool and its mint.
+    /// Pools must be migrated before their reward state and miners, whose
+    /// migrations load the pool.
+    fn resize_pool(&mut self) -> Result<()> {
+        let pool_info = self.mining_pool_pda.to_account_info();
+        require!(
+            pool_info
+                .try_borrow_data()?
+                .starts_with(MiningPoolPda::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        let space = MiningPoolPda::INIT_SPACE + MiningPoolPda::DISCRIMINATOR.len();
+        require!(pool_info.data_len() < space, MorErrorCodes::AlreadyMigrated);
+
+        // Top up rent for the larger account
+        let rent = Rent::get()?.minimum_balance(space);
+        let lamports = rent.saturating_sub(pool_info.lamports());
+        if lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: pool_info.clone(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+
+        pool_info.resize(space)?;
+        Ok(())
+    }
+
+    /// # Convert the pool's fields to the current layout
+    /// Older pools keep their budget and behave as before: SHA3 solutions, the
+    /// default multiplier table, no commission, claims paid from the vault, and
+    /// the pool maker as admin.
+    fn migrate_pool(&mut self, mint_authority_bump: u8) -> Result<()> {
+        let pool_info = self.mining_pool_pda.to_account_info();
+        let pool = {
+            let data = pool_info.try_borrow_data()?;
+            MiningPoolV0::deserialize(&mut &data[MiningPoolPda::DISCRIMINATOR.len()..])?
+        };
+
+        let pool = MiningPoolPda {
+            pool_maker: pool.pool_maker,
+            mint: pool.mint,
+            bump: pool.bump,
+            amount: pool.amount,
+            pow_algorithm: PowAlgorithm::Sha3,
+            mint_decimals: self.mint.decimals,
+            multiplier_tiers: DEFAULT_MULTIPLIER_TIERS.to_vec(),
+            commission_bps: 0,
+            mode: PoolMode::Vault,
+            supply_cap: 0,
+            mint_authority_bump,
+            bonus_mints: Vec::new(),
+            vesting: None,
+            allowlist_root: None,
+            min_stake: 0,
+            admin: pool.pool_maker,
+            pending_admin: Pubkey::default(),
+        };
+        pool.try_serialize(&mut &mut pool_info.try_borrow_mut_data()?[..])?;
+        Ok(())
+    }
+}
+
+pub fn migrate_pool_handler(ctx: Context<MigratePool>) -> Result<()> {
+    ctx.accounts.resize_pool()?;
+
+    ctx.accounts.migrate_pool(ctx.bumps.mint_authority)?;
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+use crate::{
+    helpers::{
+        constants::{MINING_POOL_REWARD_SEED, MINING_POOL_SEED},
+        errors::MorErrorCodes,
+    },
+    states::{MiningPoolPda, MiningPoolRewardState},
+};
+
+/// Size of a reward state from before the commission, discriminator included
+const REWARD_STATE_V0_LEN: usize = 1 + 32 + 8 + 1;
+
+/// Reward state layout from before the commission and bonus mints
+#[derive(AnchorDeserialize)]
+struct RewardStateV0 {
+    pool_pda: Pubkey,
+    amount: u64,
+    bump: u8,
+}
+
+impl From<RewardStateV0> for MiningPoolRewardState {
+    fn from(reward_state: RewardStateV0) -> Self {
+        MiningPoolRewardState {
+            pool_pda: reward_state.pool_pda,
+            amount: reward_state.amount,
+            bump: reward_state.bump,
+            maker_rewards: 0,
+            bonus_amounts: Vec::new(),
+            settled_epoch: 0,
+        }
+    }
+}
+
+/// Reward state layout from before the reward shards were settled
+#[derive(AnchorDeserialize)]
+struct RewardStateV1 {
+    pool_pda: Pubkey,
+    amount: u64,
+    bump: u8,
+    maker_rewards: u64,
+    bonus_amounts: Vec<u64>,
+}
+
+impl From<RewardStateV1> for MiningPoolRewardState {
+    fn from(reward_state: RewardStateV1) -> Self {
+        MiningPoolRewardState {
+            pool_pda: reward_state.pool_pda,
+            amount: reward_state.amount,
+            bump: reward_state.bump,
+            maker_rewards: reward_state.maker_rewards,
+            bonus_amounts: reward_state.bonus_amounts,
+            settled_epoch: 0,
+        }
+    }
+}
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct MigrateRewardState<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: May still have an older layout that Account<MiningPoolRewardState> can't load.
+    /// The seeds, owner and discriminator are checked before it is resized and rewritten.
+    /// The owner check also keeps a delegated reward state from being migrated.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [MINING_POOL_REWARD_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump
+    )]
+    pub mining_pool_reward_state: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+    )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateRewardState<'info> {
+    /// # Grow the reward state to the current layout
+    /// Anyone can pay for it, the rewrite only depends on the account itself.
+    fn resize_reward_state(&mut self) -> Result<()> {
+        let reward_state_info = self.mining_pool_reward_state.to_account_info();
+        require!(
+            reward_state_info
+                .try_borrow_data()?
+                .starts_with(MiningPoolRewardState::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        let space = MiningPoolRewardState::INIT_SPACE + MiningPoolRewardState::DISCRIMINATOR.len();
+        if reward_state_info.data_len() >= space {
+            return Ok(());
+        }
+
+        // Top up rent for the larger account
+        let rent = Rent::get()?.minimum_balance(space);
+        let lamports = rent.saturating_sub(reward_state_info.lamports());
+        if lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: reward_state_info.clone(),
+                    },
+                ),
+                lamports,
+            )?;
+        }
+
+        reward_state_info.resize(space)?;
+        Ok(())
+    }
+
+    /// # Convert the reward state's fields to the current layout
+    /// `data_len` is the account size before resizing, which tells the layouts apart.
+    /// Older layouts start unsettled, so the next settle_epoch splits the remaining
+    /// rewards across the shards. Older pools stored the pool's bump here, so the
+    /// reward state's own `bump` is written back too.
+    fn migrate_reward_state(&mut self, data_len: usize, bump: u8) -> Result<()> {
+        let reward_state_info = self.mining_pool_reward_state.to_account_info();
+        let space = MiningPoolRewardState::INIT_SPACE + MiningPoolRewardState::DISCRIMINATOR.len();
+        let mut reward_state: MiningPoolRewardState = {
+            let data = reward_state_info.try_borrow_data()?;
+            let fields = &mut &data[MiningPoolRewardState::DISCRIMINATOR.len()..];
+            if data_len <= REWARD_STATE_V0_LEN {
+                RewardStateV0::deserialize(fields)?.into()
+            } else if data_len < space {
+                RewardStateV1::deserialize(fields)?.into()
+            } else {
+                MiningPoolRewardState::deserialize(fields)?
+            }
+        };
+        require!(
+            data_len < space || reward_state.bump != bump,
+            MorErrorCodes::AlreadyMigrated
+        );
+        reward_state.bump = bump;
+
+        reward_state.try_serialize(&mut &mut reward_state_info.try_borrow_mut_data()?[..])?;
+        Ok(())
+    }
+}
+
+pub fn migrate_reward_state_handler(ctx: Context<MigrateRewardState>) -> Result<()> {
+    let data_len = ctx.accounts.mining_pool_reward_state.data_len();
+
+    ctx.accounts.resize_reward_state()?;
+
+    ctx.accounts
+        .migrate_reward_state(data_len, ctx.bumps.mining_pool_reward_state)?;
+
+    Ok(())
+}
@@ -1,17 +1,71 @@
+pub mod accept_admin;
+pub mod add_bonus_mint;
+pub mod claim_commission;
 pub mod claim_rewards;
+pub mod close_epoch_ledger;
 pub mod delegate;
+pub mod delegate_epoch_ledger;
 pub mod delegate_reward_pool;
+pub mod delegate_reward_shard;
+pub mod initialize_epoch_ledger;
 pub mod initialize_miner;
 pub mod initialize_pool;
+pub mod initialize_protocol_config;
+pub mod initialize_reward_shard;
+pub mod migrate_miner;
+pub mod migrate_pool;
+pub mod migrate_reward_state;
+pub mod propose_admin;
+pub mod release_vested;
+pub mod set_allowlist;
+pub mod set_commission;
+pub mod set_min_stake;
+pub mod set_miner_beneficiary;
+pub mod set_miner_signer;
+pub mod set_multiplier_tiers;
+pub mod set_protocol_config;
+pub mod set_vesting;
+pub mod settle_epoch;
+pub mod stake;
 pub mod submit_solution;
+pub mod submit_solutions;
 pub mod undelegate;
+pub mod undelegate_epoch_ledger;
 pub mod undelegate_reward_pool;
+pub mod unstake;
 
+pub use accept_admin::*;
+pub use add_bonus_mint::*;
+pub use claim_commission::*;
 pub use claim_rewards::*;
+pub use close_epoch_ledger::*;
 pub use delegate::*;
+pub use delegate_epoch_ledger::*;
 pub use delegate_reward_pool::*;
+pub use delegate_reward_shard::*;
+pub use initialize_epoch_ledger::*;
 pub use initialize_miner::*;
 pub use initialize_pool::*;
+pub use initialize_protocol_config::*;
+pub use initialize_reward_shard::*;
+pub use migrate_miner::*;
+pub use migrate_pool::*;
+pub use migrate_reward_state::*;
+pub use propose_admin::*;
+pub use release_vested::*;
+pub use set_allowlist::*;
+pub use set_commission::*;
+pub use set_min_stake::*;
+pub use set_miner_beneficiary::*;
+pub use set_miner_signer::*;
+pub use set_multiplier_tiers::*;
+pub use set_protocol_config::*;
+pub use set_vesting::*;
+pub use settle_epoch::*;
+pub use stake::*;
 pub use submit_solution::*;
+pub use submit_solutions::*;
 pub use undelegate::*;
+pub use undelegate_epoch_ledger::*;
 pub use undelegate_reward_pool::*;
+pub use unstake::*;
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{constants::MINING_POOL_SEED, errors::MorErrorCodes},
+    states::MiningPoolPda,
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+}
+
+impl<'info> ProposeAdmin<'info> {
+    /// # Propose a new admin for the pool
+    /// Nothing changes until the proposed admin accepts, and proposing
+    /// Pubkey::default() withdraws the proposal.
+    fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        // Only the mining pool's admin can hand it over
+        require!(
+            self.admin.key() == self.mining_pool_pda.admin,
+            MorErrorCodes::InvalidAuthority
+        );
+
+        self.mining_pool_pda.pending_admin = new_admin;
+        Ok(())
+    }
+}
+
+pub fn propose_admin_handler(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.propose_admin(new_admin)?;
+
+    Ok(())
+}
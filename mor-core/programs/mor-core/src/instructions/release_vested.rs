@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    helpers::{
+        constants::{MINER_SEED, MINING_POOL_SEED, VESTING_SEED},
+        errors::MorErrorCodes,
+        utils::linear_vested,
+    },
+    states::{MinerAccountPoolPda, MiningPoolPda, VestingEscrow},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct ReleaseVested<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [MINER_SEED, authority.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump
+    )]
+    pub miner: Account<'info, MinerAccountPoolPda>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, miner.key().as_ref()],
+        bump = vesting_escrow.bump,
+    )]
+    pub vesting_escrow: Account<'info, VestingEscrow>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = mining_pool_pda.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Owner of the recipient ATA. It must be the miner's beneficiary
+    /// when one is set, as checked in release_vested.
+    pub beneficiary: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Programs
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReleaseVested<'info> {
+    /// # Pay everything vested and not yet released to the beneficiary
+    fn release_vested(&mut self) -> Result<()> {
+        // A persisted beneficiary routes every payout
+        require!(
+            self.miner.beneficiary == Pubkey::default()
+                || self.beneficiary.key() == self.miner.beneficiary,
+            MorErrorCodes::InvalidBeneficiary
+        );
+
+        let escrow = &mut self.vesting_escrow;
+        let now = Clock::get()?.unix_timestamp;
+        let vested = if now < escrow.cliff_timestamp {
+            0
+        } else {
+            linear_vested(
+                escrow.deposited,
+                escrow.start_timestamp,
+                escrow.duration_seconds,
+                now,
+            )
+        };
+        let amount = vested.saturating_sub(escrow.released);
+        require!(amount > 0, MorErrorCodes::NoRewardsToClaim);
+
+        let miner_key = self.miner.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[VESTING_SEED, miner_key.as_ref(), &[escrow.bump]]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.escrow_vault.to_account_info(),
+                    to: self.recipient_ata.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    authority: escrow.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            amount,
+            self.mint.decimals,
+        )?;
+        escrow.released += amount;
+
+        Ok(())
+    }
+}
+
+pub fn release_vested_handler(ctx: Context<ReleaseVested>) -> Result<()> {
+    ctx.accounts.release_vested()?;
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{constants::MINING_POOL_SEED, errors::MorErrorCodes},
+    states::MiningPoolPda,
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SetAllowlist<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+}
+
+impl<'info> SetAllowlist<'info> {
+    /// # Set or clear the Merkle root of the wallets allowed to mine
+    /// Only new miners are checked, miners created before keep mining.
+    fn set_allowlist(&mut self, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+        // Only the mining pool's admin can change its allowlist
+        require!(
+            self.admin.key() == self.mining_pool_pda.admin,
+            MorErrorCodes::InvalidAuthority
+        );
+
+        self.mining_pool_pda.allowlist_root = allowlist_root;
+        Ok(())
+    }
+}
+
+pub fn set_allowlist_handler(
+    ctx: Context<SetAllowlist>,
+    allowlist_root: Option<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts.set_allowlist(allowlist_root)?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{
+        constants::{MAX_COMMISSION_BPS, MINING_POOL_SEED},
+        errors::MorErrorCodes,
+    },
+    states::MiningPoolPda,
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SetCommission<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+}
+
+impl<'info> SetCommission<'info> {
+    /// # Change the pool maker's share of future rewards
+    fn set_commission(&mut self, commission_bps: u16) -> Result<()> {
+        // Only the mining pool's admin can change its commission
+        require!(
+            self.admin.key() == self.mining_pool_pda.admin,
+            MorErrorCodes::InvalidAuthority
+        );
+        require_gte!(
+            MAX_COMMISSION_BPS,
+            commission_bps,
+            MorErrorCodes::CommissionTooHigh
+        );
+
+        self.mining_pool_pda.commission_bps = commission_bps;
+        Ok(())
+    }
+}
+
+pub fn set_commission_handler(ctx: Context<SetCommission>, commission_bps: u16) -> Result<()> {
+    ctx.accounts.set_commission(commission_bps)?;
+
+    Ok(())
+}
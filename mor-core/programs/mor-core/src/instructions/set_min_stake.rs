@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{constants::MINING_POOL_SEED, errors::MorErrorCodes},
+    states::MiningPoolPda,
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SetMinStake<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+}
+
+impl<'info> SetMinStake<'info> {
+    /// # Set the stake a miner needs to submit solutions
+    /// Miners below it keep their rewards but can't mine until they stake more.
+    fn set_min_stake(&mut self, min_stake: u64) -> Result<()> {
+        // Only the mining pool's admin can change its minimum stake
+        require!(
+            self.admin.key() == self.mining_pool_pda.admin,
+            MorErrorCodes::InvalidAuthority
+        );
+
+        self.mining_pool_pda.min_stake = min_stake;
+        Ok(())
+    }
+}
+
+pub fn set_min_stake_handler(ctx: Context<SetMinStake>, min_stake: u64) -> Result<()> {
+    ctx.accounts.set_min_stake(min_stake)?;
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::constants::{MINER_SEED, MINING_POOL_SEED},
+    states::{MinerAccountPoolPda, MiningPoolPda},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SetMinerBeneficiary<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINER_SEED, authority.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump
+    )]
+    pub miner: Account<'info, MinerAccountPoolPda>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+}
+
+impl<'info> SetMinerBeneficiary<'info> {
+    /// # Set the owner every claim of the miner pays
+    /// Pubkey::default() removes it, letting the authority choose per claim
+    fn set_miner_beneficiary(&mut self, beneficiary: Pubkey) -> Result<()> {
+        self.miner.beneficiary = beneficiary;
+        msg!("miner beneficiary: {}", beneficiary);
+        Ok(())
+    }
+}
+
+pub fn set_miner_beneficiary_handler(
+    ctx: Context<SetMinerBeneficiary>,
+    beneficiary: Pubkey,
+) -> Result<()> {
+    ctx.accounts.set_miner_beneficiary(beneficiary)?;
+
+    Ok(())
+}
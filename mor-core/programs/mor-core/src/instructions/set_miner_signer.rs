@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::constants::{MINER_SEED, MINING_POOL_SEED},
+    states::{MinerAccountPoolPda, MiningPoolPda},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SetMinerSigner<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINER_SEED, authority.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump
+    )]
+    pub miner: Account<'info, MinerAccountPoolPda>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+}
+
+impl<'info> SetMinerSigner<'info> {
+    /// # Set or rotate the miner's hot key
+    /// Pubkey::default() removes it, leaving submissions to the authority
+    fn set_miner_signer(&mut self, signer: Pubkey) -> Result<()> {
+        self.miner.signer = signer;
+        msg!("miner signer: {}", signer);
+        Ok(())
+    }
+}
+
+pub fn set_miner_signer_handler(ctx: Context<SetMinerSigner>, signer: Pubkey) -> Result<()> {
+    ctx.accounts.set_miner_signer(signer)?;
+
+    Ok(())
+}
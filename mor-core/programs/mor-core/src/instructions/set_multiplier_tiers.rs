@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{
+        constants::MINING_POOL_SEED, errors::MorErrorCodes, utils::validate_multiplier_tiers,
+    },
+    states::{MiningPoolPda, MultiplierTier},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SetMultiplierTiers<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+}
+
+impl<'info> SetMultiplierTiers<'info> {
+    /// # Replace the pool's multiplier table
+    fn set_multiplier_tiers(&mut self, multiplier_tiers: Vec<MultiplierTier>) -> Result<()> {
+        // Only the mining pool's admin can change its multiplier table
+        require!(
+            self.admin.key() == self.mining_pool_pda.admin,
+            MorErrorCodes::InvalidAuthority
+        );
+        require!(
+            validate_multiplier_tiers(&multiplier_tiers),
+            MorErrorCodes::InvalidMultiplierTiers
+        );
+
+        self.mining_pool_pda.multiplier_tiers = multiplier_tiers;
+        Ok(())
+    }
+}
+
+pub fn set_multiplier_tiers_handler(
+    ctx: Context<SetMultiplierTiers>,
+    multiplier_tiers: Vec<MultiplierTier>,
+) -> Result<()> {
+    ctx.accounts.set_multiplier_tiers(multiplier_tiers)?;
+
+    Ok(())
+}
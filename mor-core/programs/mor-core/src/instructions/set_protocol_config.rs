@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{
+        constants::{MAX_PROTOCOL_FEE_BPS, PROTOCOL_CONFIG_SEED},
+        errors::MorErrorCodes,
+    },
+    states::ProtocolConfig,
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SetProtocolConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+impl<'info> SetProtocolConfig<'info> {
+    /// # Replace the protocol admin, treasury and fees
+    /// Fees apply to pools created and rewards claimed from now on.
+    fn set_protocol_config(
+        &mut self,
+        admin: Pubkey,
+        treasury: Pubkey,
+        pool_creation_fee: u64,
+        protocol_fee_bps: u16,
+    ) -> Result<()> {
+        // Only the protocol admin can change the protocol config
+        require!(
+            self.admin.key() == self.protocol_config.admin,
+            MorErrorCodes::InvalidAuthority
+        );
+        require_gte!(
+            MAX_PROTOCOL_FEE_BPS,
+            protocol_fee_bps,
+            MorErrorCodes::ProtocolFeeTooHigh
+        );
+
+        let protocol_config = &mut self.protocol_config;
+        protocol_config.admin = admin;
+        protocol_config.treasury = treasury;
+        protocol_config.pool_creation_fee = pool_creation_fee;
+        protocol_config.protocol_fee_bps = protocol_fee_bps;
+        Ok(())
+    }
+}
+
+pub fn set_protocol_config_handler(
+    ctx: Context<SetProtocolConfig>,
+    admin: Pubkey,
+    treasury: Pubkey,
+    pool_creation_fee: u64,
+    protocol_fee_bps: u16,
+) -> Result<()> {
+    ctx.accounts
+        .set_protocol_config(admin, treasury, pool_creation_fee, protocol_fee_bps)?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{constants::MINING_POOL_SEED, errors::MorErrorCodes, utils::validate_vesting},
+    states::{MiningPoolPda, VestingConfig},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SetVesting<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+}
+
+impl<'info> SetVesting<'info> {
+    /// # Set or clear the vesting schedule of future claims
+    /// Tokens already in a vesting escrow move to the new duration on the
+    /// miner's next claim, keeping what has vested.
+    fn set_vesting(&mut self, vesting: Option<VestingConfig>) -> Result<()> {
+        // Only the mining pool's admin can change its vesting
+        require!(
+            self.admin.key() == self.mining_pool_pda.admin,
+            MorErrorCodes::InvalidAuthority
+        );
+        if let Some(vesting) = vesting {
+            require!(
+                validate_vesting(vesting.cliff_seconds, vesting.duration_seconds),
+                MorErrorCodes::InvalidVestingSchedule
+            );
+        }
+
+        self.mining_pool_pda.vesting = vesting;
+        Ok(())
+    }
+}
+
+pub fn set_vesting_handler(ctx: Context<SetVesting>, vesting: Option<VestingConfig>) -> Result<()> {
+    ctx.accounts.set_vesting(vesting)?;
+
+    Ok(())
+}
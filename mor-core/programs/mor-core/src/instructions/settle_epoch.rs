@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{
+        constants::{EPOCH_SLOT_LENGTH, MINING_POOL_REWARD_SEED, MINING_POOL_SEED, REWARD_SHARDS},
+        errors::MorErrorCodes,
+        utils::{epoch_stats_slot, fold_epoch_stats, shard_share},
+    },
+    states::{EpochLedger, EpochStats, MiningPoolPda, MiningPoolRewardState, RewardShard},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SettleEpoch<'info> {
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+    )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    #[account(
+        mut,
+        seeds = [MINING_POOL_REWARD_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump = mining_pool_reward_state.bump,
+    )]
+    pub mining_pool_reward_state: Account<'info, MiningPoolRewardState>,
+}
+
+impl<'info> SettleEpoch<'info> {
+    /// # Fold the reward shards' credits into the reward state
+    /// Permissionless crank, once per epoch. `remaining_accounts` holds every reward
+    /// shard of the pool in index order, then any of the pool's epoch ledgers. What
+    /// the shards credited leaves the pool's remaining rewards, their commission goes
+    /// to the pool maker, and what remains is split evenly across the shards again.
+    /// The shards' stats of each passed ledger's epoch are added to it, those of
+    /// other epochs wait for a later settlement.
+    fn settle_epoch(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let current_epoch = Clock::get()?.slot / EPOCH_SLOT_LENGTH;
+        require_gt!(
+            current_epoch,
+            self.mining_pool_reward_state.settled_epoch,
+            MorErrorCodes::AlreadySettled
+        );
+        require!(
+            remaining_accounts.len() >= REWARD_SHARDS as usize,
+            MorErrorCodes::InvalidRewardShard
+        );
+        let (shard_accounts, ledger_accounts) = remaining_accounts.split_at(REWARD_SHARDS as usize);
+
+        let pool_key = self.mining_pool_pda.key();
+        let mut shards = Vec::with_capacity(shard_accounts.len());
+        for (index, shard_info) in shard_accounts.iter().enumerate() {
+            // Owner and discriminator are checked here, pool and index below
+            let shard = Account::<RewardShard>::try_from(shard_info)?;
+            require!(
+                shard.pool == pool_key && shard.index as usize == index,
+                MorErrorCodes::InvalidRewardShard
+            );
+            shards.push(shard);
+        }
+
+        let reward_state = &mut self.mining_pool_reward_state;
+        for shard in &mut shards {
+            reward_state.amount = reward_state.amount.saturating_sub(shard.credited);
+            reward_state.maker_rewards =
+                reward_state.maker_rewards.saturating_add(shard.commission);
+            for (remaining, credited) in reward_state
+                .bonus_amounts
+                .iter_mut()
+                .zip(&shard.bonus_credited)
+            {
+                *remaining = remaining.saturating_sub(*credited);
+            }
+            shard.credited = 0;
+            shard.commission = 0;
+            shard.bonus_credited = vec![0; reward_state.bonus_amounts.len()];
+        }
+
+        for ledger_info in ledger_accounts {
+            // Owner and discriminator are checked here, pool below
+            let mut epoch_ledger = Account::<EpochLedger>::try_from(ledger_info)?;
+            require_keys_eq!(
+                epoch_ledger.pool,
+                pool_key,
+                MorErrorCodes::InvalidEpochLedger
+            );
+            for shard in &mut shards {
+                let stats = &mut shard.epoch_stats[epoch_stats_slot(epoch_ledger.epoch)];
+                if stats.epoch == epoch_ledger.epoch {
+                    fold_epoch_stats(&mut epoch_ledger, stats);
+                    *stats = EpochStats::default();
+                }
+            }
+            epoch_ledger.settled_epoch = current_epoch;
+            epoch_ledger.exit(&crate::ID)?;
+        }
+
+        // Split what remains anew, so no shard sits on budget others ran out of
+        for shard in &mut shards {
+            shard.budget = shard_share(reward_state.amount, shard.index);
+            shard.bonus_budgets = reward_state
+                .bonus_amounts
+                .iter()
+                .map(|remaining| shard_share(*remaining, shard.index))
+                .collect();
+            shard.exit(&crate::ID)?;
+        }
+        reward_state.settled_epoch = current_epoch;
+
+        msg!(
+            "settled epoch {}, pool remaining amount: {}",
+            current_epoch,
+            reward_state.amount
+        );
+        Ok(())
+    }
+}
+
+pub fn settle_epoch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleEpoch<'info>>,
+) -> Result<()> {
+    ctx.accounts.settle_epoch(ctx.remaining_accounts)?;
+
+    Ok(())
+}
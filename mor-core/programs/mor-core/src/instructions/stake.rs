@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{
+    helpers::{
+        constants::{MINER_SEED, MINING_POOL_SEED, STAKE_VAULT_SEED},
+        errors::MorErrorCodes,
+        utils::weighted_stake_timestamp,
+    },
+    states::{MinerAccountPoolPda, MiningPoolPda},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINER_SEED, authority.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump
+    )]
+    pub miner: Account<'info, MinerAccountPoolPda>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    /// Token Accounts
+    #[account(
+        address = mining_pool_pda.mint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+        associated_token::token_program = token_program
+    )]
+    pub authority_ata: InterfaceAccount<'info, TokenAccount>,
+    /// Holds the pool's stake apart from the reward vault
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [STAKE_VAULT_SEED, mining_pool_pda.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = mining_pool_pda,
+        token::token_program = token_program
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Programs
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Stake<'info> {
+    /// # Lock tokens into the pool's stake vault
+    fn stake(&mut self, amount: u64) -> Result<()> {
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.authority_ata.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.stake_vault.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                },
+            ),
+            amount,
+            self.mint.decimals,
+        )?;
+
+        let miner = &mut self.miner;
+        miner.staked_amount = miner
+            .staked_amount
+            .checked_add(amount)
+            .ok_or(MorErrorCodes::InvalidAmount)?;
+        // The added tokens start with no loyalty, diluting the age of the stake
+        miner.last_staked_timestamp = weighted_stake_timestamp(
+            miner.last_staked_timestamp,
+            Clock::get()?.unix_timestamp,
+            amount,
+            miner.staked_amount,
+        );
+
+        Ok(())
+    }
+}
+
+pub fn stake_handler(ctx: Context<Stake>, amount: u64) -> Result<()> {
+    require_gt!(amount, 0, MorErrorCodes::InvalidAmount);
+
+    ctx.accounts.stake(amount)?;
+
+    Ok(())
+}
@@ -1,21 +1,36 @@
-use anchor_lang::prelude::*;
-use sha3::{Digest, Sha3_256};
 use crate::{
-    helpers::{constants::{EPOCH_SLOT_LENGTH, MINER_SEED, MINING_POOL_REWARD_SEED, MINING_POOL_SEED}, errors::MorErrorCodes, utils::generate_challenge},
-    states::{MinerAccountPoolPda, MiningPoolPda, MiningPoolRewardState},
+    helpers::{
+        constants::{
+            EPOCH_SLOT_LENGTH, MAX_EPOCH_OFFSET, MAX_REWARD_SCALE_BITS, MINER_SEED,
+            MINING_POOL_REWARD_SEED, MINING_POOL_SEED, MULTIPLIER_BASE_BPS, REWARD_SHARDS,
+            REWARD_SHARD_SEED,
+        },
+        errors::MorErrorCodes,
+        utils::{
+            apply_loyalty, calculate_loyalty_bps, calculate_multiplier, epoch_stats_slot,
+            generate_challenge, leading_zero_bits, next_difficulty, pow_hash, reward_shard_index,
+            verify_pow,
+        },
+    },
+    states::{
+        EpochSolution, EpochStats, MinerAccountPoolPda, MiningPoolPda, MiningPoolRewardState,
+        RewardShard,
+    },
 };
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
-#[instruction()]
+#[instruction(nonce: u64, epoch: u64)]
 pub struct SubmitSolution<'info> {
+    /// CHECK: Loaded with load_miner, so a missing miner fails with MinerNotInitialized.
+    /// Miners are only created by initialize_miner.
     #[account(
-        init_if_needed,
-        space = MinerAccountPoolPda::INIT_SPACE + MinerAccountPoolPda::DISCRIMINATOR.len(),
-        payer = authority, 
-        seeds = [MINER_SEED, authority.key().as_ref(), mining_pool_pda.key().as_ref()], 
+        mut,
+        seeds = [MINER_SEED, authority.key().as_ref(), mining_pool_pda.key().as_ref()],
         bump
     )]
-    pub miner: Account<'info, MinerAccountPoolPda>,
+    pub miner: UncheckedAccount<'info>,
 
     #[account(
         seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
@@ -23,106 +38,325 @@ pub struct SubmitSolution<'info> {
       )]
     pub mining_pool_pda: Account<'info, MiningPoolPda>,
 
+    /// CHECK: Checked with require_reward_state, so a missing one fails with
+    /// RewardStateNotInitialized. Not written, so concurrent solutions don't queue
+    /// on it. settle_epoch folds the shards' credits into it.
     #[account(
-        init_if_needed,
-        payer = authority,
-        space = MiningPoolRewardState::INIT_SPACE + MiningPoolRewardState::DISCRIMINATOR.len(),
         seeds = [MINING_POOL_REWARD_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.key().as_ref()],
         bump,
     )]
-    pub mining_pool_reward_state: Account<'info, MiningPoolRewardState>,
+    pub mining_pool_reward_state: UncheckedAccount<'info>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    /// The reward shard the authority's solutions are credited from
+    #[account(
+        mut,
+        seeds = [REWARD_SHARD_SEED, mining_pool_pda.key().as_ref(), &[reward_shard_index(&authority.key())]],
+        bump = reward_shard.bump,
+    )]
+    pub reward_shard: Account<'info, RewardShard>,
+
+    /// CHECK: Owner of the miner and its rewards, only used to derive the miner.
+    /// Either it or the miner's hot key must sign, as checked in submit_solution.
+    pub authority: UncheckedAccount<'info>,
+
+    /// The authority itself, or the hot key it set with set_miner_signer
+    pub signer: Signer<'info>,
 }
 
 impl<'info> SubmitSolution<'info> {
-    fn submit_solution(&mut self, nonce: u64, epoch: u64) -> Result<()> {
-        let miner = &mut self.miner;
-        miner.authority = self.authority.key();
+    fn submit_solution(&mut self, nonce: u64, epoch: u64, digest: [u8; 16]) -> Result<()> {
+        require_reward_state(&self.mining_pool_reward_state)?;
+        let miner_info = self.miner.to_account_info();
+        let miner = &mut load_miner(&miner_info)?;
 
-        let slot = Clock::get()?.slot;
-        let current_epoch = slot / EPOCH_SLOT_LENGTH;
+        let signer = self.signer.key();
+        require!(
+            signer == self.authority.key()
+                || (miner.signer != Pubkey::default() && signer == miner.signer),
+            MorErrorCodes::InvalidAuthority
+        );
 
-        msg!(
-            "Epoch Range Elapsed: {:?}",
-            (epoch as i64 - current_epoch as i64).abs()
+        // Pools can ask for a stake, so free keypairs can't each mine a share
+        require_gte!(
+            miner.staked_amount,
+            self.mining_pool_pda.min_stake,
+            MorErrorCodes::InsufficientStakedAmount
         );
 
-        // Only allow epoch that is within ±4 of the current
-        require!(
-            (epoch as i64 - current_epoch as i64).abs() <= 4,
-            MorErrorCodes::InvalidEpochRange
+        let slot = Clock::get()?.slot;
+        let previous_epoch = miner.last_epoch_mined;
+        let (actual_reward, achieved_difficulty) = credit_solution(
+            miner,
+            &self.mining_pool_pda,
+            &mut self.reward_shard,
+            EpochSolution {
+                epoch,
+                nonce,
+                digest,
+            },
+            slot,
+        )?;
+
+        record_solution(
+            &mut self.reward_shard,
+            epoch,
+            &miner.authority,
+            actual_reward,
+            achieved_difficulty,
         );
 
-        // Ensure user hasn't mined this epoch already // TODO: uncomment this when we update difficulty logic
-        // require!(epoch != miner.last_epoch_mined, MorErrorCodes::AlreadyMined);
+        adjust_difficulty(miner, previous_epoch, slot);
+        store_miner(&miner_info, miner)
+    }
+}
 
-        msg!("nonce: {}", nonce);
-        msg!("slot: {}", slot);
-        msg!("epoch: {}", epoch);
+/// # Load an existing miner
+/// Fails with MinerNotInitialized when the authority has no miner in the pool yet.
+/// Older layouts fail to deserialize until migrate_miner upgrades them.
+pub(crate) fn load_miner(miner_info: &AccountInfo) -> Result<MinerAccountPoolPda> {
+    require!(
+        !miner_info.data_is_empty(),
+        MorErrorCodes::MinerNotInitialized
+    );
+    require_keys_eq!(
+        *miner_info.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    MinerAccountPoolPda::try_deserialize(&mut &miner_info.try_borrow_data()?[..])
+}
 
-        let challenge = generate_challenge(&miner.authority, epoch);
-        let mut hasher = Sha3_256::new();
-        hasher.update(&challenge);
-        hasher.update(&nonce.to_le_bytes());
-        let result = hasher.finalize();
-        msg!("RESULT: {:?}", result);
+/// # Write a miner loaded with load_miner back to its account
+pub(crate) fn store_miner(miner_info: &AccountInfo, miner: &MinerAccountPoolPda) -> Result<()> {
+    miner.try_serialize(&mut &mut miner_info.try_borrow_mut_data()?[..])
+}
 
-        for i in 0..miner.difficulty {
-            if result[i as usize] != 0 {
-                return err!(MorErrorCodes::InvalidSolution);
-            }
-        }
+/// # Check the pool's reward state exists
+/// Fails with RewardStateNotInitialized rather than creating it, which only
+/// initialize_pool does.
+pub(crate) fn require_reward_state(reward_state_info: &AccountInfo) -> Result<()> {
+    require!(
+        !reward_state_info.data_is_empty(),
+        MorErrorCodes::RewardStateNotInitialized
+    );
+    require_keys_eq!(
+        *reward_state_info.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    MiningPoolRewardState::try_deserialize(&mut &reward_state_info.try_borrow_data()?[..])?;
+    Ok(())
+}
 
-        // Check if mining pool has rewards available
-        require!(
-            self.mining_pool_reward_state.amount > 0,
-            MorErrorCodes::NoRewardsAvailable
-        );
+/// # Check an epoch is within MAX_EPOCH_OFFSET of the current one
+pub(crate) fn require_epoch_in_range(epoch: u64, current_epoch: u64) -> Result<()> {
+    require!(
+        epoch.abs_diff(current_epoch) <= MAX_EPOCH_OFFSET,
+        MorErrorCodes::InvalidEpochRange
+    );
+    Ok(())
+}
 
-        // Calculate reward amount based on available pool amount and miner multiplier
-        // Use 5% of available pool amount as base reward, multiplied by miner's multiplier
-        let base_reward_percentage = 5; // 5% of available pool amount
-        let base_reward = (self.mining_pool_reward_state.amount * base_reward_percentage) / 100;
-        let reward_amount = base_reward * miner.multiplier as u64;
-        
-        // Ensure we don't exceed the available amount in the pool
-        let actual_reward = if reward_amount > self.mining_pool_reward_state.amount {
-            self.mining_pool_reward_state.amount
-        } else {
-            reward_amount
+/// # Count a credited solution in its epoch's stats on the miner's shard
+/// settle_epoch folds them into the epoch's ledger, so solutions don't all write
+/// one account. A slot still holding an epoch that was never folded in is reused,
+/// and that epoch's counters are lost.
+pub(crate) fn record_solution(
+    shard: &mut RewardShard,
+    epoch: u64,
+    authority: &Pubkey,
+    reward: u64,
+    difficulty: u32,
+) {
+    let stats = &mut shard.epoch_stats[epoch_stats_slot(epoch)];
+    if stats.epoch != epoch {
+        if stats.submissions > 0 {
+            msg!("dropping unsettled stats of epoch {}", stats.epoch);
+        }
+        *stats = EpochStats {
+            epoch,
+            ..EpochStats::default()
         };
+    }
+    stats.submissions += 1;
+    stats.total_rewards = stats.total_rewards.saturating_add(reward);
+    if difficulty > stats.best_difficulty {
+        stats.best_difficulty = difficulty;
+        stats.best_miner = *authority;
+    }
+}
+
+/// # Verify a solution and credit its reward to the miner
+/// Shared by submit_solution and submit_solutions. Fails with AlreadyMined
+/// unless the epoch is newer than the miner's last one. The reward is drawn from the
+/// miner's reward shard, which stands in for its share of the pool. Returns the
+/// reward credited, commission included, and the difficulty the solution achieved.
+pub(crate) fn credit_solution(
+    miner: &mut MinerAccountPoolPda,
+    pool: &MiningPoolPda,
+    shard: &mut RewardShard,
+    solution: EpochSolution,
+    slot: u64,
+) -> Result<(u64, u32)> {
+    let EpochSolution {
+        epoch,
+        nonce,
+        digest,
+    } = solution;
+    let current_epoch = slot / EPOCH_SLOT_LENGTH;
+
+    msg!(
+        "Epoch Range Elapsed: {:?}",
+        (epoch as i64 - current_epoch as i64).abs()
+    );
+
+    // Only allow epoch that is within ±MAX_EPOCH_OFFSET of the current
+    require_epoch_in_range(epoch, current_epoch)?;
+
+    // Each epoch earns one reward, so epochs must be newer than the last one
+    // mined. u64::MAX means the miner never mined.
+    require!(
+        miner.last_epoch_mined == u64::MAX || epoch > miner.last_epoch_mined,
+        MorErrorCodes::AlreadyMined
+    );
+
+    msg!("nonce: {}", nonce);
+    msg!("slot: {}", slot);
+    msg!("epoch: {}", epoch);
+
+    // The pool's PoW algorithm decides how the nonce is proven and hashed
+    let pow_algorithm = pool.pow_algorithm;
+    let challenge = generate_challenge(&miner.authority, epoch);
+    require!(
+        verify_pow(pow_algorithm, &challenge, nonce, &digest),
+        MorErrorCodes::InvalidSolution
+    );
+    let result = pow_hash(pow_algorithm, &challenge, nonce, &digest);
+    msg!("RESULT: {:?}", result);
+
+    let achieved_difficulty = leading_zero_bits(&result);
+    msg!("achieved difficulty: {}", achieved_difficulty);
+    require!(
+        achieved_difficulty >= miner.difficulty as u32,
+        MorErrorCodes::InvalidSolution
+    );
+
+    // Check if the miner's shard has rewards available
+    require!(shard.budget > 0, MorErrorCodes::NoRewardsAvailable);
+
+    // The multiplier follows the miner's stake through the pool's tier table,
+    // and its stake bonus grows the longer the stake stays locked
+    miner.multiplier = apply_loyalty(
+        calculate_multiplier(
+            miner.staked_amount,
+            pool.mint_decimals,
+            &pool.multiplier_tiers,
+        ),
+        calculate_loyalty_bps(miner.last_staked_timestamp, Clock::get()?.unix_timestamp),
+    );
+
+    // Calculate reward amount based on available pool amount and miner multiplier
+    // Use 5% of available pool amount as base reward, multiplied by miner's multiplier.
+    // The shards split the pool evenly, so the pool counts as the shard's budget times REWARD_SHARDS.
+    let base_reward_percentage = 5; // 5% of available pool amount
+    let pool_amount = shard.budget as u128 * REWARD_SHARDS as u128;
+    let base_reward = (pool_amount * base_reward_percentage / 100) as u64;
+    let reward_amount = (base_reward as u128 * miner.multiplier as u128
+        / MULTIPLIER_BASE_BPS as u128)
+        .min(u64::MAX as u128) as u64;
+
+    // Like ORE, each bit beyond the required difficulty doubles the reward, up to a cap
+    let scale_bits = achieved_difficulty
+        .saturating_sub(miner.difficulty as u32)
+        .min(MAX_REWARD_SCALE_BITS as u32);
+    let reward_amount = reward_amount.saturating_mul(1 << scale_bits);
+
+    // Ensure we don't exceed what the shard has left to give
+    let actual_reward = reward_amount.min(shard.budget);
 
-        // Update miner rewards and reduce pool amount
-        miner.rewards += actual_reward;
-        self.mining_pool_reward_state.amount = self.mining_pool_reward_state.amount.saturating_sub(actual_reward);
-        
-        miner.last_epoch_mined = epoch;
-        msg!("miner rewards: {}", miner.rewards);
-        msg!("pool remaining amount: {}", self.mining_pool_reward_state.amount);
-
-        // TODO: need to fix difficulty logic since above difficulty 3 it takes too long to mine
-        // Simple adaptive difficulty: adjust based on how quickly the miner is solving epochs
-        let target_epoch_time: u64 = EPOCH_SLOT_LENGTH; // Target: 1 epoch per EPOCH_SLOT_LENGTH slots
-        if miner.last_epoch_mined != u64::MAX {
-            let last_mined_slot = miner.last_epoch_mined * EPOCH_SLOT_LENGTH;
-            let time_since_last = slot.saturating_sub(last_mined_slot);
-            if time_since_last < target_epoch_time && miner.difficulty < 4 {
-                miner.difficulty += 1;
-                msg!("Increasing difficulty to {}", miner.difficulty);
-            } else if time_since_last > target_epoch_time && miner.difficulty > 1 {
-                miner.difficulty -= 1;
-                msg!("Decreasing difficulty to {}", miner.difficulty);
-            }
+    // The pool maker's commission comes out of the credited reward
+    let commission =
+        (actual_reward as u128 * pool.commission_bps as u128 / MULTIPLIER_BASE_BPS as u128) as u64;
+    shard.commission = shard.commission.saturating_add(commission);
+    msg!("pool maker commission: {}", commission);
+
+    // Update miner rewards and reduce the shard's budget
+    miner.rewards += actual_reward - commission;
+
+    // Running bonus campaigns credit their mint pro-rata to the miner's reward
+    let now = Clock::get()?.unix_timestamp;
+    for (index, bonus) in pool.bonus_mints.iter().enumerate() {
+        let Some(remaining) = shard.bonus_budgets.get_mut(index) else {
+            continue;
+        };
+        if now >= bonus.ends_at || *remaining == 0 {
+            continue;
+        }
+        let bonus_reward = ((actual_reward - commission) as u128 * bonus.rate_bps as u128
+            / MULTIPLIER_BASE_BPS as u128)
+            .min(*remaining as u128) as u64;
+        *remaining -= bonus_reward;
+        shard.bonus_credited[index] = shard.bonus_credited[index].saturating_add(bonus_reward);
+        if miner.bonus_rewards.len() <= index {
+            miner.bonus_rewards.resize(index + 1, 0);
         }
-        Ok(())
+        miner.bonus_rewards[index] = miner.bonus_rewards[index].saturating_add(bonus_reward);
+        msg!(
+            "bonus {} rewards: {}",
+            bonus.mint,
+            miner.bonus_rewards[index]
+        );
+    }
+    shard.budget -= actual_reward;
+    shard.credited = shard.credited.saturating_add(actual_reward);
+
+    // Mining the epoch after the last one extends the streak, a gap restarts it
+    let last_epoch_mined = miner.last_epoch_mined;
+    let stats = &mut miner.stats;
+    stats.current_streak = if stats.total_solutions == 0 {
+        1
+    } else if epoch == last_epoch_mined.wrapping_add(1) {
+        stats.current_streak.saturating_add(1)
+    } else {
+        1
+    };
+    if stats.total_solutions == 0 {
+        stats.first_epoch_mined = epoch;
+    }
+    stats.total_solutions += 1;
+    stats.total_rewards = stats
+        .total_rewards
+        .saturating_add(actual_reward - commission);
+    stats.best_difficulty = stats.best_difficulty.max(achieved_difficulty);
+
+    miner.last_epoch_mined = epoch;
+    msg!("miner rewards: {}", miner.rewards);
+    msg!("shard remaining budget: {}", shard.budget);
+
+    Ok((actual_reward, achieved_difficulty))
+}
+
+/// # Adapt the miner's difficulty to how quickly it is solving epochs
+/// `previous_epoch` is the miner's last_epoch_mined from before the solutions were
+/// credited. Past MIN_DIFFICULTY each step is one leading zero bit, so the work only
+/// doubles or halves.
+pub(crate) fn adjust_difficulty(miner: &mut MinerAccountPoolPda, previous_epoch: u64, slot: u64) {
+    let difficulty = next_difficulty(miner.difficulty, previous_epoch, slot / EPOCH_SLOT_LENGTH);
+    if difficulty > miner.difficulty {
+        msg!("Increasing difficulty to {}", difficulty);
+    } else if difficulty < miner.difficulty {
+        msg!("Decreasing difficulty to {}", difficulty);
     }
+    miner.difficulty = difficulty;
 }
 
-pub fn submit_solution_handler(ctx: Context<SubmitSolution>, nonce: u64, epoch: u64) -> Result<()> {
-    ctx.accounts.submit_solution(nonce, epoch)?;
+pub fn submit_solution_handler(
+    ctx: Context<SubmitSolution>,
+    nonce: u64,
+    epoch: u64,
+    digest: [u8; 16],
+) -> Result<()> {
+    ctx.accounts.submit_solution(nonce, epoch, digest)?;
 
     Ok(())
 }
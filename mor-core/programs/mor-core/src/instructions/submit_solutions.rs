@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    helpers::{
+        constants::{
+            MAX_BATCH_SOLUTIONS, MINER_SEED, MINING_POOL_REWARD_SEED, MINING_POOL_SEED,
+            REWARD_SHARD_SEED,
+        },
+        errors::MorErrorCodes,
+        utils::reward_shard_index,
+    },
+    instructions::submit_solution::{
+        adjust_difficulty, credit_solution, load_miner, record_solution, require_reward_state,
+        store_miner,
+    },
+    states::{EpochSolution, MiningPoolPda, RewardShard},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct SubmitSolutions<'info> {
+    /// CHECK: Loaded with load_miner, so a missing miner fails with MinerNotInitialized.
+    /// Miners are only created by initialize_miner.
+    #[account(
+        mut,
+        seeds = [MINER_SEED, authority.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump
+    )]
+    pub miner: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+    )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    /// CHECK: Checked with require_reward_state, so a missing one fails with
+    /// RewardStateNotInitialized. Not written, as in submit_solution.
+    #[account(
+        seeds = [MINING_POOL_REWARD_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump,
+    )]
+    pub mining_pool_reward_state: UncheckedAccount<'info>,
+
+    /// The reward shard the authority's solutions are credited from
+    #[account(
+        mut,
+        seeds = [REWARD_SHARD_SEED, mining_pool_pda.key().as_ref(), &[reward_shard_index(&authority.key())]],
+        bump = reward_shard.bump,
+    )]
+    pub reward_shard: Account<'info, RewardShard>,
+
+    /// CHECK: Owner of the miner and its rewards, only used to derive the miner.
+    /// Either it or the miner's hot key must sign, as checked in submit_solutions.
+    pub authority: UncheckedAccount<'info>,
+
+    /// The authority itself, or the hot key it set with set_miner_signer
+    pub signer: Signer<'info>,
+}
+
+impl<'info> SubmitSolutions<'info> {
+    /// # Verify and credit the solutions of several epochs at once
+    /// Lets a miner that fell behind catch up in one transaction. Epochs must be
+    /// increasing and newer than the last one mined, so each earns one reward.
+    /// The difficulty adapts once, after the last solution.
+    fn submit_solutions(&mut self, solutions: &[EpochSolution]) -> Result<()> {
+        require!(
+            !solutions.is_empty() && solutions.len() <= MAX_BATCH_SOLUTIONS as usize,
+            MorErrorCodes::InvalidBatchSize
+        );
+        require_reward_state(&self.mining_pool_reward_state)?;
+        let miner_info = self.miner.to_account_info();
+        let miner = &mut load_miner(&miner_info)?;
+
+        let signer = self.signer.key();
+        require!(
+            signer == self.authority.key()
+                || (miner.signer != Pubkey::default() && signer == miner.signer),
+            MorErrorCodes::InvalidAuthority
+        );
+
+        // Pools can ask for a stake, so free keypairs can't each mine a share
+        require_gte!(
+            miner.staked_amount,
+            self.mining_pool_pda.min_stake,
+            MorErrorCodes::InsufficientStakedAmount
+        );
+
+        let slot = Clock::get()?.slot;
+        let previous_epoch = miner.last_epoch_mined;
+        for solution in solutions {
+            let (actual_reward, achieved_difficulty) = credit_solution(
+                miner,
+                &self.mining_pool_pda,
+                &mut self.reward_shard,
+                *solution,
+                slot,
+            )?;
+            record_solution(
+                &mut self.reward_shard,
+                solution.epoch,
+                &miner.authority,
+                actual_reward,
+                achieved_difficulty,
+            );
+        }
+
+        adjust_difficulty(miner, previous_epoch, slot);
+        store_miner(&miner_info, miner)
+    }
+}
+
+pub fn submit_solutions_handler(
+    ctx: Context<SubmitSolutions>,
+    solutions: Vec<EpochSolution>,
+) -> Result<()> {
+    ctx.accounts.submit_solutions(&solutions)?;
+
+    Ok(())
+}
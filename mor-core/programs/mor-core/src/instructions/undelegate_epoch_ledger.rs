@@ -0,0 +1,58 @@
+use crate::{
+    helpers::constants::{EPOCH_SEED, EPOCH_SLOT_LENGTH, MAX_EPOCH_OFFSET, MINING_POOL_SEED},
+    helpers::errors::MorErrorCodes,
+    states::{EpochLedger, MiningPoolPda},
+};
+use anchor_lang::prelude::*;
+use ephemeral_rollups_sdk::anchor::commit;
+use ephemeral_rollups_sdk::ephem::commit_and_undelegate_accounts;
+
+#[commit]
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct UndelegateEpochLedger<'info> {
+    pub payer: Signer<'info>,
+    /// CHECK The pda to undelegate
+    #[account(
+        mut,
+        seeds = [EPOCH_SEED, mining_pool_pda.key().as_ref(), &epoch.to_le_bytes()],
+        bump = epoch_ledger.bump,
+    )]
+    pub epoch_ledger: Account<'info, EpochLedger>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+}
+
+impl<'info> UndelegateEpochLedger<'info> {
+    /// # Return a finished epoch's ledger to the base layer
+    /// Permissionless once no more solutions can be submitted for the epoch.
+    fn undelegate_ledger(&mut self, epoch: u64) -> Result<()> {
+        let current_epoch = Clock::get()?.slot / EPOCH_SLOT_LENGTH;
+        require_gt!(
+            current_epoch,
+            epoch.saturating_add(MAX_EPOCH_OFFSET),
+            MorErrorCodes::InvalidEpochRange
+        );
+
+        commit_and_undelegate_accounts(
+            self.payer.to_account_info().as_ref(),
+            vec![&self.epoch_ledger.to_account_info()],
+            self.magic_context.as_ref(),
+            self.magic_program.as_ref(),
+        )?;
+        Ok(())
+    }
+}
+
+pub fn undelegate_epoch_ledger_handler(
+    ctx: Context<UndelegateEpochLedger>,
+    epoch: u64,
+) -> Result<()> {
+    ctx.accounts.undelegate_ledger(epoch)?;
+
+    Ok(())
+}
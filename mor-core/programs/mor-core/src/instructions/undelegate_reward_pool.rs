@@ -1,5 +1,5 @@
 use crate::{
-    helpers::constants::{MINING_POOL_REWARD_SEED, MINING_POOL_SEED},
+    helpers::constants::{MINING_POOL_REWARD_SEED, MINING_POOL_SEED, REWARD_SHARD_SEED},
     helpers::errors::MorErrorCodes,
     states::{MiningPoolPda, MiningPoolRewardState},
 };
@@ -11,11 +11,11 @@ use ephemeral_rollups_sdk::ephem::commit_and_undelegate_accounts;
 #[derive(Accounts)]
 #[instruction()]
 pub struct UndelegateRewardPool<'info> {
-    pub payer: Signer<'info>,
+    pub payer: Signer<'info>, // pool admin
     /// CHECK The pda to undelegate
     #[account(
         mut,
-        seeds = [MINING_POOL_REWARD_SEED, payer.key().as_ref(), mining_pool_pda.key().as_ref()],
+        seeds = [MINING_POOL_REWARD_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.key().as_ref()],
         bump = mining_pool_reward_state.bump,
     )]
     pub mining_pool_reward_state: Account<'info, MiningPoolRewardState>,
@@ -28,17 +28,43 @@ pub struct UndelegateRewardPool<'info> {
 }
 
 impl<'info> UndelegateRewardPool<'info> {
-    fn undelegate_reward_pool(&mut self) -> Result<()> {
-        // Only the mining pool's pool_maker can undelegate the reward pool
+    /// # Undelegate the reward state, and the reward shards passed in `shard_accounts`
+    fn undelegate_reward_pool(
+        &mut self,
+        shard_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        // Only the mining pool's admin can undelegate the reward pool
         require!(
-            self.payer.key() == self.mining_pool_pda.pool_maker,
+            self.payer.key() == self.mining_pool_pda.admin,
             MorErrorCodes::InvalidAuthority
         );
 
+        // Every shard must be one of this pool's, in index order
+        let mining_pool_pda_key = self.mining_pool_pda.key();
+        for (index, shard_info) in shard_accounts.iter().enumerate() {
+            let (expected, _bump) = Pubkey::find_program_address(
+                &[
+                    REWARD_SHARD_SEED,
+                    mining_pool_pda_key.as_ref(),
+                    &[index as u8],
+                ],
+                &crate::ID,
+            );
+            require_keys_eq!(
+                shard_info.key(),
+                expected,
+                MorErrorCodes::InvalidRewardShard
+            );
+        }
+
+        let reward_state_info = self.mining_pool_reward_state.to_account_info();
+        let mut accounts = vec![&reward_state_info];
+        accounts.extend(shard_accounts.iter());
+
         // undelegate the reward pool account to the ER
         commit_and_undelegate_accounts(
             self.payer.to_account_info().as_ref(),
-            vec![&self.mining_pool_reward_state.to_account_info()],
+            accounts,
             self.magic_context.as_ref(),
             self.magic_program.as_ref(),
         )?;
@@ -46,8 +72,11 @@ impl<'info> UndelegateRewardPool<'info> {
     }
 }
 
-pub fn undelegate_reward_pool_handler(ctx: Context<UndelegateRewardPool>) -> Result<()> {
-    ctx.accounts.undelegate_reward_pool()?;
+pub fn undelegate_reward_pool_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UndelegateRewardPool<'info>>,
+) -> Result<()> {
+    ctx.accounts
+        .undelegate_reward_pool(ctx.remaining_accounts)?;
 
     Ok(())
 }
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    helpers::{
+        constants::{MINER_SEED, MINING_POOL_SEED, STAKE_VAULT_SEED},
+        errors::MorErrorCodes,
+        utils::weighted_stake_timestamp,
+    },
+    states::{MinerAccountPoolPda, MiningPoolPda},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct Unstake<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MINER_SEED, authority.key().as_ref(), mining_pool_pda.key().as_ref()],
+        bump
+    )]
+    pub miner: Account<'info, MinerAccountPoolPda>,
+
+    #[account(
+        seeds = [MINING_POOL_SEED, mining_pool_pda.pool_maker.key().as_ref(), mining_pool_pda.mint.key().as_ref()],
+        bump = mining_pool_pda.bump,
+      )]
+    pub mining_pool_pda: Account<'info, MiningPoolPda>,
+
+    /// Token Accounts
+    #[account(
+        address = mining_pool_pda.mint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+        associated_token::token_program = token_program
+    )]
+    pub authority_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED, mining_pool_pda.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = mining_pool_pda,
+        token::token_program = token_program
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Programs
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Unstake<'info> {
+    /// # Return staked tokens to the miner
+    fn unstake(&mut self, amount: u64) -> Result<()> {
+        let miner = &mut self.miner;
+        require_gte!(
+            miner.staked_amount,
+            amount,
+            MorErrorCodes::InsufficientStakedAmount
+        );
+
+        // Unstaking forfeits the same share of the loyalty time earned
+        miner.last_staked_timestamp = if amount == miner.staked_amount {
+            0
+        } else {
+            weighted_stake_timestamp(
+                miner.last_staked_timestamp,
+                Clock::get()?.unix_timestamp,
+                amount,
+                miner.staked_amount,
+            )
+        };
+        miner.staked_amount -= amount;
+
+        let pool_maker_key = self.mining_pool_pda.pool_maker.key();
+        let mint_key = self.mining_pool_pda.mint.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            MINING_POOL_SEED,
+            pool_maker_key.as_ref(),
+            mint_key.as_ref(),
+            &[self.mining_pool_pda.bump],
+        ]];
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.stake_vault.to_account_info(),
+                    to: self.authority_ata.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    authority: self.mining_pool_pda.to_account_info(),
+                },
+                &signer_seeds,
+            ),
+            amount,
+            self.mint.decimals,
+        )?;
+
+        Ok(())
+    }
+}
+
+pub fn unstake_handler(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+    require_gt!(amount, 0, MorErrorCodes::InvalidAmount);
+
+    ctx.accounts.unstake(amount)?;
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ pub mod instructions;
 pub mod states;
 
 use instructions::*;
+use states::{EpochSolution, MultiplierTier, PoolMode, PowAlgorithm, VestingConfig};
 
 declare_id!("8BwLz8SvdFeT7qqd1nJFQMypTtuuWEpEEpVz6x6DA4Hm");
 
@@ -14,24 +15,78 @@ declare_id!("8BwLz8SvdFeT7qqd1nJFQMypTtuuWEpEEpVz6x6DA4Hm");
 pub mod mor_core {
     use super::*;
 
-    pub fn initialize_pool(ctx: Context<InitializePool>, amount: u64) -> Result<()> {
-        instructions::initialize_pool::initialize_pool_handler(ctx, amount)
+    pub fn initialize_protocol_config(
+        ctx: Context<InitializeProtocolConfig>,
+        treasury: Pubkey,
+        pool_creation_fee: u64,
+        protocol_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::initialize_protocol_config::initialize_protocol_config_handler(
+            ctx,
+            treasury,
+            pool_creation_fee,
+            protocol_fee_bps,
+        )
     }
 
-    pub fn initialize_miner(ctx: Context<InitializeMiner>) -> Result<()> {
-        instructions::initialize_miner::initialize_miner_handler(ctx)
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        amount: u64,
+        pow_algorithm: PowAlgorithm,
+        commission_bps: u16,
+        mode: PoolMode,
+    ) -> Result<()> {
+        instructions::initialize_pool::initialize_pool_handler(
+            ctx,
+            amount,
+            pow_algorithm,
+            commission_bps,
+            mode,
+        )
+    }
+
+    pub fn add_bonus_mint(
+        ctx: Context<AddBonusMint>,
+        amount: u64,
+        rate_bps: u32,
+        ends_at: i64,
+    ) -> Result<()> {
+        instructions::add_bonus_mint::add_bonus_mint_handler(ctx, amount, rate_bps, ends_at)
+    }
+
+    pub fn initialize_miner(ctx: Context<InitializeMiner>, proof: Vec<[u8; 32]>) -> Result<()> {
+        instructions::initialize_miner::initialize_miner_handler(ctx, proof)
     }
 
     pub fn delegate(ctx: Context<DelegateMinerAccount>) -> Result<()> {
         instructions::delegate::delegate_handler(ctx)
     }
 
-    pub fn submit_solution(ctx: Context<SubmitSolution>, nonce: u64, epoch: u64) -> Result<()> {
-        instructions::submit_solution::submit_solution_handler(ctx, nonce, epoch)
+    pub fn submit_solution(
+        ctx: Context<SubmitSolution>,
+        nonce: u64,
+        epoch: u64,
+        digest: [u8; 16],
+    ) -> Result<()> {
+        instructions::submit_solution::submit_solution_handler(ctx, nonce, epoch, digest)
+    }
+
+    pub fn submit_solutions(
+        ctx: Context<SubmitSolutions>,
+        solutions: Vec<EpochSolution>,
+    ) -> Result<()> {
+        instructions::submit_solutions::submit_solutions_handler(ctx, solutions)
+    }
+
+    pub fn claim_commission(ctx: Context<ClaimCommission>) -> Result<()> {
+        instructions::claim_commission::claim_commission_handler(ctx)
     }
 
-    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
-        instructions::claim_rewards::claim_rewards_handler(ctx)
+    pub fn claim_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimRewards<'info>>,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        instructions::claim_rewards::claim_rewards_handler(ctx, amount)
     }
 
     pub fn undelegate_miner(ctx: Context<UndelegateMinerAccount>) -> Result<()> {
@@ -42,7 +97,124 @@ pub mod mor_core {
         instructions::delegate_reward_pool::delegate_reward_pool_handler(ctx)
     }
 
-    pub fn undelegate_reward_pool(ctx: Context<UndelegateRewardPool>) -> Result<()> {
+    pub fn undelegate_reward_pool<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UndelegateRewardPool<'info>>,
+    ) -> Result<()> {
         instructions::undelegate_reward_pool::undelegate_reward_pool_handler(ctx)
     }
+
+    pub fn initialize_reward_shard(ctx: Context<InitializeRewardShard>, index: u8) -> Result<()> {
+        instructions::initialize_reward_shard::initialize_reward_shard_handler(ctx, index)
+    }
+
+    pub fn delegate_reward_shard(ctx: Context<DelegateRewardShard>, index: u8) -> Result<()> {
+        instructions::delegate_reward_shard::delegate_reward_shard_handler(ctx, index)
+    }
+
+    pub fn settle_epoch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleEpoch<'info>>,
+    ) -> Result<()> {
+        instructions::settle_epoch::settle_epoch_handler(ctx)
+    }
+
+    pub fn initialize_epoch_ledger(ctx: Context<InitializeEpochLedger>, epoch: u64) -> Result<()> {
+        instructions::initialize_epoch_ledger::initialize_epoch_ledger_handler(ctx, epoch)
+    }
+
+    pub fn delegate_epoch_ledger(ctx: Context<DelegateEpochLedger>, epoch: u64) -> Result<()> {
+        instructions::delegate_epoch_ledger::delegate_epoch_ledger_handler(ctx, epoch)
+    }
+
+    pub fn undelegate_epoch_ledger(ctx: Context<UndelegateEpochLedger>, epoch: u64) -> Result<()> {
+        instructions::undelegate_epoch_ledger::undelegate_epoch_ledger_handler(ctx, epoch)
+    }
+
+    pub fn close_epoch_ledger(ctx: Context<CloseEpochLedger>, epoch: u64) -> Result<()> {
+        instructions::close_epoch_ledger::close_epoch_ledger_handler(ctx, epoch)
+    }
+
+    pub fn migrate_miner(ctx: Context<MigrateMiner>) -> Result<()> {
+        instructions::migrate_miner::migrate_miner_handler(ctx)
+    }
+
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        instructions::migrate_pool::migrate_pool_handler(ctx)
+    }
+
+    pub fn migrate_reward_state(ctx: Context<MigrateRewardState>) -> Result<()> {
+        instructions::migrate_reward_state::migrate_reward_state_handler(ctx)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::propose_admin::propose_admin_handler(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::accept_admin_handler(ctx)
+    }
+
+    pub fn release_vested(ctx: Context<ReleaseVested>) -> Result<()> {
+        instructions::release_vested::release_vested_handler(ctx)
+    }
+
+    pub fn set_allowlist(
+        ctx: Context<SetAllowlist>,
+        allowlist_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::set_allowlist::set_allowlist_handler(ctx, allowlist_root)
+    }
+
+    pub fn set_commission(ctx: Context<SetCommission>, commission_bps: u16) -> Result<()> {
+        instructions::set_commission::set_commission_handler(ctx, commission_bps)
+    }
+
+    pub fn set_min_stake(ctx: Context<SetMinStake>, min_stake: u64) -> Result<()> {
+        instructions::set_min_stake::set_min_stake_handler(ctx, min_stake)
+    }
+
+    pub fn set_miner_beneficiary(
+        ctx: Context<SetMinerBeneficiary>,
+        beneficiary: Pubkey,
+    ) -> Result<()> {
+        instructions::set_miner_beneficiary::set_miner_beneficiary_handler(ctx, beneficiary)
+    }
+
+    pub fn set_miner_signer(ctx: Context<SetMinerSigner>, signer: Pubkey) -> Result<()> {
+        instructions::set_miner_signer::set_miner_signer_handler(ctx, signer)
+    }
+
+    pub fn set_multiplier_tiers(
+        ctx: Context<SetMultiplierTiers>,
+        multiplier_tiers: Vec<MultiplierTier>,
+    ) -> Result<()> {
+        instructions::set_multiplier_tiers::set_multiplier_tiers_handler(ctx, multiplier_tiers)
+    }
+
+    pub fn set_protocol_config(
+        ctx: Context<SetProtocolConfig>,
+        admin: Pubkey,
+        treasury: Pubkey,
+        pool_creation_fee: u64,
+        protocol_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_protocol_config::set_protocol_config_handler(
+            ctx,
+            admin,
+            treasury,
+            pool_creation_fee,
+            protocol_fee_bps,
+        )
+    }
+
+    pub fn set_vesting(ctx: Context<SetVesting>, vesting: Option<VestingConfig>) -> Result<()> {
+        instructions::set_vesting::set_vesting_handler(ctx, vesting)
+    }
+
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        instructions::stake::stake_handler(ctx, amount)
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        instructions::unstake::unstake_handler(ctx, amount)
+    }
 }
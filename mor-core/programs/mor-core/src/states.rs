@@ -1,5 +1,106 @@
 use anchor_lang::prelude::*;
 
+use crate::helpers::constants::{MAX_BONUS_MINTS, MAX_MULTIPLIER_TIERS, SHARD_EPOCH_SLOTS};
+
+/// Proof-of-work puzzle the miners of a pool solve
+#[derive(
+    AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default, PartialEq, Eq,
+)]
+pub enum PowAlgorithm {
+    /// SHA3-256(challenge || nonce)
+    #[default]
+    Sha3,
+    /// Equi-X over challenge || nonce: memory-hard to solve, cheap to verify
+    Equix,
+}
+
+/// Where a pool's rewards come from
+#[derive(
+    AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default, PartialEq, Eq,
+)]
+pub enum PoolMode {
+    /// Claims transfer out of the tokens deposited in the vault
+    #[default]
+    Vault,
+    /// The program holds the mint authority and claims mint new tokens
+    MintAuthority,
+}
+
+/// Stake bracket of a pool's multiplier table, in whole staked tokens
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MultiplierTier {
+    /// Whole staked tokens from which this tier applies
+    pub min_tokens: u64,
+    /// Bonus at `min_tokens`, in basis points
+    pub bonus_bps: u32,
+    /// Extra bonus per whole token above `min_tokens`, in basis points
+    pub bps_per_token: u32,
+}
+
+/// Second token a pool pays alongside its own mint, for a limited time
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BonusMint {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    /// Bonus tokens credited per main token a miner earns, in basis points
+    pub rate_bps: u32,
+    /// Unix timestamp after which solutions no longer earn this bonus
+    pub ends_at: i64,
+}
+
+/// Vesting schedule a pool applies to claimed rewards of its own mint.
+/// Bonus mints are not vested, claims pay them out right away.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VestingConfig {
+    /// Seconds after the first locked tokens before anything can be released
+    pub cliff_seconds: i64,
+    /// Seconds over which each claim vests linearly
+    pub duration_seconds: i64,
+}
+
+/// Proof-of-work solution for one epoch, as submitted in a batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EpochSolution {
+    pub epoch: u64,
+    pub nonce: u64,
+    /// Equi-X solution for Equix pools, zeroes for SHA3 pools
+    pub digest: [u8; 16],
+}
+
+/// Lifetime counters of a miner, kept by submit_solution and claim_rewards
+#[derive(
+    AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default, PartialEq, Eq,
+)]
+pub struct MinerStats {
+    pub total_solutions: u64,
+    /// Rewards credited to the miner, after the pool maker's commission
+    pub total_rewards: u64,
+    /// Rewards claimed, the protocol fee included
+    pub total_claimed: u64,
+    /// Epoch of the first solution, meaningless while total_solutions is 0
+    pub first_epoch_mined: u64,
+    /// Most leading zero bits of any solution
+    pub best_difficulty: u32,
+    /// Consecutive epochs mined, up to last_epoch_mined
+    pub current_streak: u32,
+}
+
+/// Solutions of one epoch counted by a reward shard, until settle_epoch folds
+/// them into the epoch's ledger
+#[derive(
+    AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default, PartialEq, Eq,
+)]
+pub struct EpochStats {
+    pub epoch: u64,
+    pub submissions: u32,
+    /// Credited to miners, pool maker commission included
+    pub total_rewards: u64,
+    /// Most leading zero bits of any solution
+    pub best_difficulty: u32,
+    /// Authority of the miner that achieved best_difficulty
+    pub best_miner: Pubkey,
+}
+
 #[derive(InitSpace)]
 #[account(discriminator = 1)]
 pub struct MiningPoolPda {
@@ -7,6 +108,21 @@ pub struct MiningPoolPda {
     pub mint: Pubkey,
     pub bump: u8,
     pub amount: u64,
+    pub pow_algorithm: PowAlgorithm,
+    pub mint_decimals: u8,
+    #[max_len(MAX_MULTIPLIER_TIERS)]
+    pub multiplier_tiers: Vec<MultiplierTier>, // sorted by min_tokens
+    pub commission_bps: u16, // pool maker's share of each credited reward
+    pub mode: PoolMode,
+    pub supply_cap: u64, // mint supply claims may not exceed, MintAuthority pools only
+    pub mint_authority_bump: u8,
+    #[max_len(MAX_BONUS_MINTS)]
+    pub bonus_mints: Vec<BonusMint>, // each paid from the pool PDA's ATA of that mint
+    pub vesting: Option<VestingConfig>, // claims of the pool's mint go to a vesting escrow when set, bonus mints never vest
+    pub allowlist_root: Option<[u8; 32]>, // Merkle root of the wallets allowed to mine, None is open
+    pub min_stake: u64,                   // staked base units a miner needs to submit solutions
+    pub admin: Pubkey, // signs config changes and claims the commission, starts as the pool maker
+    pub pending_admin: Pubkey, // proposed admin until it accepts, default means none
 }
 
 #[derive(InitSpace)]
@@ -15,12 +131,18 @@ pub struct MinerAccountPoolPda {
     pub authority: Pubkey,
     pub last_epoch_mined: u64,
     pub rewards: u64,
-    pub multiplier: u8,
+    pub multiplier: u32, // basis points, MULTIPLIER_BASE_BPS is 1x
     pub staked_amount: u64,
-    pub difficulty: u8,
+    pub difficulty: u8, // required leading zero bits of the solution hash
     pub pool: Pubkey,
     pub last_staked_timestamp: i64,
     pub bump: u8,
+    pub version: u8,
+    pub signer: Pubkey, // hot key that may submit solutions, default means authority only
+    pub beneficiary: Pubkey, // owner claims must pay, default lets the authority choose
+    #[max_len(MAX_BONUS_MINTS)]
+    pub bonus_rewards: Vec<u64>, // unclaimed rewards of each of the pool's bonus mints
+    pub stats: MinerStats,
 }
 
 #[derive(InitSpace)]
@@ -29,4 +151,66 @@ pub struct MiningPoolRewardState {
     pub pool_pda: Pubkey,
     pub amount: u64,
     pub bump: u8,
+    pub maker_rewards: u64, // commission the pool maker can claim
+    #[max_len(MAX_BONUS_MINTS)]
+    pub bonus_amounts: Vec<u64>, // remaining budget of each of the pool's bonus mints
+    pub settled_epoch: u64, // last epoch settle_epoch folded the reward shards in
+}
+
+/// Slice of a pool's reward budget that a subset of its miners credit from.
+/// Solutions write here instead of to the shared MiningPoolRewardState and
+/// EpochLedger, and settle_epoch folds the credits and epoch stats back in and
+/// splits the budget anew.
+#[derive(InitSpace)]
+#[account(discriminator = 7)]
+pub struct RewardShard {
+    pub pool: Pubkey,
+    pub index: u8,
+    pub budget: u64, // rewards this shard can still credit until the next settlement
+    pub credited: u64, // credited since the last settlement, commission included
+    pub commission: u64, // pool maker commission credited since the last settlement
+    #[max_len(MAX_BONUS_MINTS)]
+    pub bonus_budgets: Vec<u64>, // same as budget, for each of the pool's bonus mints
+    #[max_len(MAX_BONUS_MINTS)]
+    pub bonus_credited: Vec<u64>, // same as credited, for each of the pool's bonus mints
+    pub epoch_stats: [EpochStats; SHARD_EPOCH_SLOTS], // solutions of each epoch since the last settlement, at epoch_stats_slot
+    pub bump: u8,
+}
+
+/// What a pool's miners achieved in one epoch, folded in from the reward shards by settle_epoch
+#[derive(InitSpace)]
+#[account(discriminator = 6)]
+pub struct EpochLedger {
+    pub pool: Pubkey,
+    pub epoch: u64,
+    pub submissions: u32,
+    pub total_rewards: u64, // credited to miners, pool maker commission included
+    pub best_difficulty: u32, // most leading zero bits of any solution
+    pub best_miner: Pubkey, // authority of the miner that achieved best_difficulty
+    pub payer: Pubkey,      // paid the rent, which close_epoch_ledger returns to it
+    pub settled_epoch: u64, // last epoch settle_epoch folded the reward shards' stats in
+    pub bump: u8,
+}
+
+#[derive(InitSpace)]
+#[account(discriminator = 5)]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,       // wallet collecting the protocol's fees
+    pub pool_creation_fee: u64, // lamports initialize_pool charges, 0 for none
+    pub protocol_fee_bps: u16,  // share of each claim paid to the treasury
+    pub bump: u8,
+}
+
+#[derive(InitSpace)]
+#[account(discriminator = 4)]
+pub struct VestingEscrow {
+    pub miner: Pubkey,
+    pub mint: Pubkey,
+    pub deposited: u64, // locked since the schedule started, released tokens included
+    pub released: u64,
+    pub start_timestamp: i64, // linear vesting start, moved so deposits keep what vested
+    pub cliff_timestamp: i64, // nothing is released before
+    pub duration_seconds: i64,
+    pub bump: u8,
 }
@@ -0,0 +1,65 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use common::{account_data, epoch_ledger, error_code, mining_pool, process, TestAccount};
+use mor_core::helpers::constants::MAX_EPOCH_OFFSET;
+use mor_core::helpers::errors::MorErrorCodes;
+use mor_core::instruction;
+
+const EPOCH: u64 = 1_000;
+
+/// Accounts of close_epoch_ledger for the ledger of EPOCH, last settled at
+/// `settled_epoch`, in instruction order
+fn close_accounts(settled_epoch: u64) -> Vec<TestAccount> {
+    let payer = Pubkey::new_unique();
+    let (pool_key, pool) = mining_pool(Pubkey::new_unique(), Pubkey::new_unique(), 0);
+    let (ledger_key, mut ledger) = epoch_ledger(pool_key, EPOCH);
+    ledger.payer = payer;
+    ledger.settled_epoch = settled_epoch;
+
+    vec![
+        TestAccount::empty(payer),
+        TestAccount::program(pool_key, account_data(&pool)).read_only(),
+        TestAccount::program(ledger_key, account_data(&ledger)),
+    ]
+}
+
+fn close_epoch_ledger(accounts: &mut [TestAccount]) -> std::result::Result<(), ProgramError> {
+    process(
+        accounts,
+        &instruction::CloseEpochLedger { epoch: EPOCH }.data(),
+    )
+}
+
+#[test]
+fn returns_the_rent_of_a_settled_ledger_to_its_payer() {
+    let mut accounts = close_accounts(EPOCH + MAX_EPOCH_OFFSET + 1);
+    let rent = accounts[2].lamports;
+
+    close_epoch_ledger(&mut accounts).unwrap();
+
+    assert_eq!(accounts[0].lamports, rent);
+    assert_eq!(accounts[2].lamports, 0);
+}
+
+#[test]
+fn rejects_a_ledger_settled_while_its_epoch_took_solutions() {
+    let mut accounts = close_accounts(EPOCH + MAX_EPOCH_OFFSET);
+
+    assert_eq!(
+        close_epoch_ledger(&mut accounts),
+        Err(error_code(MorErrorCodes::EpochLedgerNotSettled))
+    );
+}
+
+#[test]
+fn rejects_a_payer_other_than_the_ledgers() {
+    let mut accounts = close_accounts(EPOCH + MAX_EPOCH_OFFSET + 1);
+    accounts[0] = TestAccount::empty(Pubkey::new_unique());
+
+    assert_eq!(
+        close_epoch_ledger(&mut accounts),
+        Err(error_code(ErrorCode::ConstraintAddress))
+    );
+}
@@ -0,0 +1,351 @@
+//! Runs mor-core's entrypoint on accounts laid out the way the runtime passes them,
+//! for cases the TypeScript tests can't set up on a validator, like old layouts.
+
+#![allow(dead_code)]
+
+use std::cell::Cell;
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    entrypoint::{deserialize, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER},
+    instruction::Instruction,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+};
+use anchor_lang::system_program;
+use mor_core::helpers::constants::{
+    DEFAULT_MULTIPLIER_TIERS, EPOCH_SEED, MINER_ACCOUNT_VERSION, MINER_SEED,
+    MINING_POOL_REWARD_SEED, MINING_POOL_SEED, MINT_AUTHORITY_SEED, MULTIPLIER_BASE_BPS,
+    REWARD_SHARDS, REWARD_SHARD_SEED,
+};
+use mor_core::helpers::utils::shard_share;
+use mor_core::states::{
+    EpochLedger, MinerAccountPoolPda, MinerStats, MiningPoolPda, MiningPoolRewardState, PoolMode,
+    PowAlgorithm, RewardShard,
+};
+
+thread_local! {
+    static SLOT: Cell<u64> = const { Cell::new(0) };
+}
+
+/// An account handed to `process`, written back once the instruction returns
+#[derive(Clone)]
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    /// An account owned by mor-core holding `data`, rent exempt
+    pub fn program(key: Pubkey, data: Vec<u8>) -> Self {
+        TestAccount {
+            key,
+            owner: mor_core::ID,
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            is_signer: false,
+            is_writable: true,
+            executable: false,
+        }
+    }
+
+    /// An account that doesn't exist yet
+    pub fn empty(key: Pubkey) -> Self {
+        TestAccount {
+            key,
+            owner: system_program::ID,
+            lamports: 0,
+            data: Vec::new(),
+            is_signer: false,
+            is_writable: true,
+            executable: false,
+        }
+    }
+
+    /// A wallet signing the transaction
+    pub fn signer(key: Pubkey, lamports: u64) -> Self {
+        TestAccount {
+            is_signer: true,
+            lamports,
+            ..TestAccount::empty(key)
+        }
+    }
+
+    pub fn system_program() -> Self {
+        TestAccount {
+            key: system_program::ID,
+            owner: Pubkey::default(),
+            lamports: 1,
+            data: Vec::new(),
+            is_signer: false,
+            is_writable: false,
+            executable: true,
+        }
+    }
+
+    pub fn read_only(self) -> Self {
+        TestAccount {
+            is_writable: false,
+            ..self
+        }
+    }
+
+    /// The account's data after its discriminator
+    pub fn load<T: AnchorDeserialize>(&self) -> T {
+        T::deserialize(&mut &self.data[1..]).unwrap()
+    }
+}
+
+/// Serializes `data` behind the account's 1-byte discriminator
+pub fn account_data<T: AnchorSerialize + Discriminator>(account: &T) -> Vec<u8> {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    account.serialize(&mut data).unwrap();
+    data
+}
+
+/// Sets the slot the Clock sysvar reports
+pub fn set_slot(slot: u64) {
+    SLOT.with(|cell| cell.set(slot));
+}
+
+/// Stands in for the runtime's syscalls: the Clock and Rent sysvars, and system
+/// program transfers, the only CPI the tested instructions make.
+struct Runtime;
+
+impl SyscallStubs for Runtime {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let slot = SLOT.with(Cell::get);
+        let clock = Clock {
+            slot,
+            unix_timestamp: slot as i64 * 400 / 1000,
+            ..Clock::default()
+        };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> std::result::Result<(), ProgramError> {
+        // SystemInstruction::Transfer is variant 2 followed by the lamports
+        assert_eq!(instruction.program_id, system_program::ID);
+        assert_eq!(instruction.data[..4], 2u32.to_le_bytes());
+        let lamports = u64::from_le_bytes(instruction.data[4..12].try_into().unwrap());
+        let find = |key: &Pubkey| account_infos.iter().find(|info| info.key == key).unwrap();
+        let from = find(&instruction.accounts[0].pubkey);
+        let to = find(&instruction.accounts[1].pubkey);
+        **from.try_borrow_mut_lamports()? = from
+            .lamports()
+            .checked_sub(lamports)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        **to.try_borrow_mut_lamports()? += lamports;
+        Ok(())
+    }
+}
+
+/// Runs one instruction of mor-core on `accounts` and writes the accounts back,
+/// unless it fails, as the runtime would
+pub fn process(accounts: &mut [TestAccount], data: &[u8]) -> std::result::Result<(), ProgramError> {
+    set_syscall_stubs(Box::new(Runtime));
+
+    let input = serialize(accounts, data);
+    // Leaked so the account infos can borrow it for 'static, as entry expects
+    let input: &'static mut [u64] = Box::leak(input.into_boxed_slice());
+    let (program_id, account_infos, data) = unsafe { deserialize(input.as_mut_ptr() as *mut u8) };
+    let account_infos: &'static [AccountInfo<'static>] =
+        Box::leak(account_infos.into_boxed_slice());
+
+    mor_core::entry(program_id, account_infos, data)?;
+
+    for account in accounts.iter_mut() {
+        let info = account_infos
+            .iter()
+            .find(|info| *info.key == account.key)
+            .unwrap();
+        account.owner = *info.owner;
+        account.lamports = info.lamports();
+        account.data = info.try_borrow_data()?.to_vec();
+    }
+    Ok(())
+}
+
+/// Lays the accounts and instruction data out as the runtime's aligned input
+fn serialize(accounts: &[TestAccount], data: &[u8]) -> Vec<u64> {
+    let mut input = Vec::new();
+    input.extend_from_slice(&(accounts.len() as u64).to_le_bytes());
+    for (index, account) in accounts.iter().enumerate() {
+        if let Some(first) = accounts[..index]
+            .iter()
+            .position(|other| other.key == account.key)
+        {
+            input.push(first as u8);
+            input.extend_from_slice(&[0; 7]);
+            continue;
+        }
+        // The runtime merges the privileges of an account passed more than once
+        let same = accounts.iter().filter(|other| other.key == account.key);
+        input.push(NON_DUP_MARKER);
+        input.push(same.clone().any(|other| other.is_signer) as u8);
+        input.push(same.clone().any(|other| other.is_writable) as u8);
+        input.push(account.executable as u8);
+        input.extend_from_slice(&[0; 4]);
+        input.extend_from_slice(account.key.as_ref());
+        input.extend_from_slice(account.owner.as_ref());
+        input.extend_from_slice(&account.lamports.to_le_bytes());
+        input.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+        input.extend_from_slice(&account.data);
+        input.resize(input.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        input.resize(input.len().next_multiple_of(size_of::<u64>()), 0);
+        input.extend_from_slice(&u64::MAX.to_le_bytes());
+    }
+    input.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    input.extend_from_slice(data);
+    input.extend_from_slice(mor_core::ID.as_ref());
+
+    // Aligned for the u64 reads deserialize makes
+    let mut aligned = vec![0u64; input.len().div_ceil(size_of::<u64>())];
+    unsafe {
+        std::ptr::copy_nonoverlapping(input.as_ptr(), aligned.as_mut_ptr() as *mut u8, input.len())
+    };
+    aligned
+}
+
+/// The custom error code an instruction failing with `error` returns
+pub fn error_code(error: impl Into<anchor_lang::error::Error>) -> ProgramError {
+    error.into().into()
+}
+
+/// A SHA3 vault pool of `maker` with `amount` tokens left, as initialize_pool creates it
+pub fn mining_pool(maker: Pubkey, mint: Pubkey, amount: u64) -> (Pubkey, MiningPoolPda) {
+    let program_id = mor_core::ID;
+    let (key, bump) = Pubkey::find_program_address(
+        &[MINING_POOL_SEED, maker.as_ref(), mint.as_ref()],
+        &program_id,
+    );
+    let (_, mint_authority_bump) =
+        Pubkey::find_program_address(&[MINT_AUTHORITY_SEED, key.as_ref()], &program_id);
+    let pool = MiningPoolPda {
+        pool_maker: maker,
+        mint,
+        bump,
+        amount,
+        pow_algorithm: PowAlgorithm::Sha3,
+        mint_decimals: 6,
+        multiplier_tiers: DEFAULT_MULTIPLIER_TIERS.to_vec(),
+        commission_bps: 0,
+        mode: PoolMode::Vault,
+        supply_cap: 0,
+        mint_authority_bump,
+        bonus_mints: Vec::new(),
+        vesting: None,
+        allowlist_root: None,
+        min_stake: 0,
+        admin: maker,
+        pending_admin: Pubkey::default(),
+    };
+    (key, pool)
+}
+
+/// The reward state of a pool, settled at `settled_epoch`
+pub fn reward_state(
+    pool_key: Pubkey,
+    pool: &MiningPoolPda,
+    settled_epoch: u64,
+) -> (Pubkey, MiningPoolRewardState) {
+    let (key, bump) = Pubkey::find_program_address(
+        &[
+            MINING_POOL_REWARD_SEED,
+            pool.pool_maker.as_ref(),
+            pool_key.as_ref(),
+        ],
+        &mor_core::ID,
+    );
+    let reward_state = MiningPoolRewardState {
+        pool_pda: pool_key,
+        amount: pool.amount,
+        bump,
+        maker_rewards: 0,
+        bonus_amounts: Vec::new(),
+        settled_epoch,
+    };
+    (key, reward_state)
+}
+
+/// Reward shard `index` of a pool holding its share of `amount`
+pub fn reward_shard(pool_key: Pubkey, index: u8, amount: u64) -> (Pubkey, RewardShard) {
+    assert!(index < REWARD_SHARDS);
+    let (key, bump) = Pubkey::find_program_address(
+        &[REWARD_SHARD_SEED, pool_key.as_ref(), &[index]],
+        &mor_core::ID,
+    );
+    let shard = RewardShard {
+        pool: pool_key,
+        index,
+        budget: shard_share(amount, index),
+        credited: 0,
+        commission: 0,
+        bonus_budgets: Vec::new(),
+        bonus_credited: Vec::new(),
+        epoch_stats: Default::default(),
+        bump,
+    };
+    (key, shard)
+}
+
+/// A miner of `authority` that never mined, as initialize_miner creates it
+pub fn miner(authority: Pubkey, pool_key: Pubkey) -> (Pubkey, MinerAccountPoolPda) {
+    let (key, bump) = Pubkey::find_program_address(
+        &[MINER_SEED, authority.as_ref(), pool_key.as_ref()],
+        &mor_core::ID,
+    );
+    let miner = MinerAccountPoolPda {
+        authority,
+        last_epoch_mined: u64::MAX,
+        rewards: 0,
+        multiplier: MULTIPLIER_BASE_BPS,
+        staked_amount: 0,
+        difficulty: 0,
+        pool: pool_key,
+        last_staked_timestamp: 0,
+        bump,
+        version: MINER_ACCOUNT_VERSION,
+        signer: Pubkey::default(),
+        beneficiary: Pubkey::default(),
+        bonus_rewards: Vec::new(),
+        stats: MinerStats::default(),
+    };
+    (key, miner)
+}
+
+/// A pool's empty ledger of `epoch`, as initialize_epoch_ledger creates it
+pub fn epoch_ledger(pool_key: Pubkey, epoch: u64) -> (Pubkey, EpochLedger) {
+    let (key, bump) = Pubkey::find_program_address(
+        &[EPOCH_SEED, pool_key.as_ref(), &epoch.to_le_bytes()],
+        &mor_core::ID,
+    );
+    let ledger = EpochLedger {
+        pool: pool_key,
+        epoch,
+        submissions: 0,
+        total_rewards: 0,
+        best_difficulty: 0,
+        best_miner: Pubkey::default(),
+        payer: Pubkey::default(),
+        settled_epoch: 0,
+        bump,
+    };
+    (key, ledger)
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use common::{error_code, process, TestAccount};
+use mor_core::helpers::constants::{
+    DEFAULT_MULTIPLIER_TIERS, MINER_ACCOUNT_VERSION, MINER_SEED, MINING_POOL_REWARD_SEED,
+    MINING_POOL_SEED, MINT_AUTHORITY_SEED, MULTIPLIER_BASE_BPS,
+};
+use mor_core::helpers::errors::MorErrorCodes;
+use mor_core::instruction;
+use mor_core::states::{MinerAccountPoolPda, MiningPoolPda, MiningPoolRewardState, PoolMode};
+
+const POOL_AMOUNT: u64 = 1_000_000_000;
+const DECIMALS: u8 = 6;
+
+/// A pool created before the pool settings, in the layout of the time
+struct OldPool {
+    maker: Pubkey,
+    mint: Pubkey,
+    pool: Pubkey,
+    pool_bump: u8,
+    account: TestAccount,
+}
+
+impl OldPool {
+    fn new() -> Self {
+        let (maker, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (pool, pool_bump) = Pubkey::find_program_address(
+            &[MINING_POOL_SEED, maker.as_ref(), mint.as_ref()],
+            &mor_core::ID,
+        );
+        let mut pool_data = vec![1];
+        (maker, mint, pool_bump, POOL_AMOUNT)
+            .serialize(&mut pool_data)
+            .unwrap();
+        OldPool {
+            maker,
+            mint,
+            pool,
+            pool_bump,
+            account: TestAccount::program(pool, pool_data),
+        }
+    }
+}
+
+/// A mint account of the token program, as InterfaceAccount<Mint> loads it
+fn mint_account(key: Pubkey) -> TestAccount {
+    let mut data = vec![0; 82];
+    data[44] = DECIMALS;
+    data[45] = 1; // is_initialized
+    TestAccount {
+        owner: anchor_spl::token::ID,
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        ..TestAccount::empty(key).read_only()
+    }
+}
+
+fn migrate_pool(old: &OldPool) -> (Vec<TestAccount>, std::result::Result<(), ProgramError>) {
+    let (mint_authority, _) =
+        Pubkey::find_program_address(&[MINT_AUTHORITY_SEED, old.pool.as_ref()], &mor_core::ID);
+    let mut accounts = vec![
+        TestAccount::signer(Pubkey::new_unique(), 1_000_000_000),
+        old.account.clone(),
+        TestAccount::empty(old.maker).read_only(),
+        mint_account(old.mint),
+        TestAccount::empty(mint_authority).read_only(),
+        TestAccount::system_program(),
+    ];
+    let result = process(&mut accounts, &instruction::MigratePool {}.data());
+    (accounts, result)
+}
+
+#[test]
+fn migrates_an_old_pool_with_its_reward_state_and_miners() {
+    let old = OldPool::new();
+
+    let (accounts, result) = migrate_pool(&old);
+    result.unwrap();
+    let pool_account = accounts[1].clone();
+    let pool: MiningPoolPda = pool_account.load();
+    assert_eq!((pool.pool_maker, pool.mint), (old.maker, old.mint));
+    assert_eq!((pool.bump, pool.amount), (old.pool_bump, POOL_AMOUNT));
+    assert_eq!(pool.mint_decimals, DECIMALS);
+    assert_eq!(pool.multiplier_tiers, DEFAULT_MULTIPLIER_TIERS.to_vec());
+    assert_eq!((pool.commission_bps, pool.mode), (0, PoolMode::Vault));
+    assert_eq!(pool.admin, old.maker);
+    assert!(pool_account.lamports >= Rent::default().minimum_balance(pool_account.data.len()));
+
+    // The reward state and miners load the pool, so they migrate once it has
+    let (reward_state_key, reward_state_bump) = Pubkey::find_program_address(
+        &[
+            MINING_POOL_REWARD_SEED,
+            old.maker.as_ref(),
+            old.pool.as_ref(),
+        ],
+        &mor_core::ID,
+    );
+    let mut reward_state_data = vec![3];
+    (old.pool, POOL_AMOUNT, old.pool_bump)
+        .serialize(&mut reward_state_data)
+        .unwrap();
+    let mut accounts = vec![
+        TestAccount::signer(Pubkey::new_unique(), 1_000_000_000),
+        TestAccount::program(reward_state_key, reward_state_data),
+        pool_account.clone().read_only(),
+        TestAccount::system_program(),
+    ];
+    process(&mut accounts, &instruction::MigrateRewardState {}.data()).unwrap();
+    let reward_state: MiningPoolRewardState = accounts[1].load();
+    assert_eq!(reward_state.amount, POOL_AMOUNT);
+    assert_eq!(reward_state.bump, reward_state_bump);
+
+    let authority = Pubkey::new_unique();
+    let (miner_key, miner_bump) = Pubkey::find_program_address(
+        &[MINER_SEED, authority.as_ref(), old.pool.as_ref()],
+        &mor_core::ID,
+    );
+    let mut miner_data = vec![2];
+    (
+        authority, 7u64, 500u64, 2u8, 0u64, 1u8, old.pool, 0i64, miner_bump,
+    )
+        .serialize(&mut miner_data)
+        .unwrap();
+    let mut accounts = vec![
+        TestAccount::signer(authority, 1_000_000_000),
+        TestAccount::program(miner_key, miner_data),
+        pool_account.read_only(),
+        TestAccount::system_program(),
+    ];
+    process(&mut accounts, &instruction::MigrateMiner {}.data()).unwrap();
+    let miner: MinerAccountPoolPda = accounts[1].load();
+    assert_eq!((miner.last_epoch_mined, miner.rewards), (7, 500));
+    assert_eq!(miner.multiplier, 2 * MULTIPLIER_BASE_BPS);
+    assert_eq!(miner.difficulty, 8);
+    assert_eq!(miner.version, MINER_ACCOUNT_VERSION);
+}
+
+#[test]
+fn rejects_a_pool_already_migrated() {
+    let mut old = OldPool::new();
+    let (accounts, result) = migrate_pool(&old);
+    result.unwrap();
+    old.account = accounts[1].clone();
+
+    assert_eq!(
+        migrate_pool(&old).1,
+        Err(error_code(MorErrorCodes::AlreadyMigrated))
+    );
+}
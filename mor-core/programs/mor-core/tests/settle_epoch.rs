@@ -0,0 +1,100 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use common::{
+    account_data, epoch_ledger, error_code, mining_pool, process, reward_shard, reward_state,
+    set_slot, TestAccount,
+};
+use mor_core::helpers::constants::{EPOCH_SLOT_LENGTH, REWARD_SHARDS};
+use mor_core::helpers::errors::MorErrorCodes;
+use mor_core::helpers::utils::epoch_stats_slot;
+use mor_core::instruction;
+use mor_core::states::{EpochLedger, EpochStats, RewardShard};
+
+const POOL_AMOUNT: u64 = 1_000_000_000;
+const EPOCH: u64 = 1_000;
+
+/// Accounts of settle_epoch for a pool whose shards counted `stats`, given as
+/// (shard index, stats), followed by the pool's ledgers of `ledger_epochs`
+fn settle_accounts(stats: &[(u8, EpochStats)], ledger_epochs: &[u64]) -> Vec<TestAccount> {
+    let (pool_key, pool) = mining_pool(Pubkey::new_unique(), Pubkey::new_unique(), POOL_AMOUNT);
+    let (reward_state_key, reward_state) = reward_state(pool_key, &pool, EPOCH - 1);
+
+    let mut accounts = vec![
+        TestAccount::program(pool_key, account_data(&pool)).read_only(),
+        TestAccount::program(reward_state_key, account_data(&reward_state)),
+    ];
+    for index in 0..REWARD_SHARDS {
+        let (shard_key, mut shard) = reward_shard(pool_key, index, POOL_AMOUNT);
+        for (_, shard_stats) in stats.iter().filter(|(shard, _)| *shard == index) {
+            shard.epoch_stats[epoch_stats_slot(shard_stats.epoch)] = *shard_stats;
+        }
+        accounts.push(TestAccount::program(shard_key, account_data(&shard)));
+    }
+    for &epoch in ledger_epochs {
+        let (ledger_key, ledger) = epoch_ledger(pool_key, epoch);
+        accounts.push(TestAccount::program(ledger_key, account_data(&ledger)));
+    }
+    accounts
+}
+
+fn settle_epoch(accounts: &mut [TestAccount]) -> std::result::Result<(), ProgramError> {
+    set_slot(EPOCH * EPOCH_SLOT_LENGTH);
+    process(accounts, &instruction::SettleEpoch {}.data())
+}
+
+fn stats(epoch: u64, submissions: u32, best_difficulty: u32, best_miner: Pubkey) -> EpochStats {
+    EpochStats {
+        epoch,
+        submissions,
+        total_rewards: submissions as u64 * 100,
+        best_difficulty,
+        best_miner,
+    }
+}
+
+#[test]
+fn folds_the_shards_stats_into_the_passed_ledgers() {
+    let (best, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut accounts = settle_accounts(
+        &[
+            (1, stats(EPOCH - 1, 3, 20, best)),
+            (5, stats(EPOCH - 1, 2, 12, other)),
+            (5, stats(EPOCH, 1, 30, other)),
+        ],
+        &[EPOCH - 1],
+    );
+
+    settle_epoch(&mut accounts).unwrap();
+
+    let ledger: EpochLedger = accounts[2 + REWARD_SHARDS as usize].load();
+    assert_eq!(ledger.submissions, 5);
+    assert_eq!(ledger.total_rewards, 500);
+    assert_eq!((ledger.best_difficulty, ledger.best_miner), (20, best));
+    assert_eq!(ledger.settled_epoch, EPOCH);
+
+    // Folded stats are cleared, the epoch without a ledger waits for a later settlement
+    let shard: RewardShard = accounts[2 + 5].load();
+    assert_eq!(
+        shard.epoch_stats[epoch_stats_slot(EPOCH - 1)],
+        EpochStats::default()
+    );
+    assert_eq!(
+        shard.epoch_stats[epoch_stats_slot(EPOCH)],
+        stats(EPOCH, 1, 30, other)
+    );
+}
+
+#[test]
+fn rejects_a_ledger_of_another_pool() {
+    let mut accounts = settle_accounts(&[], &[]);
+    let (other_pool, _) = mining_pool(Pubkey::new_unique(), Pubkey::new_unique(), POOL_AMOUNT);
+    let (ledger_key, ledger) = epoch_ledger(other_pool, EPOCH - 1);
+    accounts.push(TestAccount::program(ledger_key, account_data(&ledger)));
+
+    assert_eq!(
+        settle_epoch(&mut accounts),
+        Err(error_code(MorErrorCodes::InvalidEpochLedger))
+    );
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use common::{
+    account_data, error_code, miner, mining_pool, process, reward_shard, reward_state, set_slot,
+    TestAccount,
+};
+use mor_core::helpers::constants::EPOCH_SLOT_LENGTH;
+use mor_core::helpers::errors::MorErrorCodes;
+use mor_core::helpers::utils::{epoch_stats_slot, reward_shard_index};
+use mor_core::instruction;
+use mor_core::states::{EpochSolution, MinerAccountPoolPda, RewardShard};
+
+const POOL_AMOUNT: u64 = 1_000_000_000;
+const EPOCH: u64 = 1_000;
+
+/// Accounts of submit_solutions for a fresh authority, in instruction order
+fn submit_accounts() -> Vec<TestAccount> {
+    let authority = Pubkey::new_unique();
+    let (pool_key, pool) = mining_pool(Pubkey::new_unique(), Pubkey::new_unique(), POOL_AMOUNT);
+    let (reward_state_key, reward_state) = reward_state(pool_key, &pool, EPOCH);
+    let (shard_key, shard) = reward_shard(pool_key, reward_shard_index(&authority), POOL_AMOUNT);
+    let (miner_key, miner) = miner(authority, pool_key);
+
+    vec![
+        TestAccount::program(miner_key, account_data(&miner)),
+        TestAccount::program(pool_key, account_data(&pool)).read_only(),
+        TestAccount::program(reward_state_key, account_data(&reward_state)).read_only(),
+        TestAccount::program(shard_key, account_data(&shard)),
+        TestAccount::empty(authority).read_only(),
+        TestAccount::signer(authority, 1_000_000_000),
+    ]
+}
+
+fn submit_solutions(
+    accounts: &mut [TestAccount],
+    epochs: &[u64],
+) -> std::result::Result<(), ProgramError> {
+    // The miner starts at difficulty 0, so any nonce solves it
+    let solutions = epochs
+        .iter()
+        .map(|&epoch| EpochSolution {
+            epoch,
+            nonce: 0,
+            digest: [0; 16],
+        })
+        .collect();
+    set_slot(EPOCH * EPOCH_SLOT_LENGTH);
+    process(accounts, &instruction::SubmitSolutions { solutions }.data())
+}
+
+#[test]
+fn credits_each_solution_of_the_batch() {
+    let epochs = [EPOCH, EPOCH + 1];
+    let mut accounts = submit_accounts();
+
+    submit_solutions(&mut accounts, &epochs).unwrap();
+
+    let miner: MinerAccountPoolPda = accounts[0].load();
+    assert_eq!(miner.last_epoch_mined, EPOCH + 1);
+    assert_eq!(miner.stats.total_solutions, 2);
+    assert!(miner.rewards > 0);
+    // Each epoch is counted on the shard until settle_epoch folds it into its ledger
+    let shard: RewardShard = accounts[3].load();
+    for epoch in epochs {
+        let stats = shard.epoch_stats[epoch_stats_slot(epoch)];
+        assert_eq!((stats.epoch, stats.submissions), (epoch, 1));
+        assert!(stats.total_rewards > 0);
+    }
+}
+
+#[test]
+fn rejects_a_batch_without_a_miner() {
+    let mut accounts = submit_accounts();
+    accounts[0] = TestAccount::empty(accounts[0].key);
+
+    assert_eq!(
+        submit_solutions(&mut accounts, &[EPOCH]),
+        Err(error_code(MorErrorCodes::MinerNotInitialized))
+    );
+}
+
+#[test]
+fn rejects_a_batch_without_a_reward_state() {
+    let mut accounts = submit_accounts();
+    accounts[2] = TestAccount::empty(accounts[2].key).read_only();
+
+    assert_eq!(
+        submit_solutions(&mut accounts, &[EPOCH]),
+        Err(error_code(MorErrorCodes::RewardStateNotInitialized))
+    );
+}
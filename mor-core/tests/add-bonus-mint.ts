@@ -0,0 +1,122 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+import { BN } from "bn.js";
+
+describe("Add Bonus Mint", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      wallet.payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const miningPoolRewardState = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool_reward"),
+      wallet.payer.publicKey.toBuffer(),
+      miningPoolPda.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  it("Add Bonus Mint", async () => {
+    // Runs before delegate-reward-pool, the reward state holds the bonus budget
+    const bonusMint = await createMint(
+      connection,
+      wallet.payer,
+      wallet.publicKey,
+      null,
+      0
+    );
+    const adminAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      wallet.payer,
+      bonusMint,
+      wallet.publicKey
+    );
+    await mintTo(
+      connection,
+      wallet.payer,
+      bonusMint,
+      adminAta.address,
+      wallet.publicKey,
+      1_000
+    );
+
+    const bonusVault = getAssociatedTokenAddressSync(
+      bonusMint,
+      miningPoolPda,
+      true,
+      TOKEN_PROGRAM_ID
+    );
+    const endsAt = Math.floor(Date.now() / 1000) + 7 * 86_400;
+
+    const tx = await program.methods
+      .addBonusMint(new BN(1_000), 5_000, new BN(endsAt)) // 0.5 bonus token per token mined, for a week
+      .accountsStrict({
+        admin: wallet.payer.publicKey,
+        miningPoolPda: miningPoolPda,
+        miningPoolRewardState: miningPoolRewardState,
+        bonusMint: bonusMint,
+        adminAta: adminAta.address,
+        bonusVault: bonusVault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    console.log("Transaction Signature:", tx);
+
+    const miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    const bonus = miningPoolAccount.bonusMints.find((b) =>
+      b.mint.equals(bonusMint)
+    );
+    assert.ok(bonus);
+    assert.equal(bonus.rateBps, 5_000);
+
+    const rewardState = await program.account.miningPoolRewardState.fetch(
+      miningPoolRewardState
+    );
+    const index = miningPoolAccount.bonusMints.indexOf(bonus);
+    assert.equal(rewardState.bonusAmounts[index].toString(), "1000");
+  });
+});
@@ -0,0 +1,98 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("Claim Commission", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      wallet.payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const miningPoolRewardState = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool_reward"),
+      wallet.payer.publicKey.toBuffer(),
+      miningPoolPda.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const mintAuthority = PublicKey.findProgramAddressSync(
+    [Buffer.from("mint"), miningPoolPda.toBuffer()],
+    program.programId
+  )[0];
+
+  it("Claim Commission", async () => {
+    const vault = getAssociatedTokenAddressSync(
+      TEST_TOKEN,
+      miningPoolPda,
+      true,
+      TOKEN_PROGRAM_ID
+    );
+
+    const recipientTokenAccount = await getOrCreateAssociatedTokenAccount(
+      connection,
+      wallet.payer,
+      TEST_TOKEN,
+      wallet.payer.publicKey
+    );
+
+    // The reward state must be undelegated so the base layer can write to it
+    const tx = await program.methods
+      .claimCommission()
+      .accountsStrict({
+        admin: wallet.payer.publicKey,
+        miningPoolPda: miningPoolPda,
+        miningPoolRewardState: miningPoolRewardState,
+        vault: vault,
+        mint: TEST_TOKEN,
+        mintAuthority: mintAuthority,
+        recipientAta: recipientTokenAccount.address,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    console.log("Transaction Signature:", tx);
+
+    const rewardState = await program.account.miningPoolRewardState.fetch(
+      miningPoolRewardState
+    );
+    assert.equal(rewardState.makerRewards.toString(), "0");
+  });
+});
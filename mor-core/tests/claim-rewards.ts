@@ -2,11 +2,14 @@ import * as anchor from "@coral-xyz/anchor";
 import { PublicKey, SystemProgram } from "@solana/web3.js";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
   getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
+  mintTo,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { getKeypairFromFile } from "@solana-developers/helpers";
+import { assert } from "chai";
 
 import { MorCore } from "../target/types/mor_core";
 
@@ -61,6 +64,11 @@ describe("Claim Rewards", () => {
       TOKEN_PROGRAM_ID
     );
 
+    const mintAuthority = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint"), miningPoolPda.toBuffer()],
+      program.programId
+    )[0];
+
     const recipientTokenAccount = await getOrCreateAssociatedTokenAccount(
       connection,
       wallet.payer,
@@ -68,22 +76,180 @@ describe("Claim Rewards", () => {
       wallet.payer.publicKey
     );
 
+    // Every bonus mint is paid from its vault to the wallet's ATA, in pool order
+    const miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    const bonusAccounts = miningPoolAccount.bonusMints.flatMap((bonus) => [
+      { pubkey: bonus.mint, isSigner: false, isWritable: false },
+      {
+        pubkey: getAssociatedTokenAddressSync(
+          bonus.mint,
+          miningPoolPda,
+          true,
+          TOKEN_PROGRAM_ID
+        ),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: getAssociatedTokenAddressSync(
+          bonus.mint,
+          wallet.payer.publicKey,
+          false,
+          TOKEN_PROGRAM_ID
+        ),
+        isSigner: false,
+        isWritable: true,
+      },
+    ]);
+
+    // The protocol's fee on each claim goes to the treasury's token account
+    const protocolConfig = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config")],
+      program.programId
+    )[0];
+    const protocolConfigAccount = await program.account.protocolConfig.fetch(
+      protocolConfig
+    );
+    const treasuryAta = getAssociatedTokenAddressSync(
+      TEST_TOKEN,
+      protocolConfigAccount.treasury,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    // Claim half to the wallet, then the rest
+    const minerAccount = await program.account.minerAccountPoolPda.fetch(
+      minerPda
+    );
+    const half = minerAccount.rewards.divn(2);
+    if (half.gtn(0)) {
+      const partialTx = await program.methods
+        .claimRewards(half)
+        .accountsStrict({
+          miner: minerPda,
+          mint: TEST_TOKEN,
+          mintAuthority: mintAuthority,
+          miningPoolPda: miningPoolPda,
+          vault: vault,
+          beneficiary: wallet.payer.publicKey,
+          recipientAta: recipientTokenAccount.address,
+          vestingEscrow: null,
+          escrowVault: null,
+          authority: wallet.payer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([wallet.payer])
+        .rpc();
+      console.log("Partial Claim Signature:", partialTx);
+    }
+
     const tx = await program.methods
-      .claimRewards()
+      .claimRewards(null)
       .accountsStrict({
         miner: minerPda,
         mint: TEST_TOKEN,
+        mintAuthority: mintAuthority,
         miningPoolPda: miningPoolPda,
         vault: vault,
+        beneficiary: wallet.payer.publicKey,
         recipientAta: recipientTokenAccount.address,
+        vestingEscrow: null, // the test pool doesn't vest
+        escrowVault: null,
+        protocolConfig: protocolConfig,
+        treasury: protocolConfigAccount.treasury,
+        treasuryAta: treasuryAta,
         authority: wallet.payer.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       })
+      .remainingAccounts(bonusAccounts)
       .signers([wallet.payer])
       .rpc();
 
     console.log("Transaction Signature:", tx);
   });
+
+  it("Reject a claim paid in another mint the pool holds", async () => {
+    // The pool PDA owns a vault for every bonus mint, like this one
+    const otherMint = await createMint(
+      connection,
+      wallet.payer,
+      wallet.publicKey,
+      null,
+      0
+    );
+    const otherVault = await getOrCreateAssociatedTokenAccount(
+      connection,
+      wallet.payer,
+      otherMint,
+      miningPoolPda,
+      true
+    );
+    await mintTo(
+      connection,
+      wallet.payer,
+      otherMint,
+      otherVault.address,
+      wallet.publicKey,
+      1_000
+    );
+
+    const protocolConfig = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config")],
+      program.programId
+    )[0];
+    const protocolConfigAccount = await program.account.protocolConfig.fetch(
+      protocolConfig
+    );
+
+    try {
+      await program.methods
+        .claimRewards(null)
+        .accountsStrict({
+          miner: minerPda,
+          mint: otherMint,
+          mintAuthority: PublicKey.findProgramAddressSync(
+            [Buffer.from("mint"), miningPoolPda.toBuffer()],
+            program.programId
+          )[0],
+          miningPoolPda: miningPoolPda,
+          vault: otherVault.address,
+          beneficiary: wallet.payer.publicKey,
+          recipientAta: getAssociatedTokenAddressSync(
+            otherMint,
+            wallet.payer.publicKey,
+            false,
+            TOKEN_PROGRAM_ID
+          ),
+          vestingEscrow: null,
+          escrowVault: null,
+          protocolConfig: protocolConfig,
+          treasury: protocolConfigAccount.treasury,
+          treasuryAta: getAssociatedTokenAddressSync(
+            otherMint,
+            protocolConfigAccount.treasury,
+            false,
+            TOKEN_PROGRAM_ID
+          ),
+          authority: wallet.payer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([wallet.payer])
+        .rpc();
+      assert.fail("claim_rewards paid out of another mint's vault");
+    } catch (error) {
+      assert.include(error.toString(), "ConstraintAddress");
+    }
+    const vaultAfter = await connection.getTokenAccountBalance(
+      otherVault.address
+    );
+    assert.equal(vaultAfter.value.amount, "1000");
+  });
 });
@@ -36,6 +36,17 @@ describe("Delegate Reward Pool", () => {
   let miningPoolPda: PublicKey;
   let miningPoolRewardStatePda: PublicKey;
 
+  const REWARD_SHARDS = 8;
+  const rewardShardPda = (index: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("reward_shard"),
+        miningPoolPda.toBuffer(),
+        Buffer.from([index]),
+      ],
+      program.programId
+    )[0];
+
   it("Delegate reward pool to ER", async () => {
     miningPoolPda = anchor.web3.PublicKey.findProgramAddressSync(
       [
@@ -88,4 +99,35 @@ describe("Delegate Reward Pool", () => {
       `${duration}ms (Base Layer) Delegate Reward Pool txHash: ${txHash}`
     );
   });
+
+  it("Delegate reward shards to ER", async () => {
+    // Solutions credit the shards, so they live on the ER next to the reward state
+    for (let index = 0; index < REWARD_SHARDS; index++) {
+      const start = Date.now();
+      let tx = await program.methods
+        .delegateRewardShard(index)
+        .accountsPartial({
+          payer: provider.wallet.publicKey,
+          rewardShard: rewardShardPda(index),
+          miningPoolPda: miningPoolPda,
+        })
+        .transaction();
+
+      tx.feePayer = provider.wallet.publicKey;
+      tx.recentBlockhash = (
+        await provider.connection.getLatestBlockhash()
+      ).blockhash;
+
+      tx = await providerEphemeralRollup.wallet.signTransaction(tx);
+      const txHash = await provider.sendAndConfirm(tx, [], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+
+      const duration = Date.now() - start;
+      console.log(
+        `${duration}ms (Base Layer) Delegate Reward Shard ${index} txHash: ${txHash}`
+      );
+    }
+  });
 });
@@ -55,11 +55,11 @@ describe("Fetch Miner", () => {
       );
 
       console.log("Miner Account Details:");
-      console.log("Difficulty:", minerAccount.difficulty);
+      console.log("Difficulty (bits):", minerAccount.difficulty);
       console.log("Authority:", minerAccount.authority.toString());
       console.log("Last Epoch Mined:", minerAccount.lastEpochMined.toString());
       console.log("Rewards:", minerAccount.rewards.toString());
-      console.log("Multiplier:", minerAccount.multiplier);
+      console.log("Multiplier:", minerAccount.multiplier / 10_000);
       console.log("Staked Amount:", minerAccount.stakedAmount.toString());
       console.log(
         "Last Staked Timestamp:",
@@ -68,6 +68,20 @@ describe("Fetch Miner", () => {
         ).toISOString()
       );
       console.log("Pool:", minerAccount.pool.toString());
+      console.log("Version:", minerAccount.version);
+      console.log("Hot Key:", minerAccount.signer.toString());
+      console.log("Beneficiary:", minerAccount.beneficiary.toString());
+      console.log(
+        "Bonus rewards:",
+        minerAccount.bonusRewards.map((r) => r.toString())
+      );
+      const stats = minerAccount.stats;
+      console.log("Lifetime solutions:", stats.totalSolutions.toString());
+      console.log("Lifetime rewards earned:", stats.totalRewards.toString());
+      console.log("Lifetime rewards claimed:", stats.totalClaimed.toString());
+      console.log("First epoch mined:", stats.firstEpochMined.toString());
+      console.log("Best difficulty (bits):", stats.bestDifficulty);
+      console.log("Current streak (epochs):", stats.currentStreak);
     } catch (error) {
       console.log("Error fetching miner account:", error);
       console.log(
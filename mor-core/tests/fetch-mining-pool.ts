@@ -46,9 +46,40 @@ describe("Fetch Miner", () => {
       console.log("Mining Pool Account Details:");
       console.log("Mining Pool PDA:", miningPoolPda.toString());
       console.log("Pool Maker:", miningPoolAccount.poolMaker.toString());
+      console.log("Admin:", miningPoolAccount.admin.toString());
       console.log("Mint:", miningPoolAccount.mint.toString());
       console.log("Bump:", miningPoolAccount.bump.toString());
       console.log("Amount in pool:", miningPoolAccount.amount.toString());
+      console.log(
+        "PoW algorithm:",
+        Object.keys(miningPoolAccount.powAlgorithm)[0]
+      );
+      console.log("Mint decimals:", miningPoolAccount.mintDecimals);
+      console.log(
+        "Commission:",
+        `${miningPoolAccount.commissionBps / 100}%`
+      );
+      console.log("Mode:", Object.keys(miningPoolAccount.mode)[0]);
+      console.log("Supply cap:", miningPoolAccount.supplyCap.toString());
+      if (miningPoolAccount.vesting) {
+        console.log(
+          `Vesting: ${miningPoolAccount.vesting.cliffSeconds.toString()}s cliff, over ${miningPoolAccount.vesting.durationSeconds.toString()}s`
+        );
+      }
+      for (const bonus of miningPoolAccount.bonusMints) {
+        console.log(
+          `Bonus mint: ${bonus.mint.toString()}, ${
+            bonus.rateBps / 10_000
+          } per token mined until ${bonus.endsAt.toString()}`
+        );
+      }
+      for (const tier of miningPoolAccount.multiplierTiers) {
+        console.log(
+          `Multiplier tier: from ${tier.minTokens.toString()} tokens, +${
+            tier.bonusBps / 10_000
+          }x and +${tier.bpsPerToken / 10_000}x per extra token`
+        );
+      }
     } catch (error) {
       console.log("Error fetching miner account:", error);
       console.log(
@@ -83,6 +114,10 @@ describe("Fetch Miner", () => {
       console.log("Pool PDA:", miningPoolRewardStateAccount.poolPda.toString());
       console.log("Amount:", miningPoolRewardStateAccount.amount.toString());
       console.log("Bump:", miningPoolRewardStateAccount.bump.toString());
+      console.log(
+        "Maker rewards:",
+        miningPoolRewardStateAccount.makerRewards.toString()
+      );
     } catch (error) {
       console.log("Error fetching miner account:", error);
       console.log(
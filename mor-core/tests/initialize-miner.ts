@@ -50,7 +50,7 @@ describe("Initialize Miner", () => {
 
     // Initialize the miner account
     const tx = await program.methods
-      .initializeMiner()
+      .initializeMiner([]) // the pool has no allowlist
       .accountsStrict({
         miner: minerPda,
         miningPoolPda: miningPoolPda,
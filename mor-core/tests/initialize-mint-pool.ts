@@ -0,0 +1,110 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+import { BN } from "bn.js";
+
+describe("Initialize Mint Authority Pool", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  it("Initialize Pool & Hand Over The Mint Authority", async () => {
+    // A fresh mint, so the test token keeps its authority for the other tests
+    const mint = await createMint(
+      connection,
+      wallet.payer,
+      wallet.publicKey,
+      null,
+      0
+    );
+    const poolMakerAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      wallet.payer,
+      mint,
+      wallet.publicKey
+    );
+
+    const miningPoolPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("mining_pool"), wallet.publicKey.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+    const miningPoolRewardState = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("mining_pool_reward"),
+        wallet.publicKey.toBuffer(),
+        miningPoolPda.toBuffer(),
+      ],
+      program.programId
+    )[0];
+    const mintAuthority = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint"), miningPoolPda.toBuffer()],
+      program.programId
+    )[0];
+    const vault = getAssociatedTokenAddressSync(
+      mint,
+      miningPoolPda,
+      true,
+      TOKEN_PROGRAM_ID
+    );
+    const protocolConfig = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config")],
+      program.programId
+    )[0];
+    const protocolConfigAccount = await program.account.protocolConfig.fetch(
+      protocolConfig
+    );
+
+    const tx = await program.methods
+      .initializePool(new BN(1_000_000), { sha3: {} }, 0, { mintAuthority: {} }) // at most 1M tokens minted
+      .accountsStrict({
+        poolMaker: wallet.publicKey,
+        miningPoolPda: miningPoolPda,
+        miningPoolRewardState: miningPoolRewardState,
+        mintAuthority: mintAuthority,
+        mint: mint,
+        poolMakerAta: poolMakerAta.address,
+        vault: vault,
+        protocolConfig: protocolConfig,
+        treasury: protocolConfigAccount.treasury,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID,
+      })
+      .signers([wallet.payer])
+      .rpc();
+    console.log("Transaction Signature:", tx);
+
+    const mintInfo = await getMint(connection, mint);
+    assert.ok(mintInfo.mintAuthority.equals(mintAuthority));
+
+    const miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.deepEqual(miningPoolAccount.mode, { mintAuthority: {} });
+    assert.equal(miningPoolAccount.supplyCap.toString(), "1000000");
+  });
+});
@@ -12,6 +12,7 @@ import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
 
 import { MorCore } from "../target/types/mor_core";
 import { BN } from "bn.js";
+import { assert } from "chai";
 
 describe("Initialize Mining Pool", () => {
   const wallet = anchor.Wallet.local();
@@ -54,14 +55,16 @@ describe("Initialize Mining Pool", () => {
       program.programId
     )[0];
 
-    miningPoolRewardState = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("mining_pool_reward"),
-        payer.publicKey.toBuffer(),
-        miningPoolPda.toBuffer(),
-      ],
-      program.programId
-    )[0];
+    let rewardStateBump: number;
+    [miningPoolRewardState, rewardStateBump] =
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("mining_pool_reward"),
+          payer.publicKey.toBuffer(),
+          miningPoolPda.toBuffer(),
+        ],
+        program.programId
+      );
 
     console.log("Calculated PDA: ", miningPoolPda.toBase58());
     console.log(
@@ -85,17 +88,34 @@ describe("Initialize Mining Pool", () => {
       TOKEN_PROGRAM_ID
     );
 
+    const mintAuthority = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint"), miningPoolPda.toBuffer()],
+      program.programId
+    )[0];
+
+    // Pools pay the protocol's creation fee to its treasury
+    const protocolConfig = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config")],
+      program.programId
+    )[0];
+    const protocolConfigAccount = await program.account.protocolConfig.fetch(
+      protocolConfig
+    );
+
     const initialMintInfo = await getMint(connection, TEST_TOKEN);
 
     const tx = await program.methods
-      .initializePool(new BN(500 * 10 ** initialMintInfo.decimals)) // 500 tokens
+      .initializePool(new BN(500 * 10 ** initialMintInfo.decimals), { sha3: {} }, 0, { vault: {} }) // 500 tokens, SHA3 PoW, no commission, deposited
       .accountsStrict({
         poolMaker: payer.publicKey,
         miningPoolPda: miningPoolPda,
         miningPoolRewardState: miningPoolRewardState,
+        mintAuthority: mintAuthority,
         mint: TEST_TOKEN,
         poolMakerAta: poolMakerAta,
         vault: vault,
+        protocolConfig: protocolConfig,
+        treasury: protocolConfigAccount.treasury,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SYSTEM_PROGRAM_ID,
@@ -103,5 +123,11 @@ describe("Initialize Mining Pool", () => {
       .signers([payer.payer])
       .rpc();
     console.log("Transaction Signature:", tx);
+
+    // The reward state keeps its own bump, which its seeds constraints check
+    const rewardState = await program.account.miningPoolRewardState.fetch(
+      miningPoolRewardState
+    );
+    assert.equal(rewardState.bump, rewardStateBump);
   });
 });
@@ -0,0 +1,70 @@
+import * as anchor from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+
+import { MorCore } from "../target/types/mor_core";
+import { BN } from "bn.js";
+
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+describe("Initialize Protocol Config", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const payer = provider.wallet as NodeWallet;
+
+  const protocolConfig = PublicKey.findProgramAddressSync(
+    [Buffer.from("protocol_config")],
+    program.programId
+  )[0];
+
+  it("Initialize Protocol Config", async () => {
+    // Only the upgrade authority recorded here may create the config
+    const programData = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_ID
+    )[0];
+
+    const tx = await program.methods
+      .initializeProtocolConfig(
+        payer.publicKey, // the local wallet doubles as the treasury
+        new BN(0.01 * LAMPORTS_PER_SOL), // pool creation fee
+        100 // 1% of every claim
+      )
+      .accountsStrict({
+        admin: payer.publicKey,
+        protocolConfig: protocolConfig,
+        program: program.programId,
+        programData: programData,
+        systemProgram: SYSTEM_PROGRAM_ID,
+      })
+      .signers([payer.payer])
+      .rpc();
+    console.log("Transaction Signature:", tx);
+  });
+
+  it("Fetch Protocol Config", async () => {
+    const config = await program.account.protocolConfig.fetch(protocolConfig);
+    console.log("Admin:", config.admin.toBase58());
+    console.log("Treasury:", config.treasury.toBase58());
+    console.log("Pool creation fee:", config.poolCreationFee.toString());
+    console.log("Protocol fee bps:", config.protocolFeeBps);
+  });
+});
@@ -0,0 +1,124 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("Initialize Reward Shards", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const payer = provider.wallet as NodeWallet;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+  const REWARD_SHARDS = 8;
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const miningPoolRewardState = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool_reward"),
+      payer.publicKey.toBuffer(),
+      miningPoolPda.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const rewardShards = [...Array(REWARD_SHARDS).keys()].map(
+    (index) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("reward_shard"),
+          miningPoolPda.toBuffer(),
+          Buffer.from([index]),
+        ],
+        program.programId
+      )[0]
+  );
+
+  it("Initialize Reward Shards", async () => {
+    const instructions = await Promise.all(
+      rewardShards.map((rewardShard, index) =>
+        program.methods
+          .initializeRewardShard(index)
+          .accountsStrict({
+            payer: payer.publicKey,
+            miningPoolPda: miningPoolPda,
+            miningPoolRewardState: miningPoolRewardState,
+            rewardShard: rewardShard,
+            systemProgram: SYSTEM_PROGRAM_ID,
+          })
+          .instruction()
+      )
+    );
+    const tx = new anchor.web3.Transaction().add(...instructions);
+    const txHash = await provider.sendAndConfirm(tx, [], {
+      commitment: "confirmed",
+    });
+    console.log("Initialize Reward Shards txHash: ", txHash);
+
+    // The shards start with the pool's remaining rewards split between them
+    const rewardState = await program.account.miningPoolRewardState.fetch(
+      miningPoolRewardState
+    );
+    let budgets = new anchor.BN(0);
+    for (const rewardShard of rewardShards) {
+      const shard = await program.account.rewardShard.fetch(rewardShard);
+      budgets = budgets.add(shard.budget);
+    }
+    assert.isTrue(budgets.eq(rewardState.amount));
+  });
+
+  it("Settle the first epoch", async () => {
+    // Splits the pool's remaining rewards across the shards again
+    const txHash = await program.methods
+      .settleEpoch()
+      .accountsStrict({
+        miningPoolPda: miningPoolPda,
+        miningPoolRewardState: miningPoolRewardState,
+      })
+      .remainingAccounts(
+        rewardShards.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .rpc({ commitment: "confirmed" });
+    console.log("Settle Epoch txHash: ", txHash);
+
+    const rewardState = await program.account.miningPoolRewardState.fetch(
+      miningPoolRewardState
+    );
+    console.log("Settled epoch: ", rewardState.settledEpoch.toString());
+    for (const rewardShard of rewardShards) {
+      const shard = await program.account.rewardShard.fetch(rewardShard);
+      console.log(`Shard ${shard.index} budget: ${shard.budget.toString()}`);
+    }
+  });
+});
@@ -0,0 +1,370 @@
+// Submits one solution per miner for many miners at once on the ER, to compare
+// a pool's throughput when all its miners map to one reward shard with when
+// they are spread across the pool's reward shards. Both rounds run the current
+// submit_solution, which writes only the miner and its shard. The one-shard round
+// only approximates the design before the shards, where every solution wrote the
+// pool's reward state.
+//
+// LOAD_TEST_MINERS sets the number of miners per round (default 32). Every
+// solution of both rounds has to land, and spreading the miners has to be at
+// least LOAD_TEST_MIN_SPEEDUP times faster (default 1.5).
+
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { sha3_256 } from "@noble/hashes/sha3";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+import { BN } from "bn.js";
+
+describe("Load Test", () => {
+  const wallet = anchor.Wallet.local();
+  const connection = new anchor.web3.Connection("http://localhost:8899", {
+    wsEndpoint: "ws://127.0.0.1:8900",
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+  anchor.setProvider(provider);
+
+  const providerEphemeralRollup = new anchor.AnchorProvider(
+    new anchor.web3.Connection(
+      process.env.PROVIDER_ENDPOINT || "http://0.0.0.0:7799/",
+      {
+        wsEndpoint: process.env.WS_ENDPOINT || "ws://0.0.0.0:7800/",
+      }
+    ),
+    wallet
+  );
+  const erConnection = providerEphemeralRollup.connection;
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+  const payer = provider.wallet as NodeWallet;
+
+  const MINERS = Number(process.env.LOAD_TEST_MINERS || 32);
+  const MIN_SPEEDUP = Number(process.env.LOAD_TEST_MIN_SPEEDUP || 1.5);
+  const EPOCH_SLOT_LENGTH = 150;
+  const REWARD_SHARDS = 8;
+  // Accounts set up per transaction on the base layer
+  const SETUP_CHUNK = 8;
+
+  let mint: PublicKey;
+  let miningPoolPda: PublicKey;
+  let miningPoolRewardState: PublicKey;
+
+  const rewardShardPda = (index: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("reward_shard"),
+        miningPoolPda.toBuffer(),
+        Buffer.from([index]),
+      ],
+      program.programId
+    )[0];
+
+  const minerPda = (authority: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("miner"), authority.toBuffer(), miningPoolPda.toBuffer()],
+      program.programId
+    )[0];
+
+  const shardIndex = (authority: PublicKey) =>
+    authority.toBytes()[0] % REWARD_SHARDS;
+
+  // Keypairs whose reward shard is picked by `shardOf(i)`
+  const grindMiners = (shardOf: (i: number) => number): Keypair[] =>
+    [...Array(MINERS).keys()].map((i) => {
+      while (true) {
+        const keypair = Keypair.generate();
+        if (shardIndex(keypair.publicKey) === shardOf(i)) {
+          return keypair;
+        }
+      }
+    });
+
+  const inChunks = async <T>(
+    items: T[],
+    run: (chunk: T[]) => Promise<unknown>
+  ) => {
+    for (let i = 0; i < items.length; i += SETUP_CHUNK) {
+      await run(items.slice(i, i + SETUP_CHUNK));
+    }
+  };
+
+  const delegate = async (tx: anchor.web3.Transaction, signers: Keypair[]) => {
+    tx.feePayer = payer.publicKey;
+    tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+    await provider.sendAndConfirm(tx, signers, {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+  };
+
+  // New miners start at difficulty 0. A hash without a leading zero bit earns
+  // no bonus doubling, so the shard's budget decays evenly across solutions.
+  const findNonce = (authority: PublicKey, epoch: number): number => {
+    const epochBuf = Buffer.alloc(8);
+    epochBuf.writeBigUInt64LE(BigInt(epoch));
+    const challenge = sha3_256
+      .create()
+      .update(authority.toBuffer())
+      .update(epochBuf)
+      .digest();
+    for (let nonce = 0; ; nonce++) {
+      const nonceBuf = Buffer.alloc(8);
+      nonceBuf.writeBigUInt64LE(BigInt(nonce));
+      const hash = sha3_256.create().update(challenge).update(nonceBuf).digest();
+      if (hash[0] >= 0x80) {
+        return nonce;
+      }
+    }
+  };
+
+  const setUpMiners = async (miners: Keypair[]) => {
+    await inChunks(miners, async (chunk) => {
+      const tx = new anchor.web3.Transaction();
+      for (const miner of chunk) {
+        tx.add(
+          SystemProgram.transfer({
+            fromPubkey: payer.publicKey,
+            toPubkey: miner.publicKey,
+            lamports: 0.05 * anchor.web3.LAMPORTS_PER_SOL,
+          })
+        );
+      }
+      await provider.sendAndConfirm(tx, [], { commitment: "confirmed" });
+    });
+
+    await inChunks(miners, (chunk) =>
+      Promise.all(
+        chunk.map((miner) =>
+          program.methods
+            .initializeMiner([])
+            .accountsStrict({
+              miner: minerPda(miner.publicKey),
+              miningPoolPda: miningPoolPda,
+              authority: miner.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([miner])
+            .rpc({ commitment: "confirmed" })
+        )
+      )
+    );
+
+    await inChunks(miners, (chunk) =>
+      Promise.all(
+        chunk.map(async (miner) => {
+          const tx = await program.methods
+            .delegate()
+            .accountsPartial({
+              payer: miner.publicKey,
+              minerPda: minerPda(miner.publicKey),
+              miningPoolPda: miningPoolPda,
+            })
+            .transaction();
+          await delegate(tx, [miner]);
+        })
+      )
+    );
+  };
+
+  // Sends every miner's solution at once and waits for all of them to land
+  const runRound = async (name: string, miners: Keypair[]) => {
+    await setUpMiners(miners);
+
+    const slot = await erConnection.getSlot("confirmed");
+    const epoch = Math.floor(slot / EPOCH_SLOT_LENGTH);
+    const { blockhash, lastValidBlockHeight } =
+      await erConnection.getLatestBlockhash();
+
+    const txs = await Promise.all(
+      miners.map(async (miner) => {
+        const tx = await program.methods
+          .submitSolution(
+            new BN(findNonce(miner.publicKey, epoch)),
+            new BN(epoch),
+            new Array(16).fill(0) // no Equi-X digest for SHA3 pools
+          )
+          .accountsStrict({
+            miner: minerPda(miner.publicKey),
+            miningPoolPda: miningPoolPda,
+            miningPoolRewardState: miningPoolRewardState,
+            rewardShard: rewardShardPda(shardIndex(miner.publicKey)),
+            authority: miner.publicKey,
+            signer: miner.publicKey,
+          })
+          .transaction();
+        tx.feePayer = miner.publicKey;
+        tx.recentBlockhash = blockhash;
+        tx.sign(miner);
+        return tx.serialize();
+      })
+    );
+
+    const start = Date.now();
+    const results = await Promise.all(
+      txs.map(async (raw) => {
+        const signature = await erConnection.sendRawTransaction(raw, {
+          skipPreflight: true,
+        });
+        const { value } = await erConnection.confirmTransaction(
+          { signature, blockhash, lastValidBlockHeight },
+          "confirmed"
+        );
+        return value.err === null;
+      })
+    );
+    const seconds = (Date.now() - start) / 1000;
+
+    const landed = results.filter((ok) => ok).length;
+    const throughput = landed / seconds;
+    console.log(
+      `${name}: ${landed}/${miners.length} solutions in ${seconds.toFixed(
+        2
+      )}s, ${throughput.toFixed(1)} solutions/s`
+    );
+    return { landed, throughput };
+  };
+
+  before(async () => {
+    mint = await createMint(connection, payer.payer, payer.publicKey, null, 9);
+    const payerAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      payer.payer,
+      mint,
+      payer.publicKey
+    );
+    await mintTo(
+      connection,
+      payer.payer,
+      mint,
+      payerAta.address,
+      payer.payer,
+      1_000_000 * 10 ** 9
+    );
+
+    miningPoolPda = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("mining_pool"),
+        payer.publicKey.toBuffer(),
+        mint.toBuffer(),
+      ],
+      program.programId
+    )[0];
+    miningPoolRewardState = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("mining_pool_reward"),
+        payer.publicKey.toBuffer(),
+        miningPoolPda.toBuffer(),
+      ],
+      program.programId
+    )[0];
+    const protocolConfig = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config")],
+      program.programId
+    )[0];
+    const protocolConfigAccount = await program.account.protocolConfig.fetch(
+      protocolConfig
+    );
+
+    await program.methods
+      .initializePool(
+        new BN(1_000_000).mul(new BN(10).pow(new BN(9))),
+        { sha3: {} },
+        0,
+        { vault: {} }
+      )
+      .accountsStrict({
+        poolMaker: payer.publicKey,
+        miningPoolPda: miningPoolPda,
+        miningPoolRewardState: miningPoolRewardState,
+        mintAuthority: PublicKey.findProgramAddressSync(
+          [Buffer.from("mint"), miningPoolPda.toBuffer()],
+          program.programId
+        )[0],
+        mint: mint,
+        poolMakerAta: payerAta.address,
+        vault: getAssociatedTokenAddressSync(mint, miningPoolPda, true),
+        protocolConfig: protocolConfig,
+        treasury: protocolConfigAccount.treasury,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const shards = [...Array(REWARD_SHARDS).keys()];
+    const tx = new anchor.web3.Transaction().add(
+      ...(await Promise.all(
+        shards.map((index) =>
+          program.methods
+            .initializeRewardShard(index)
+            .accountsStrict({
+              payer: payer.publicKey,
+              miningPoolPda: miningPoolPda,
+              miningPoolRewardState: miningPoolRewardState,
+              rewardShard: rewardShardPda(index),
+              systemProgram: SystemProgram.programId,
+            })
+            .instruction()
+        )
+      ))
+    );
+    await provider.sendAndConfirm(tx, [], { commitment: "confirmed" });
+
+    await delegate(
+      await program.methods
+        .delegateRewardPool()
+        .accountsPartial({
+          payer: payer.publicKey,
+          miningPoolRewardState: miningPoolRewardState,
+          miningPoolPda: miningPoolPda,
+        })
+        .transaction(),
+      []
+    );
+    for (const index of shards) {
+      await delegate(
+        await program.methods
+          .delegateRewardShard(index)
+          .accountsPartial({
+            payer: payer.publicKey,
+            rewardShard: rewardShardPda(index),
+            miningPoolPda: miningPoolPda,
+          })
+          .transaction(),
+        []
+      );
+    }
+    console.log(`Load test pool ${miningPoolPda} with ${MINERS} miners per round`);
+  });
+
+  it("Compare solution throughput with one shard and with all shards", async () => {
+    const oneShard = await runRound(
+      "All miners in one shard",
+      grindMiners(() => 0)
+    );
+    const allShards = await runRound(
+      `Miners spread across ${REWARD_SHARDS} shards`,
+      grindMiners((i) => i % REWARD_SHARDS)
+    );
+    const speedup = allShards.throughput / oneShard.throughput;
+    console.log(`Spread across shards vs one shard: ${speedup.toFixed(2)}x`);
+
+    assert.equal(oneShard.landed, MINERS, "solutions failed in one shard");
+    assert.equal(allShards.landed, MINERS, "solutions failed across shards");
+    assert.isAtLeast(speedup, MIN_SPEEDUP);
+  });
+});
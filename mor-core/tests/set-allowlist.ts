@@ -0,0 +1,113 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import { sha3_256 } from "@noble/hashes/sha3";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("Set Allowlist", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      wallet.payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  // An allowlist of just the wallet: its root is the wallet's leaf
+  const root = Array.from(
+    sha3_256(Buffer.concat([Buffer.from([0]), wallet.payer.publicKey.toBuffer()]))
+  );
+
+  it("Reject Miners Outside The Allowlist", async () => {
+    const tx = await program.methods
+      .setAllowlist(root)
+      .accountsStrict({
+        admin: wallet.payer.publicKey,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    console.log("Transaction Signature:", tx);
+
+    const miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.deepEqual(miningPoolAccount.allowlistRoot, root);
+
+    // A wallet that is not on the list can't create a miner
+    const outsider = Keypair.generate();
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: wallet.payer.publicKey,
+          toPubkey: outsider.publicKey,
+          lamports: anchor.web3.LAMPORTS_PER_SOL / 10,
+        })
+      )
+    );
+    const outsiderMiner = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("miner"),
+        outsider.publicKey.toBuffer(),
+        miningPoolPda.toBuffer(),
+      ],
+      program.programId
+    )[0];
+    try {
+      await program.methods
+        .initializeMiner([])
+        .accountsStrict({
+          miner: outsiderMiner,
+          miningPoolPda: miningPoolPda,
+          authority: outsider.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([outsider])
+        .rpc();
+      assert.fail("A wallet outside the allowlist created a miner");
+    } catch (error) {
+      assert.include(error.toString(), "NotAllowlisted");
+    }
+  });
+
+  it("Clear The Allowlist", async () => {
+    // Open the pool again for the other tests
+    await program.methods
+      .setAllowlist(null)
+      .accountsStrict({
+        admin: wallet.payer.publicKey,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    const miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.isNull(miningPoolAccount.allowlistRoot);
+  });
+});
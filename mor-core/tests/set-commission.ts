@@ -0,0 +1,71 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("Set Commission", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      wallet.payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  it("Reject Commission Above The Maximum", async () => {
+    try {
+      await program.methods
+        .setCommission(2_001) // MAX_COMMISSION_BPS is 20%
+        .accountsStrict({
+          admin: wallet.payer.publicKey,
+          miningPoolPda: miningPoolPda,
+        })
+        .signers([wallet.payer])
+        .rpc();
+      assert.fail("A commission above the maximum was accepted");
+    } catch (error) {
+      assert.include(error.toString(), "CommissionTooHigh");
+    }
+  });
+
+  it("Set Commission", async () => {
+    const tx = await program.methods
+      .setCommission(500) // 5%
+      .accountsStrict({
+        admin: wallet.payer.publicKey,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    console.log("Transaction Signature:", tx);
+
+    const miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.equal(miningPoolAccount.commissionBps, 500);
+  });
+});
@@ -0,0 +1,90 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("Set Min Stake", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      wallet.payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  it("Reject Anyone But The Pool Maker", async () => {
+    const outsider = Keypair.generate();
+    try {
+      await program.methods
+        .setMinStake(new anchor.BN(0))
+        .accountsStrict({
+          admin: outsider.publicKey,
+          miningPoolPda: miningPoolPda,
+        })
+        .signers([outsider])
+        .rpc();
+      assert.fail("Someone else changed the pool's minimum stake");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidAuthority");
+    }
+  });
+
+  it("Set And Clear The Minimum Stake", async () => {
+    const tx = await program.methods
+      .setMinStake(new anchor.BN(100 * 10 ** 9)) // 100 tokens
+      .accountsStrict({
+        admin: wallet.payer.publicKey,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    console.log("Transaction Signature:", tx);
+
+    let miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.equal(
+      miningPoolAccount.minStake.toString(),
+      (100 * 10 ** 9).toString()
+    );
+
+    // Clear it again so submit-solution can mine without staking
+    await program.methods
+      .setMinStake(new anchor.BN(0))
+      .accountsStrict({
+        admin: wallet.payer.publicKey,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.equal(miningPoolAccount.minStake.toString(), "0");
+  });
+});
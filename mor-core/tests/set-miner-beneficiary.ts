@@ -0,0 +1,81 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("Set Miner Beneficiary", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      wallet.payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const minerPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("miner"),
+      wallet.payer.publicKey.toBuffer(),
+      miningPoolPda.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const setBeneficiary = (beneficiary: PublicKey) =>
+    program.methods
+      .setMinerBeneficiary(beneficiary)
+      .accountsStrict({
+        authority: wallet.payer.publicKey,
+        miner: minerPda,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+  it("Set Beneficiary", async () => {
+    const treasury = Keypair.generate();
+    const tx = await setBeneficiary(treasury.publicKey);
+    console.log("Transaction Signature:", tx);
+
+    const minerAccount = await program.account.minerAccountPoolPda.fetch(
+      minerPda
+    );
+    assert.equal(
+      minerAccount.beneficiary.toString(),
+      treasury.publicKey.toString()
+    );
+  });
+
+  it("Clear Beneficiary", async () => {
+    const tx = await setBeneficiary(PublicKey.default);
+    console.log("Transaction Signature:", tx);
+
+    const minerAccount = await program.account.minerAccountPoolPda.fetch(
+      minerPda
+    );
+    assert.isTrue(minerAccount.beneficiary.equals(PublicKey.default));
+  });
+});
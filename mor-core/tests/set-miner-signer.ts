@@ -0,0 +1,78 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("Set Miner Signer", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      wallet.payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const minerPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("miner"),
+      wallet.payer.publicKey.toBuffer(),
+      miningPoolPda.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const setSigner = (signer: PublicKey) =>
+    program.methods
+      .setMinerSigner(signer)
+      .accountsStrict({
+        authority: wallet.payer.publicKey,
+        miner: minerPda,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+  it("Set Hot Key", async () => {
+    const hotKey = Keypair.generate();
+    const tx = await setSigner(hotKey.publicKey);
+    console.log("Transaction Signature:", tx);
+
+    const minerAccount = await program.account.minerAccountPoolPda.fetch(
+      minerPda
+    );
+    assert.equal(minerAccount.signer.toString(), hotKey.publicKey.toString());
+  });
+
+  it("Clear Hot Key", async () => {
+    const tx = await setSigner(PublicKey.default);
+    console.log("Transaction Signature:", tx);
+
+    const minerAccount = await program.account.minerAccountPoolPda.fetch(
+      minerPda
+    );
+    assert.isTrue(minerAccount.signer.equals(PublicKey.default));
+  });
+});
@@ -0,0 +1,79 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("Set Multiplier Tiers", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      wallet.payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  // Same values as DEFAULT_MULTIPLIER_TIERS
+  const defaultTiers = [
+    { minTokens: new anchor.BN(1), bonusBps: 0, bpsPerToken: 5_000 },
+    { minTokens: new anchor.BN(10), bonusBps: 40_000, bpsPerToken: 5_000 },
+    { minTokens: new anchor.BN(50), bonusBps: 240_000, bpsPerToken: 3_333 },
+    { minTokens: new anchor.BN(100), bonusBps: 400_000, bpsPerToken: 2_000 },
+  ];
+
+  it("Reject Unsorted Tiers", async () => {
+    try {
+      await program.methods
+        .setMultiplierTiers([defaultTiers[1], defaultTiers[0]])
+        .accountsStrict({
+          admin: wallet.payer.publicKey,
+          miningPoolPda: miningPoolPda,
+        })
+        .signers([wallet.payer])
+        .rpc();
+      assert.fail("Unsorted tiers were accepted");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidMultiplierTiers");
+    }
+  });
+
+  it("Set Multiplier Tiers", async () => {
+    const tx = await program.methods
+      .setMultiplierTiers(defaultTiers)
+      .accountsStrict({
+        admin: wallet.payer.publicKey,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    console.log("Transaction Signature:", tx);
+
+    const miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.equal(miningPoolAccount.multiplierTiers.length, defaultTiers.length);
+  });
+});
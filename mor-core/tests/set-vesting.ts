@@ -0,0 +1,97 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("Set Vesting", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      wallet.payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const DAY = 86_400;
+
+  it("Reject A Cliff Longer Than The Duration", async () => {
+    try {
+      await program.methods
+        .setVesting({
+          cliffSeconds: new anchor.BN(60 * DAY),
+          durationSeconds: new anchor.BN(30 * DAY),
+        })
+        .accountsStrict({
+          admin: wallet.payer.publicKey,
+          miningPoolPda: miningPoolPda,
+        })
+        .signers([wallet.payer])
+        .rpc();
+      assert.fail("A cliff longer than the duration was accepted");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidVestingSchedule");
+    }
+  });
+
+  it("Set And Clear Vesting", async () => {
+    const tx = await program.methods
+      .setVesting({
+        cliffSeconds: new anchor.BN(7 * DAY),
+        durationSeconds: new anchor.BN(30 * DAY),
+      })
+      .accountsStrict({
+        admin: wallet.payer.publicKey,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    console.log("Transaction Signature:", tx);
+
+    let miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.equal(
+      miningPoolAccount.vesting.durationSeconds.toString(),
+      (30 * DAY).toString()
+    );
+
+    // Clear it again so claim-rewards pays out directly
+    await program.methods
+      .setVesting(null)
+      .accountsStrict({
+        admin: wallet.payer.publicKey,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.isNull(miningPoolAccount.vesting);
+  });
+});
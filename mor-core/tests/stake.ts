@@ -0,0 +1,129 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("Stake", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      wallet.payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const minerPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("miner"),
+      wallet.payer.publicKey.toBuffer(),
+      miningPoolPda.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const stakeVault = PublicKey.findProgramAddressSync(
+    [Buffer.from("stake_vault"), miningPoolPda.toBuffer()],
+    program.programId
+  )[0];
+
+  const amount = new anchor.BN(10_000_000);
+
+  it("Stake Tokens", async () => {
+    const authorityAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      wallet.payer,
+      TEST_TOKEN,
+      wallet.payer.publicKey
+    );
+    const before = await program.account.minerAccountPoolPda.fetch(minerPda);
+
+    const tx = await program.methods
+      .stake(amount)
+      .accountsStrict({
+        authority: wallet.payer.publicKey,
+        miner: minerPda,
+        miningPoolPda: miningPoolPda,
+        mint: TEST_TOKEN,
+        authorityAta: authorityAta.address,
+        stakeVault: stakeVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    console.log("Transaction Signature:", tx);
+
+    const after = await program.account.minerAccountPoolPda.fetch(minerPda);
+    assert.equal(
+      after.stakedAmount.toString(),
+      before.stakedAmount.add(amount).toString()
+    );
+    assert.isTrue(after.lastStakedTimestamp.gtn(0));
+  });
+
+  it("Unstake Half", async () => {
+    const authorityAta = await getOrCreateAssociatedTokenAccount(
+      connection,
+      wallet.payer,
+      TEST_TOKEN,
+      wallet.payer.publicKey
+    );
+    const before = await program.account.minerAccountPoolPda.fetch(minerPda);
+    const half = before.stakedAmount.divn(2);
+
+    const tx = await program.methods
+      .unstake(half)
+      .accountsStrict({
+        authority: wallet.payer.publicKey,
+        miner: minerPda,
+        miningPoolPda: miningPoolPda,
+        mint: TEST_TOKEN,
+        authorityAta: authorityAta.address,
+        stakeVault: stakeVault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet.payer])
+      .rpc();
+
+    console.log("Transaction Signature:", tx);
+
+    // Half the stake forfeits half the time it was locked
+    const after = await program.account.minerAccountPoolPda.fetch(minerPda);
+    assert.equal(
+      after.stakedAmount.toString(),
+      before.stakedAmount.sub(half).toString()
+    );
+    assert.isTrue(after.lastStakedTimestamp.gte(before.lastStakedTimestamp));
+  });
+});
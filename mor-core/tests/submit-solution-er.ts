@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
-import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { PublicKey } from "@solana/web3.js";
 import { sha3_256 } from "@noble/hashes/sha3";
+import { assert } from "chai";
 
 import { MorCore } from "../target/types/mor_core";
 
@@ -24,6 +25,9 @@ describe("SubmitSolution", () => {
   const MINER_SEED = "miner";
   const MINING_POOL_SEED = "mining_pool";
   const MINING_POOL_REWARD_SEED = "mining_pool_reward";
+  const REWARD_SHARD_SEED = "reward_shard";
+  const REWARD_SHARDS = 8;
+  const SHARD_EPOCH_SLOTS = 9;
 
   let minerPda: PublicKey;
   let miningPoolPda: PublicKey;
@@ -46,6 +50,20 @@ describe("SubmitSolution", () => {
     return Buffer.from(hasher.digest()); // 32 bytes
   };
 
+  // Difficulty is the number of leading zero bits of the hash
+  const leadingZeroBits = (hash: Uint8Array): number => {
+    let bits = 0;
+    for (const byte of hash) {
+      if (byte === 0) {
+        bits += 8;
+        continue;
+      }
+      bits += Math.clz32(byte) - 24;
+      break;
+    }
+    return bits;
+  };
+
   const findValidNonce = (challenge: Buffer, difficulty: number): number => {
     for (let nonce = 0; nonce < Number.MAX_SAFE_INTEGER; nonce++) {
       const nonceBuf = Buffer.alloc(8);
@@ -57,7 +75,7 @@ describe("SubmitSolution", () => {
         .digest();
       // console.log("Nonce: ", nonce);
       // console.log("Hash: ", Array.from(hash.slice(0, 10)));
-      if (hash.slice(0, difficulty).every((b) => b === 0)) {
+      if (leadingZeroBits(hash) >= difficulty) {
         console.log("Found nonce: ", nonce);
         console.log("Hash: ", Array.from(hash));
         return nonce;
@@ -100,6 +118,16 @@ describe("SubmitSolution", () => {
       program.programId
     )[0];
 
+    // Solutions are credited from the authority's reward shard
+    const rewardShard = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(REWARD_SHARD_SEED),
+        miningPoolPda.toBuffer(),
+        Buffer.from([payer.publicKey.toBytes()[0] % REWARD_SHARDS]),
+      ],
+      program.programId
+    )[0];
+
     minerPda = PublicKey.findProgramAddressSync(
       [
         Buffer.from(MINER_SEED),
@@ -131,13 +159,18 @@ describe("SubmitSolution", () => {
     const nonce = findValidNonce(challenge, difficulty);
 
     const method = program.methods
-      .submitSolution(new anchor.BN(nonce), new anchor.BN(epoch))
+      .submitSolution(
+        new anchor.BN(nonce),
+        new anchor.BN(epoch),
+        new Array(16).fill(0) // no Equi-X digest for SHA3 pools
+      )
       .accountsStrict({
         miner: minerPda,
         authority: payer.publicKey,
+        signer: payer.publicKey,
         miningPoolPda: miningPoolPda,
         miningPoolRewardState: miningPoolRewardState,
-        systemProgram: SystemProgram.programId,
+        rewardShard: rewardShard,
       })
       .signers([payer]);
     if (isSimulate) {
@@ -156,6 +189,12 @@ describe("SubmitSolution", () => {
       const tx = await method.rpc();
       console.log("Submitted solution with nonce:", nonce);
       console.log("Transaction signature:", tx);
+
+      // The delegated shard counts the epoch until settle_epoch folds it into its ledger
+      const shard = await program.account.rewardShard.fetch(rewardShard);
+      const stats = shard.epochStats[epoch % SHARD_EPOCH_SLOTS];
+      assert.equal(stats.epoch.toNumber(), epoch);
+      assert.isAbove(stats.submissions, 0);
     }
   };
 
@@ -1,8 +1,9 @@
 // This file is just for testing, we'll only be using ER for mining
 
 import * as anchor from "@coral-xyz/anchor";
-import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
 import { sha3_256 } from "@noble/hashes/sha3";
+import { assert } from "chai";
 
 import { MorCore } from "../target/types/mor_core";
 
@@ -25,10 +26,14 @@ describe("SubmitSolution", () => {
 
   const MINING_POOL_SEED = "mining_pool";
   const MINING_POOL_REWARD_SEED = "mining_pool_reward";
+  const REWARD_SHARD_SEED = "reward_shard";
+  const REWARD_SHARDS = 8;
+  const SHARD_EPOCH_SLOTS = 9;
 
   let minerPda: PublicKey;
   let miningPoolPda: PublicKey;
   let miningPoolRewardState: PublicKey;
+  let submittedEpoch: number;
 
   const payer = wallet.payer;
 
@@ -47,6 +52,20 @@ describe("SubmitSolution", () => {
     return Buffer.from(hasher.digest()); // 32 bytes
   };
 
+  // Difficulty is the number of leading zero bits of the hash
+  const leadingZeroBits = (hash: Uint8Array): number => {
+    let bits = 0;
+    for (const byte of hash) {
+      if (byte === 0) {
+        bits += 8;
+        continue;
+      }
+      bits += Math.clz32(byte) - 24;
+      break;
+    }
+    return bits;
+  };
+
   const findValidNonce = (challenge: Buffer, difficulty: number): number => {
     for (let nonce = 0; nonce < Number.MAX_SAFE_INTEGER; nonce++) {
       const nonceBuf = Buffer.alloc(8);
@@ -58,7 +77,7 @@ describe("SubmitSolution", () => {
         .digest();
       // console.log("Nonce: ", nonce);
       // console.log("Hash: ", Array.from(hash.slice(0, 10)));
-      if (hash.slice(0, difficulty).every((b) => b === 0)) {
+      if (leadingZeroBits(hash) >= difficulty) {
         console.log("Found nonce: ", nonce);
         console.log("Hash: ", Array.from(hash.slice(0, 10)));
         return nonce;
@@ -86,6 +105,16 @@ describe("SubmitSolution", () => {
       program.programId
     )[0];
 
+    // Solutions are credited from the authority's reward shard
+    const rewardShard = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(REWARD_SHARD_SEED),
+        miningPoolPda.toBuffer(),
+        Buffer.from([payer.publicKey.toBytes()[0] % REWARD_SHARDS]),
+      ],
+      program.programId
+    )[0];
+
     minerPda = PublicKey.findProgramAddressSync(
       [
         Buffer.from(MINER_SEED),
@@ -107,13 +136,18 @@ describe("SubmitSolution", () => {
     const nonce = findValidNonce(challenge, difficulty);
 
     const method = program.methods
-      .submitSolution(new anchor.BN(nonce), new anchor.BN(epoch))
+      .submitSolution(
+        new anchor.BN(nonce),
+        new anchor.BN(epoch),
+        new Array(16).fill(0) // no Equi-X digest for SHA3 pools
+      )
       .accountsStrict({
         miner: minerPda,
         authority: payer.publicKey,
+        signer: payer.publicKey,
         miningPoolPda: miningPoolPda,
         miningPoolRewardState: miningPoolRewardState,
-        systemProgram: SystemProgram.programId,
+        rewardShard: rewardShard,
       })
       .signers([payer]);
     if (isSimulate) {
@@ -130,8 +164,17 @@ describe("SubmitSolution", () => {
       }
     } else {
       const tx = await method.rpc();
+      submittedEpoch = epoch;
       console.log("Submitted solution with nonce:", nonce);
       console.log("Transaction signature:", tx);
+
+      // The shard counts the epoch's solutions until settle_epoch folds them into its ledger
+      const shard = await program.account.rewardShard.fetch(rewardShard);
+      const stats = shard.epochStats[epoch % SHARD_EPOCH_SLOTS];
+      assert.equal(stats.epoch.toNumber(), epoch);
+      console.log(
+        `Epoch ${stats.epoch}: ${stats.submissions} solutions, ${stats.totalRewards} rewards, best ${stats.bestDifficulty} bits by ${stats.bestMiner}`
+      );
     }
   };
 
@@ -148,4 +191,88 @@ describe("SubmitSolution", () => {
       attempts++;
     }
   });
+
+  it("Reject a second solution for an epoch already mined", async () => {
+    // The epoch check comes before the PoW check, so any nonce will do
+    const rewardShard = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(REWARD_SHARD_SEED),
+        miningPoolPda.toBuffer(),
+        Buffer.from([payer.publicKey.toBytes()[0] % REWARD_SHARDS]),
+      ],
+      program.programId
+    )[0];
+    const slot = submittedEpoch % SHARD_EPOCH_SLOTS;
+    const before = await program.account.rewardShard.fetch(rewardShard);
+
+    try {
+      await program.methods
+        .submitSolution(
+          new anchor.BN(0),
+          new anchor.BN(submittedEpoch),
+          new Array(16).fill(0)
+        )
+        .accountsStrict({
+          miner: minerPda,
+          authority: payer.publicKey,
+          signer: payer.publicKey,
+          miningPoolPda: miningPoolPda,
+          miningPoolRewardState: miningPoolRewardState,
+          rewardShard: rewardShard,
+        })
+        .signers([payer])
+        .rpc();
+      assert.fail("submit_solution credited an epoch twice");
+    } catch (error) {
+      assert.include(error.toString(), "AlreadyMined");
+    }
+    const after = await program.account.rewardShard.fetch(rewardShard);
+    assert.equal(
+      after.epochStats[slot].submissions,
+      before.epochStats[slot].submissions
+    );
+  });
+
+  it("Reject a solution from a wallet without a miner", async () => {
+    // Miners are only created by create-account, never by a first solution
+    const stranger = Keypair.generate();
+    const strangerMiner = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(MINER_SEED),
+        stranger.publicKey.toBuffer(),
+        miningPoolPda.toBuffer(),
+      ],
+      program.programId
+    )[0];
+    const rewardShard = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(REWARD_SHARD_SEED),
+        miningPoolPda.toBuffer(),
+        Buffer.from([stranger.publicKey.toBytes()[0] % REWARD_SHARDS]),
+      ],
+      program.programId
+    )[0];
+    try {
+      await program.methods
+        .submitSolution(
+          new anchor.BN(0),
+          new anchor.BN(submittedEpoch),
+          new Array(16).fill(0)
+        )
+        .accountsStrict({
+          miner: strangerMiner,
+          authority: stranger.publicKey,
+          signer: stranger.publicKey,
+          miningPoolPda: miningPoolPda,
+          miningPoolRewardState: miningPoolRewardState,
+          rewardShard: rewardShard,
+        })
+        .signers([stranger])
+        .rpc();
+      assert.fail("submit_solution created a miner");
+    } catch (error) {
+      assert.include(error.toString(), "MinerNotInitialized");
+    }
+    assert.isNull(await connection.getAccountInfo(strangerMiner));
+  });
 });
@@ -0,0 +1,182 @@
+// Submits the solutions of several epochs in one transaction, as a miner catching up would
+
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { sha3_256 } from "@noble/hashes/sha3";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("SubmitSolutions", () => {
+  const wallet = anchor.Wallet.local();
+  const connection = new anchor.web3.Connection(
+    "http://localhost:8899",
+    "confirmed"
+  );
+  const provider = new anchor.AnchorProvider(connection, wallet, {});
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const MINER_SEED = "miner";
+  const EPOCH_SLOT_LENGTH = 150;
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const MINING_POOL_SEED = "mining_pool";
+  const MINING_POOL_REWARD_SEED = "mining_pool_reward";
+  const REWARD_SHARD_SEED = "reward_shard";
+  const REWARD_SHARDS = 8;
+  const SHARD_EPOCH_SLOTS = 9;
+
+  let minerPda: PublicKey;
+  let miningPoolPda: PublicKey;
+  let miningPoolRewardState: PublicKey;
+
+  const payer = wallet.payer;
+
+  // Epoch and Challenge helpers
+  const getEpoch = async (): Promise<number> => {
+    const slot = await connection.getSlot("confirmed");
+    return Math.floor(slot / EPOCH_SLOT_LENGTH);
+  };
+
+  const generateChallenge = (authority: PublicKey, epoch: number): Buffer => {
+    const epochBuf = Buffer.alloc(8);
+    epochBuf.writeBigUInt64LE(BigInt(epoch));
+    const hasher = sha3_256.create();
+    hasher.update(authority.toBuffer());
+    hasher.update(epochBuf);
+    return Buffer.from(hasher.digest()); // 32 bytes
+  };
+
+  // Difficulty is the number of leading zero bits of the hash
+  const leadingZeroBits = (hash: Uint8Array): number => {
+    let bits = 0;
+    for (const byte of hash) {
+      if (byte === 0) {
+        bits += 8;
+        continue;
+      }
+      bits += Math.clz32(byte) - 24;
+      break;
+    }
+    return bits;
+  };
+
+  const findValidNonce = (challenge: Buffer, difficulty: number): number => {
+    for (let nonce = 0; nonce < Number.MAX_SAFE_INTEGER; nonce++) {
+      const nonceBuf = Buffer.alloc(8);
+      nonceBuf.writeBigUInt64LE(BigInt(nonce));
+      const hash = sha3_256
+        .create()
+        .update(challenge)
+        .update(nonceBuf)
+        .digest();
+      // console.log("Nonce: ", nonce);
+      // console.log("Hash: ", Array.from(hash.slice(0, 10)));
+      if (leadingZeroBits(hash) >= difficulty) {
+        console.log("Found nonce: ", nonce);
+        console.log("Hash: ", Array.from(hash.slice(0, 10)));
+        return nonce;
+      }
+    }
+    throw new Error("No valid nonce found (increase difficulty?)");
+  };
+
+  it("Submit a batch of PoW solutions", async () => {
+    miningPoolPda = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(MINING_POOL_SEED),
+        payer.publicKey.toBuffer(),
+        TEST_TOKEN.toBuffer(),
+      ],
+      program.programId
+    )[0];
+
+    miningPoolRewardState = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(MINING_POOL_REWARD_SEED),
+        payer.publicKey.toBuffer(),
+        miningPoolPda.toBuffer(),
+      ],
+      program.programId
+    )[0];
+
+    // Solutions are credited from the authority's reward shard
+    const rewardShard = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(REWARD_SHARD_SEED),
+        miningPoolPda.toBuffer(),
+        Buffer.from([payer.publicKey.toBytes()[0] % REWARD_SHARDS]),
+      ],
+      program.programId
+    )[0];
+
+    minerPda = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(MINER_SEED),
+        payer.publicKey.toBuffer(),
+        miningPoolPda.toBuffer(),
+      ],
+      program.programId
+    )[0];
+
+    const minerAccount = await program.account.minerAccountPoolPda.fetch(
+      minerPda
+    );
+    const difficulty = minerAccount.difficulty;
+
+    // Epochs must be increasing and newer than the last one mined
+    const current = await getEpoch();
+    const lastMined = minerAccount.lastEpochMined.eq(
+      new anchor.BN("18446744073709551615")
+    )
+      ? current - 1
+      : minerAccount.lastEpochMined.toNumber();
+    const first = Math.max(current, lastMined + 1);
+    const epochs = [first, first + 1];
+    console.log("Solving epochs", epochs, "for difficulty:", difficulty);
+
+    const solutions = epochs.map((epoch) => ({
+      epoch: new anchor.BN(epoch),
+      nonce: new anchor.BN(
+        findValidNonce(generateChallenge(payer.publicKey, epoch), difficulty)
+      ),
+      digest: new Array(16).fill(0), // no Equi-X digest for SHA3 pools
+    }));
+
+    const before = await program.account.rewardShard.fetch(rewardShard);
+
+    const tx = await program.methods
+      .submitSolutions(solutions)
+      .accountsStrict({
+        miner: minerPda,
+        miningPoolPda: miningPoolPda,
+        miningPoolRewardState: miningPoolRewardState,
+        rewardShard: rewardShard,
+        authority: payer.publicKey,
+        signer: payer.publicKey,
+      })
+      .signers([payer])
+      .rpc();
+    console.log("Transaction signature:", tx);
+
+    const after = await program.account.minerAccountPoolPda.fetch(minerPda);
+    console.log("Last epoch mined:", after.lastEpochMined.toString());
+    console.log("Lifetime solutions:", after.stats.totalSolutions.toString());
+
+    // The shard counts each epoch until settle_epoch folds it into its ledger
+    const shard = await program.account.rewardShard.fetch(rewardShard);
+    for (const epoch of epochs) {
+      const stats = shard.epochStats[epoch % SHARD_EPOCH_SLOTS];
+      const previous = before.epochStats[epoch % SHARD_EPOCH_SLOTS];
+      assert.equal(stats.epoch.toNumber(), epoch);
+      assert.equal(
+        stats.submissions,
+        (previous.epoch.toNumber() === epoch ? previous.submissions : 0) + 1
+      );
+    }
+  });
+});
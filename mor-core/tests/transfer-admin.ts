@@ -0,0 +1,127 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+
+import { MorCore } from "../target/types/mor_core";
+
+describe("Transfer Admin", () => {
+  const wallet = anchor.Wallet.local();
+  let rpcHttpUrl = "http://localhost:8899";
+  let rpcWsUrl = "ws://127.0.0.1:8900";
+
+  const connection = new anchor.web3.Connection(rpcHttpUrl, {
+    wsEndpoint: rpcWsUrl,
+  });
+  const provider = new anchor.AnchorProvider(
+    connection,
+    wallet,
+    anchor.AnchorProvider.defaultOptions()
+  );
+
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.MorCore as anchor.Program<MorCore>;
+
+  const TEST_TOKEN = new PublicKey(
+    "tesnw8eLyAwSR5oGzGogWcAuJhp4pynBzjKvs6kvw9T"
+  );
+
+  const miningPoolPda = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("mining_pool"),
+      wallet.payer.publicKey.toBuffer(),
+      TEST_TOKEN.toBuffer(),
+    ],
+    program.programId
+  )[0];
+
+  const newAdmin = Keypair.generate();
+
+  const proposeAdmin = (admin: Keypair, proposed: PublicKey) =>
+    program.methods
+      .proposeAdmin(proposed)
+      .accountsStrict({
+        admin: admin.publicKey,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([admin])
+      .rpc();
+
+  const acceptAdmin = (admin: Keypair) =>
+    program.methods
+      .acceptAdmin()
+      .accountsStrict({
+        newAdmin: admin.publicKey,
+        miningPoolPda: miningPoolPda,
+      })
+      .signers([admin])
+      .rpc();
+
+  it("Only The Proposed Admin Can Accept", async () => {
+    const tx = await proposeAdmin(wallet.payer, newAdmin.publicKey);
+    console.log("Transaction Signature:", tx);
+
+    let miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.equal(
+      miningPoolAccount.pendingAdmin.toString(),
+      newAdmin.publicKey.toString()
+    );
+    // The current admin stays in charge until the proposal is accepted
+    assert.equal(
+      miningPoolAccount.admin.toString(),
+      wallet.payer.publicKey.toString()
+    );
+
+    try {
+      await acceptAdmin(Keypair.generate());
+      assert.fail("Someone else accepted the proposal");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidAuthority");
+    }
+  });
+
+  it("Hand The Pool Over And Back", async () => {
+    await acceptAdmin(newAdmin);
+
+    let miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.equal(
+      miningPoolAccount.admin.toString(),
+      newAdmin.publicKey.toString()
+    );
+    assert.equal(
+      miningPoolAccount.pendingAdmin.toString(),
+      PublicKey.default.toString()
+    );
+
+    // The pool maker no longer administers the pool
+    try {
+      await program.methods
+        .setMinStake(new anchor.BN(0))
+        .accountsStrict({
+          admin: wallet.payer.publicKey,
+          miningPoolPda: miningPoolPda,
+        })
+        .signers([wallet.payer])
+        .rpc();
+      assert.fail("The former admin changed the pool");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidAuthority");
+    }
+
+    // Hand it back so the other tests can administer the pool
+    await proposeAdmin(newAdmin, wallet.payer.publicKey);
+    await acceptAdmin(wallet.payer);
+
+    miningPoolAccount = await program.account.miningPoolPda.fetch(
+      miningPoolPda
+    );
+    assert.equal(
+      miningPoolAccount.admin.toString(),
+      wallet.payer.publicKey.toString()
+    );
+  });
+});
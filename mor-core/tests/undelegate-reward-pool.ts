@@ -38,6 +38,8 @@ describe("Undelegate Reward Pool", () => {
   let miningPoolPda: PublicKey;
   let miningPoolRewardStatePda: PublicKey;
 
+  const REWARD_SHARDS = 8;
+
   it("Undelegate reward pool from ER", async () => {
     miningPoolPda = anchor.web3.PublicKey.findProgramAddressSync(
       [
@@ -72,6 +74,21 @@ describe("Undelegate Reward Pool", () => {
         miningPoolRewardState: miningPoolRewardStatePda,
         miningPoolPda: miningPoolPda,
       })
+      // The reward shards come back to the base layer with the reward state
+      .remainingAccounts(
+        [...Array(REWARD_SHARDS).keys()].map((index) => ({
+          pubkey: PublicKey.findProgramAddressSync(
+            [
+              Buffer.from("reward_shard"),
+              miningPoolPda.toBuffer(),
+              Buffer.from([index]),
+            ],
+            program.programId
+          )[0],
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
       .transaction();
 
     tx.feePayer = provider.wallet.publicKey;